@@ -0,0 +1,163 @@
+//! # 排序基准测试
+//! 非比较类排序与比较类排序（快速排序、归并排序、std）在各自适用的数据上对比
+//! 1. u32 / i64 随机整数：LSD基数排序
+//! 2. [0, 1)均匀分布的f64：桶排序、LSD基数排序
+//! 3. 范围很小的key：计数排序
+//! 4. 随机字节串：MSD基数排序
+//! 5. 百万级的u32：并行归并排序、并行快速排序，使用全部CPU
+//! ```txt
+//! cargo bench -p algorithm --bench sort
+//! ```
+
+use algorithm::sort::{
+    bucket_sort, counting_sort_by_key, merge_sort, merge_sort_by, merge_sort_by_key, msd_radix_sort, par_merge_sort,
+    par_quick_sort, quick_sort, quick_sort_by, radix_sort,
+};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::thread;
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+/// 并行排序使用的规模
+const LARGE_SIZES: [usize; 2] = [1 << 20, 1 << 22];
+
+/// 简单的线性同余生成器，保证每次运行使用相同的数据
+fn random(n: usize) -> Vec<u64> {
+    let mut x = 0x2545_f491_4f6c_dd1d_u64;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            x
+        })
+        .collect()
+}
+
+/// 参与对比的排序：名称和排序函数
+type NamedSort<T> = (&'static str, fn(&mut [T]));
+
+/// 每次迭代排序input的一个副本
+fn bench_sorts<T: Clone>(c: &mut Criterion, group: &str, input: impl Fn(usize) -> Vec<T>, sorts: &[NamedSort<T>]) {
+    bench_sizes(c, group, &SIZES, input, sorts)
+}
+
+fn bench_sizes<T: Clone>(
+    c: &mut Criterion,
+    group: &str,
+    sizes: &[usize],
+    input: impl Fn(usize) -> Vec<T>,
+    sorts: &[NamedSort<T>],
+) {
+    let mut group = c.benchmark_group(group);
+    if sizes.iter().any(|&n| n >= LARGE_SIZES[0]) {
+        group.sample_size(10);
+    }
+    for &n in sizes {
+        let data = input(n);
+        for (name, sort) in sorts {
+            group.bench_with_input(BenchmarkId::new(*name, n), &data, |b, data| {
+                b.iter_batched_ref(|| data.clone(), |list| sort(list), BatchSize::LargeInput)
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_integers(c: &mut Criterion) {
+    bench_sorts(
+        c,
+        "u32",
+        |n| random(n).into_iter().map(|x| (x >> 32) as u32).collect(),
+        &[
+            ("radix", radix_sort),
+            ("quick", quick_sort),
+            ("merge", merge_sort),
+            ("std_unstable", <[u32]>::sort_unstable),
+        ],
+    );
+    bench_sorts(
+        c,
+        "i64",
+        |n| random(n).into_iter().map(|x| x as i64).collect(),
+        &[
+            ("radix", radix_sort),
+            ("quick", quick_sort),
+            ("merge", merge_sort),
+            ("std_unstable", <[i64]>::sort_unstable),
+        ],
+    );
+}
+
+fn bench_floats(c: &mut Criterion) {
+    bench_sorts(
+        c,
+        "f64_unit",
+        |n| random(n).into_iter().map(|x| (x >> 11) as f64 / (1_u64 << 53) as f64).collect(),
+        &[
+            ("bucket", bucket_sort),
+            ("radix", radix_sort),
+            ("quick", |list| quick_sort_by(list, f64::total_cmp)),
+            ("merge", |list| merge_sort_by(list, f64::total_cmp)),
+            ("std_unstable", |list| list.sort_unstable_by(f64::total_cmp)),
+        ],
+    );
+}
+
+fn bench_small_keys(c: &mut Criterion) {
+    // key只有256种，附带一个payload
+    bench_sorts(
+        c,
+        "small_keys",
+        |n| random(n).into_iter().map(|x| ((x >> 56) as u8, x as u32)).collect(),
+        &[
+            ("counting", |list| counting_sort_by_key(list, |&(k, _)| k as usize)),
+            ("merge", |list| merge_sort_by_key(list, |&(k, _)| k)),
+            ("std_stable", |list| list.sort_by_key(|&(k, _)| k)),
+        ],
+    );
+}
+
+fn bench_byte_strings(c: &mut Criterion) {
+    bench_sorts(
+        c,
+        "byte_strings",
+        |n| {
+            random(n)
+                .into_iter()
+                .map(|x| {
+                    let len = 8 + (x % 16) as usize;
+                    (0..len).map(|i| b'a' + (x.rotate_left(i as u32 * 5) % 26) as u8).collect::<Vec<u8>>()
+                })
+                .collect()
+        },
+        &[
+            ("msd_radix", msd_radix_sort),
+            ("quick", quick_sort),
+            ("merge", merge_sort),
+            ("std_unstable", <[Vec<u8>]>::sort_unstable),
+        ],
+    );
+}
+
+/// 并行排序使用的线程数
+fn threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// 线程数写在组名中，不同机器上的结果不会混在一起
+fn bench_parallel(c: &mut Criterion) {
+    bench_sizes(
+        c,
+        &format!("parallel_{}_threads", threads()),
+        &LARGE_SIZES,
+        |n| random(n).into_iter().map(|x| (x >> 32) as u32).collect(),
+        &[
+            ("merge", merge_sort),
+            ("par_merge", |list| par_merge_sort(list, threads())),
+            ("quick", quick_sort),
+            ("par_quick", |list| par_quick_sort(list, threads())),
+            ("std_stable", <[u32]>::sort),
+        ],
+    );
+}
+
+criterion_group!(benches, bench_integers, bench_floats, bench_small_keys, bench_byte_strings, bench_parallel);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+//! # 图算法
+//! 基于`structure::Graph`的邻接表实现，顶点用编号（usize）表示
+//! ## 遍历
+//! 1. 广度优先搜索（BFS）O(V+E)
+//! 2. 深度优先搜索（DFS）O(V+E)
+//! ## 有向无环图
+//! 1. 拓扑排序 O(V+E)，存在环时返回环上的顶点
+//! 2. 强连通分量（Tarjan）O(V+E)
+//! ## 最短路径
+//! 1. Dijkstra O((V+E)logV)，要求边权非负
+//! 2. Bellman-Ford O(VE)，允许负权，可以检测负环
+//! ## 最小生成树
+//! 1. Kruskal O(ElogE)
+//! 2. Prim O(ElogV)
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::ops::Add;
+
+/// 广度优先、深度优先搜索
+mod traversal;
+/// 拓扑排序
+mod topo_sort;
+/// 最短路径
+mod shortest_path;
+/// 最小生成树
+mod mst;
+/// 强连通分量
+mod scc;
+
+pub use mst::{kruskal, prim, SpanningTree};
+pub use scc::tarjan_scc;
+pub use shortest_path::{bellman_ford, dijkstra, NegativeCycle, ShortestPaths};
+pub use topo_sort::{topological_sort, Cycle};
+pub use traversal::{bfs, dfs};
+
+/// # 边权
+/// 最短路径和最小生成树需要可以相加、比较的权重，`Default::default()`作为零
+pub trait Weight: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<W: Copy + Ord + Add<Output = W> + Default> Weight for W {}
@@ -0,0 +1,155 @@
+//! # 最小生成树
+//! 连接无向图所有顶点、权重和最小的边集合；图不连通时得到最小生成森林
+//! ## Kruskal
+//! 1. 所有边按权重升序排序
+//! 2. 依次检查每条边，两端不在同一个集合中时选中该边并合并集合（并查集）
+//! ## Prim
+//! 1. 从一个顶点出发，维护每个未加入顶点到树的最小边
+//! 2. 每次把距离树最近的顶点加入，并用它的边更新其他顶点（decrease_key）
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::Weight;
+use structure::{Graph, IndexedPriorityQueue, UnionFind};
+
+/// # 生成树
+/// edges为选中的边编号
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree<W> {
+    pub edges: Vec<usize>,
+    pub weight: W,
+}
+
+/// # Kruskal
+/// 权重相同的边按编号顺序选择
+/// ## panic
+/// 传入有向图时panic
+pub fn kruskal<N, W: Weight>(graph: &Graph<N, W>) -> SpanningTree<W> {
+    assert!(!graph.is_directed(), "minimum spanning tree requires an undirected graph");
+    let mut order: Vec<usize> = (0..graph.edge_count()).collect();
+    order.sort_by_key(|&id| *graph.edge(id).unwrap().weight());
+    let mut sets = UnionFind::new(graph.node_count());
+    let mut tree = SpanningTree {
+        edges: Vec::new(),
+        weight: W::default(),
+    };
+    for id in order {
+        let edge = graph.edge(id).unwrap();
+        if sets.union(edge.from(), edge.to()) {
+            tree.edges.push(id);
+            tree.weight = tree.weight + *edge.weight();
+        }
+    }
+    tree
+}
+
+/// # Prim
+/// 按顶点编号依次从尚未加入的顶点出发，得到每个连通分量的生成树
+/// ## panic
+/// 传入有向图时panic
+pub fn prim<N, W: Weight>(graph: &Graph<N, W>) -> SpanningTree<W> {
+    assert!(!graph.is_directed(), "minimum spanning tree requires an undirected graph");
+    let n = graph.node_count();
+    let mut in_tree = vec![false; n];
+    // 每个顶点到树的最小边
+    let mut best_edge: Vec<Option<usize>> = vec![None; n];
+    let mut tree = SpanningTree {
+        edges: Vec::new(),
+        weight: W::default(),
+    };
+    let mut queue = IndexedPriorityQueue::new();
+    for root in 0..n {
+        if in_tree[root] {
+            continue;
+        }
+        queue.push(root, W::default());
+        while let Some((u, w)) = queue.pop() {
+            in_tree[u] = true;
+            if let Some(id) = best_edge[u] {
+                tree.edges.push(id);
+                tree.weight = tree.weight + w;
+            }
+            for (v, id, &w) in graph.edges_from(u) {
+                if in_tree[v] {
+                    continue;
+                }
+                if queue.priority(&v).is_none_or(|&current| w < current) {
+                    queue.push(v, w);
+                    best_edge[v] = Some(id);
+                }
+            }
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn graph(n: usize, edges: &[(usize, usize, u32)]) -> Graph<(), u32> {
+        let mut graph = Graph::new_undirected();
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(a, b, w) in edges {
+            graph.add_edge(a, b, w);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_mst() {
+        let g = graph(7, &[(0, 1, 7), (0, 3, 5), (1, 2, 8), (1, 3, 9), (1, 4, 7), (2, 4, 5), (3, 4, 15), (3, 5, 6), (4, 5, 8), (4, 6, 9), (5, 6, 11)]);
+        let k = kruskal(&g);
+        assert_eq!(39, k.weight);
+        let mut edges = k.edges.clone();
+        edges.sort();
+        assert_eq!(vec![0, 1, 4, 5, 7, 9], edges);
+        let p = prim(&g);
+        assert_eq!(39, p.weight);
+        assert_eq!(6, p.edges.len());
+    }
+
+    #[test]
+    fn test_forest() {
+        let g = graph(5, &[(0, 1, 3), (2, 3, 1), (3, 4, 2), (2, 4, 5), (1, 1, 0)]);
+        assert_eq!(SpanningTree { edges: vec![1, 2, 0], weight: 6 }, kruskal(&g));
+        assert_eq!(6, prim(&g).weight);
+        assert_eq!(SpanningTree { edges: vec![], weight: 0 }, prim(&graph(3, &[])));
+    }
+
+    #[test]
+    #[should_panic(expected = "undirected")]
+    fn test_directed() {
+        kruskal(&Graph::<(), u32>::new_directed());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_prim_matches_kruskal(n in 1_usize..12, edges in prop::collection::vec((0_usize..12, 0_usize..12, 0_u32..20), 0..40)) {
+            let edges: Vec<_> = edges.into_iter().filter(|&(a, b, _)| a < n && b < n).collect();
+            let g = graph(n, &edges);
+            let (k, p) = (kruskal(&g), prim(&g));
+            prop_assert_eq!(k.weight, p.weight);
+            prop_assert_eq!(k.edges.len(), p.edges.len());
+            // 选中的边构成森林：边数 = 顶点数 - 连通分量数
+            let mut sets = UnionFind::new(n);
+            for &(a, b, _) in &edges {
+                sets.union(a, b);
+            }
+            let components = (0..n).filter(|&x| sets.find(x) == x).count();
+            prop_assert_eq!(n - components, p.edges.len());
+            let mut sets = UnionFind::new(n);
+            for &id in &p.edges {
+                let edge = g.edge(id).unwrap();
+                prop_assert!(sets.union(edge.from(), edge.to()));
+            }
+        }
+    }
+}
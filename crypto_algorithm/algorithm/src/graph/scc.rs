@@ -0,0 +1,148 @@
+//! # 强连通分量（Tarjan）
+//! 有向图中互相可达的顶点构成一个强连通分量
+//! 1. DFS时为每个顶点记录访问序号index和low（能回到的最小序号），并把顶点压入栈
+//! 2. 回溯时用子节点的low更新自己的low；遇到仍在栈中的顶点时用它的index更新low
+//! 3. `low == index`的顶点是分量的根，弹栈直到弹出根，弹出的顶点构成一个分量
+//!
+//! 分量按逆拓扑序输出：先输出的分量不依赖后输出的分量
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use structure::Graph;
+
+/// # Tarjan强连通分量
+/// 使用显式栈代替递归，每个分量内的顶点按出栈顺序排列
+pub fn tarjan_scc<N, E>(graph: &Graph<N, E>) -> Vec<Vec<usize>> {
+    let n = graph.node_count();
+    let mut index: Vec<Option<usize>> = vec![None; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+    for root in 0..n {
+        if index[root].is_some() {
+            continue;
+        }
+        // 调用帧：(顶点, 尚未检查的邻居)
+        let mut frames = vec![(root, graph.neighbors(root))];
+        index[root] = Some(counter);
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((node, neighbors)) = frames.last_mut() {
+            let node = *node;
+            if let Some(next) = neighbors.next() {
+                match index[next] {
+                    None => {
+                        index[next] = Some(counter);
+                        low[next] = counter;
+                        counter += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        frames.push((next, graph.neighbors(next)));
+                    }
+                    Some(next_index) if on_stack[next] => low[node] = low[node].min(next_index),
+                    Some(_) => {}
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if Some(low[node]) == index[node] {
+                let mut component = Vec::new();
+                loop {
+                    let member = stack.pop().unwrap();
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Graph<(), ()> {
+        let mut graph = Graph::new_directed();
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(a, b) in edges {
+            graph.add_edge(a, b, ());
+        }
+        graph
+    }
+
+    fn normalize(mut components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        components.iter_mut().for_each(|c| c.sort());
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn test_scc() {
+        let g = graph(8, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)]);
+        let components = tarjan_scc(&g);
+        assert_eq!(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]], normalize(components.clone()));
+        // 逆拓扑序：{3,4,5}被{0,1,2}和{6,7}依赖，最先输出
+        assert_eq!(vec![3, 4, 5], normalize(vec![components[0].clone()])[0]);
+        assert_eq!(Vec::<Vec<usize>>::new(), tarjan_scc(&graph(0, &[])));
+    }
+
+    /// 朴素实现：u和v互相可达时属于同一分量
+    fn naive(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut reach = vec![vec![false; n]; n];
+        for (i, row) in reach.iter_mut().enumerate() {
+            row[i] = true;
+        }
+        for &(a, b) in edges {
+            reach[a][b] = true;
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    reach[i][j] = reach[i][j] || (reach[i][k] && reach[k][j]);
+                }
+            }
+        }
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for (v, row) in reach.iter().enumerate() {
+            match components.iter_mut().find(|c| reach[c[0]][v] && row[c[0]]) {
+                Some(c) => c.push(v),
+                None => components.push(vec![v]),
+            }
+        }
+        components
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_naive(n in 0_usize..10, edges in prop::collection::vec((0_usize..10, 0_usize..10), 0..30)) {
+            let edges: Vec<_> = edges.into_iter().filter(|&(a, b)| a < n && b < n).collect();
+            let components = tarjan_scc(&graph(n, &edges));
+            // 逆拓扑序：每条跨分量的边都指向更早输出的分量
+            let mut which = vec![0; n];
+            for (i, c) in components.iter().enumerate() {
+                c.iter().for_each(|&v| which[v] = i);
+            }
+            prop_assert!(edges.iter().all(|&(a, b)| which[a] >= which[b]));
+            prop_assert_eq!(normalize(naive(n, &edges)), normalize(components));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! # 单源最短路径
+//! ## Dijkstra
+//! 1. 起点距离为0，放入优先队列
+//! 2. 取出距离最小的顶点，此时它的距离已经确定
+//! 3. 松弛它的出边：`dist[u] + w < dist[v]`时更新dist[v]，并在队列中降低v的优先级（decrease_key）
+//!
+//! 贪心正确的前提是边权非负
+//! ## Bellman-Ford
+//! 对所有边进行V-1轮松弛，任意最短路径最多包含V-1条边；
+//! 第V轮仍然可以松弛说明从起点可以到达一个负环
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::Weight;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use structure::{Graph, IndexedPriorityQueue};
+
+/// # 最短路径树
+/// 保存起点到每个顶点的距离和路径上的前驱顶点
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths<W> {
+    source: usize,
+    dist: Vec<Option<W>>,
+    prev: Vec<Option<usize>>,
+}
+
+impl<W: Weight> ShortestPaths<W> {
+    fn new(source: usize, node_count: usize) -> Self {
+        let mut dist = vec![None; node_count];
+        dist[source] = Some(W::default());
+        ShortestPaths {
+            source,
+            dist,
+            prev: vec![None; node_count],
+        }
+    }
+    /// 尝试用`u → v`（权重w）松弛v，成功时返回true
+    fn relax(&mut self, u: usize, v: usize, w: W) -> bool {
+        let Some(du) = self.dist[u] else {
+            return false;
+        };
+        let candidate = du + w;
+        if self.dist[v].is_none_or(|dv| candidate < dv) {
+            self.dist[v] = Some(candidate);
+            self.prev[v] = Some(u);
+            true
+        } else {
+            false
+        }
+    }
+    pub fn source(&self) -> usize {
+        self.source
+    }
+    /// 起点到target的距离，不可达时为None
+    pub fn distance(&self, target: usize) -> Option<W> {
+        self.dist[target]
+    }
+    /// 起点到target的路径（包含两端），不可达时为None
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.dist[target]?;
+        let mut path = vec![target];
+        let mut node = target;
+        while let Some(prev) = self.prev[node] {
+            path.push(prev);
+            node = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// # 负环
+/// 按边的方向给出从起点可达的一个负权环
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle(pub Vec<usize>);
+
+impl Display for NegativeCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph contains a negative cycle through nodes {:?}", self.0)
+    }
+}
+
+impl Error for NegativeCycle {}
+
+/// # Dijkstra
+/// ## panic
+/// 遇到负权边时panic
+pub fn dijkstra<N, W: Weight>(graph: &Graph<N, W>, source: usize) -> ShortestPaths<W> {
+    let mut paths = ShortestPaths::new(source, graph.node_count());
+    let mut done = vec![false; graph.node_count()];
+    let mut queue = IndexedPriorityQueue::new();
+    queue.push(source, W::default());
+    while let Some((u, _)) = queue.pop() {
+        done[u] = true;
+        for (v, _, &w) in graph.edges_from(u) {
+            assert!(w >= W::default(), "dijkstra requires non-negative edge weights");
+            if !done[v] && paths.relax(u, v, w) {
+                let dv = paths.dist[v].unwrap();
+                if !queue.decrease_key(&v, dv) {
+                    queue.push(v, dv);
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// # Bellman-Ford
+/// 某一轮没有任何松弛时提前结束
+pub fn bellman_ford<N, W: Weight>(graph: &Graph<N, W>, source: usize) -> Result<ShortestPaths<W>, NegativeCycle> {
+    let n = graph.node_count();
+    let mut paths = ShortestPaths::new(source, n);
+    for round in 0..n {
+        let mut last_relaxed = None;
+        for u in 0..n {
+            for (v, _, &w) in graph.edges_from(u) {
+                if paths.relax(u, v, w) {
+                    last_relaxed = Some(v);
+                }
+            }
+        }
+        let Some(mut node) = last_relaxed else {
+            return Ok(paths);
+        };
+        if round == n - 1 {
+            // 沿前驱走n步后一定落在环上，再绕环一圈收集顶点
+            for _ in 0..n {
+                node = paths.prev[node].unwrap();
+            }
+            let mut cycle = vec![node];
+            let mut cur = paths.prev[node].unwrap();
+            while cur != node {
+                cycle.push(cur);
+                cur = paths.prev[cur].unwrap();
+            }
+            cycle.reverse();
+            return Err(NegativeCycle(cycle));
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn graph(n: usize, edges: &[(usize, usize, i64)], directed: bool) -> Graph<(), i64> {
+        let mut graph = if directed { Graph::new_directed() } else { Graph::new_undirected() };
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(a, b, w) in edges {
+            graph.add_edge(a, b, w);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let g = graph(6, &[(0, 1, 7), (0, 2, 9), (0, 5, 14), (1, 2, 10), (1, 3, 15), (2, 3, 11), (2, 5, 2), (3, 4, 6), (4, 5, 9)], false);
+        let paths = dijkstra(&g, 0);
+        assert_eq!(0, paths.source());
+        assert_eq!(vec![Some(0), Some(7), Some(9), Some(20), Some(20), Some(11)], paths.dist);
+        assert_eq!(Some(vec![0, 2, 5, 4]), paths.path_to(4));
+        assert_eq!(Some(vec![0]), paths.path_to(0));
+        let g = graph(3, &[(0, 1, 1)], true);
+        assert_eq!(None, dijkstra(&g, 0).path_to(2));
+        assert_eq!(None, dijkstra(&g, 1).distance(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-negative")]
+    fn test_dijkstra_negative() {
+        dijkstra(&graph(2, &[(0, 1, -1)], true), 0);
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let g = graph(5, &[(0, 1, 6), (0, 3, 7), (1, 2, 5), (1, 3, 8), (1, 4, -4), (2, 1, -2), (3, 2, -3), (3, 4, 9), (4, 0, 2), (4, 2, 7)], true);
+        let paths = bellman_ford(&g, 0).unwrap();
+        assert_eq!(vec![Some(0), Some(2), Some(4), Some(7), Some(-2)], paths.dist);
+        assert_eq!(Some(vec![0, 3, 2, 1, 4]), paths.path_to(4));
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let g = graph(5, &[(0, 1, 1), (1, 2, 1), (2, 3, -3), (3, 1, 1), (3, 4, 1)], true);
+        let err = bellman_ford(&g, 0).unwrap_err();
+        let mut cycle = err.0.clone();
+        let start = cycle.iter().position(|&n| n == 1).unwrap();
+        cycle.rotate_left(start);
+        assert_eq!(vec![1, 2, 3], cycle);
+        // 起点不可达的负环不影响结果
+        assert!(bellman_ford(&g, 4).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_dijkstra_matches_bellman_ford(
+            n in 1_usize..12,
+            edges in prop::collection::vec((0_usize..12, 0_usize..12, 0_i64..50), 0..40),
+            directed in any::<bool>(),
+        ) {
+            let edges: Vec<_> = edges.into_iter().filter(|&(a, b, _)| a < n && b < n).collect();
+            let g = graph(n, &edges, directed);
+            for source in 0..n {
+                let fast = dijkstra(&g, source);
+                let slow = bellman_ford(&g, source).unwrap();
+                prop_assert_eq!(&fast.dist, &slow.dist);
+                for target in 0..n {
+                    // 路径的权重和等于距离
+                    if let Some(path) = fast.path_to(target) {
+                        let total: i64 = path.windows(2).map(|p| {
+                            g.edges_from(p[0]).filter(|e| e.0 == p[1]).map(|e| *e.2).min().unwrap()
+                        }).sum();
+                        prop_assert_eq!(fast.distance(target), Some(total));
+                    }
+                }
+            }
+        }
+    }
+}
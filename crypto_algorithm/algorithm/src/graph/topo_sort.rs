@@ -0,0 +1,165 @@
+//! # 拓扑排序
+//! 对有向图的顶点排序，使得每条边`a → b`中a都排在b前面，只有有向无环图（DAG）存在拓扑序
+//! ## Kahn算法
+//! 1. 统计每个顶点的入度，把入度为0的顶点放入队列
+//! 2. 取出队首顶点输出，把它的邻居入度减1，减到0时入队
+//! 3. 输出的顶点数少于总数说明存在环
+//! ## 环检测
+//! 存在环时使用三色DFS找出一个具体的环：访问中（灰色）的顶点再次被访问说明找到了回边
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use structure::Graph;
+
+/// # 环
+/// 按边的方向给出环上的顶点，首尾顶点之间也有一条边
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+/// 字段是公开的，手动构造的空环只输出前缀
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph contains a cycle")?;
+        let Some(first) = self.0.first() else {
+            return Ok(());
+        };
+        write!(f, ": ")?;
+        for node in &self.0 {
+            write!(f, "{} -> ", node)?;
+        }
+        write!(f, "{}", first)
+    }
+}
+
+impl Error for Cycle {}
+
+/// # 拓扑排序
+/// 入度为0的顶点按编号顺序先输出
+/// ## panic
+/// 无向图没有拓扑序，传入无向图时panic
+pub fn topological_sort<N, E>(graph: &Graph<N, E>) -> Result<Vec<usize>, Cycle> {
+    assert!(graph.is_directed(), "topological sort requires a directed graph");
+    let mut in_degree = vec![0_usize; graph.node_count()];
+    for edge in graph.edges() {
+        in_degree[edge.to()] += 1;
+    }
+    let mut queue: VecDeque<usize> = (0..graph.node_count()).filter(|&n| in_degree[n] == 0).collect();
+    let mut order = Vec::with_capacity(graph.node_count());
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for next in graph.neighbors(node) {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+    if order.len() == graph.node_count() {
+        Ok(order)
+    } else {
+        Err(find_cycle(graph))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// 三色DFS找出一个环，调用前必须确定图中有环
+fn find_cycle<N, E>(graph: &Graph<N, E>) -> Cycle {
+    let mut color = vec![Color::White; graph.node_count()];
+    for root in 0..graph.node_count() {
+        if color[root] != Color::White {
+            continue;
+        }
+        // path与stack同步，记录当前DFS路径上的（灰色）顶点
+        let mut path = vec![root];
+        let mut stack = vec![graph.neighbors(root)];
+        color[root] = Color::Gray;
+        while let Some(neighbors) = stack.last_mut() {
+            match neighbors.next() {
+                Some(next) => match color[next] {
+                    Color::White => {
+                        color[next] = Color::Gray;
+                        path.push(next);
+                        stack.push(graph.neighbors(next));
+                    }
+                    Color::Gray => {
+                        let start = path.iter().position(|&n| n == next).unwrap();
+                        return Cycle(path.split_off(start));
+                    }
+                    Color::Black => {}
+                },
+                None => {
+                    color[path.pop().unwrap()] = Color::Black;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    unreachable!("find_cycle called on an acyclic graph")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Graph<(), ()> {
+        let mut graph = Graph::new_directed();
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(a, b) in edges {
+            graph.add_edge(a, b, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_crate_order() {
+        // structure <- algorithm <- tests, structure <- tests
+        let mut deps = Graph::new_directed();
+        let structure = deps.add_node("structure");
+        let algorithm = deps.add_node("algorithm");
+        let tests = deps.add_node("tests");
+        deps.add_edge(structure, algorithm, ());
+        deps.add_edge(algorithm, tests, ());
+        deps.add_edge(structure, tests, ());
+        let order = topological_sort(&deps).unwrap();
+        assert_eq!(vec!["structure", "algorithm", "tests"], order.iter().map(|&n| *deps.node(n).unwrap()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_order_respects_edges() {
+        let edges = [(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)];
+        let order = topological_sort(&graph(6, &edges)).unwrap();
+        let position = |n| order.iter().position(|&x| x == n).unwrap();
+        assert!(edges.iter().all(|&(a, b)| position(a) < position(b)));
+        assert_eq!(Ok(vec![]), topological_sort(&graph(0, &[])));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let err = topological_sort(&graph(5, &[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)])).unwrap_err();
+        assert_eq!(Cycle(vec![1, 2, 3]), err);
+        assert_eq!("graph contains a cycle: 1 -> 2 -> 3 -> 1", err.to_string());
+        assert_eq!(Cycle(vec![0]), topological_sort(&graph(1, &[(0, 0)])).unwrap_err());
+        assert_eq!("graph contains a cycle", Cycle(vec![]).to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "directed graph")]
+    fn test_undirected() {
+        let _ = topological_sort(&Graph::<(), ()>::new_undirected());
+    }
+}
@@ -0,0 +1,104 @@
+//! # 图的遍历
+//! ## 广度优先搜索（BFS）
+//! 使用队列，先访问离起点近的顶点，得到的访问顺序按层展开
+//! ## 深度优先搜索（DFS）
+//! 使用栈，沿一条路径走到底再回溯。这里使用显式栈代替递归，避免深图导致栈溢出，
+//! 访问顺序与递归版本一致（按邻接表顺序先访问第一个邻居）
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::collections::VecDeque;
+use structure::Graph;
+
+/// 从start出发的广度优先访问顺序，只包含可达顶点
+pub fn bfs<N, E>(graph: &Graph<N, E>, start: usize) -> Vec<usize> {
+    let mut visited = vec![false; graph.node_count()];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for next in graph.neighbors(node) {
+            if !visited[next] {
+                visited[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    order
+}
+
+/// 从start出发的深度优先访问顺序（先序），只包含可达顶点
+pub fn dfs<N, E>(graph: &Graph<N, E>, start: usize) -> Vec<usize> {
+    let mut visited = vec![false; graph.node_count()];
+    let mut order = Vec::new();
+    // 栈中保存每个顶点尚未检查的邻居，等价于递归时的调用帧
+    let mut stack = vec![graph.neighbors(start)];
+    visited[start] = true;
+    order.push(start);
+    while let Some(neighbors) = stack.last_mut() {
+        match neighbors.next() {
+            Some(next) => {
+                if !visited[next] {
+                    visited[next] = true;
+                    order.push(next);
+                    stack.push(graph.neighbors(next));
+                }
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///```code
+    ///  0 → 1 → 3
+    ///  ↓   ↓
+    ///  2 → 4    5
+    ///```
+    fn sample() -> Graph<(), ()> {
+        let mut graph = Graph::new_directed();
+        for _ in 0..6 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 1), (0, 2), (1, 3), (1, 4), (2, 4)] {
+            graph.add_edge(a, b, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_bfs() {
+        assert_eq!(vec![0, 1, 2, 3, 4], bfs(&sample(), 0));
+        assert_eq!(vec![5], bfs(&sample(), 5));
+    }
+
+    #[test]
+    fn test_dfs() {
+        assert_eq!(vec![0, 1, 3, 4, 2], dfs(&sample(), 0));
+        assert_eq!(vec![2, 4], dfs(&sample(), 2));
+    }
+
+    #[test]
+    fn test_deep_path() {
+        let mut graph = Graph::new_undirected();
+        for i in 0..100_000 {
+            graph.add_node(());
+            if i > 0 {
+                graph.add_edge(i - 1, i, ());
+            }
+        }
+        assert_eq!(100_000, dfs(&graph, 0).len());
+        assert_eq!((0..100_000).rev().collect::<Vec<_>>(), dfs(&graph, 99_999));
+    }
+}
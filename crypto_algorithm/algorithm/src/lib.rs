@@ -1 +1,6 @@
-mod sort;
\ No newline at end of file
+/// 排序算法
+pub mod sort;
+/// 图算法
+pub mod graph;
+/// 查找算法
+pub mod search;
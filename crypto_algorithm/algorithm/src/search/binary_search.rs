@@ -0,0 +1,121 @@
+//! # 二分查找 O(logn)
+//! 所有变体都归结为`partition_point`：序列的前一部分满足谓词，后一部分不满足，找到分界
+//! ```code
+//! target = 3
+//!            lower_bound   upper_bound
+//!                 ↓           ↓
+//! | 1 | 2 | 2 | 3 | 3 | 3 | 5 | 8 |
+//!   x < 3 ————————|           |
+//!   x <= 3 ———————————————————|
+//! equal_range = 3..6
+//! ```
+//! 每次取[lo, hi)的中点，满足谓词时lo = mid + 1，否则hi = mid，直到lo == hi
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/3
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// # 划分点
+/// list的前一部分满足pred、后一部分不满足，返回第一个不满足pred的位置；全部满足时返回`list.len()`
+pub fn partition_point<T, P: FnMut(&T) -> bool>(list: &[T], mut pred: P) -> usize {
+    let (mut lo, mut hi) = (0, list.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(&list[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// 第一个不小于target的位置
+pub fn lower_bound<T: Ord>(list: &[T], target: &T) -> usize {
+    lower_bound_by(list, target, T::cmp)
+}
+
+/// 使用比较器，list按compare升序
+pub fn lower_bound_by<T, F: FnMut(&T, &T) -> Ordering>(list: &[T], target: &T, mut compare: F) -> usize {
+    partition_point(list, |x| compare(x, target) == Ordering::Less)
+}
+
+/// 第一个key不小于key的位置，list按f升序
+pub fn lower_bound_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &[T], key: &K, mut f: F) -> usize {
+    partition_point(list, |x| f(x) < *key)
+}
+
+/// 第一个大于target的位置
+pub fn upper_bound<T: Ord>(list: &[T], target: &T) -> usize {
+    upper_bound_by(list, target, T::cmp)
+}
+
+/// 使用比较器，list按compare升序
+pub fn upper_bound_by<T, F: FnMut(&T, &T) -> Ordering>(list: &[T], target: &T, mut compare: F) -> usize {
+    partition_point(list, |x| compare(x, target) != Ordering::Greater)
+}
+
+/// 第一个key大于key的位置，list按f升序
+pub fn upper_bound_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &[T], key: &K, mut f: F) -> usize {
+    partition_point(list, |x| f(x) <= *key)
+}
+
+/// 等于target的元素所在的范围，没有时是插入位置处的空范围
+pub fn equal_range<T: Ord>(list: &[T], target: &T) -> Range<usize> {
+    equal_range_by(list, target, T::cmp)
+}
+
+/// 使用比较器，list按compare升序
+pub fn equal_range_by<T, F: FnMut(&T, &T) -> Ordering>(list: &[T], target: &T, mut compare: F) -> Range<usize> {
+    let start = lower_bound_by(list, target, &mut compare);
+    // 上界只可能在start之后
+    start..start + upper_bound_by(&list[start..], target, compare)
+}
+
+/// key等于key的元素所在的范围，list按f升序
+pub fn equal_range_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &[T], key: &K, mut f: F) -> Range<usize> {
+    let start = lower_bound_by_key(list, key, &mut f);
+    start..start + upper_bound_by_key(&list[start..], key, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let list = [1, 2, 2, 3, 3, 3, 5, 8];
+        assert_eq!(3, lower_bound(&list, &3));
+        assert_eq!(6, upper_bound(&list, &3));
+        assert_eq!(3..6, equal_range(&list, &3));
+        assert_eq!(6..6, equal_range(&list, &4));
+        assert_eq!(0..0, equal_range(&list, &0));
+        assert_eq!(8..8, equal_range(&list, &9));
+        assert_eq!(0..0, equal_range(&[], &1));
+        assert_eq!(3, partition_point(&list, |&x| x <= 2));
+        let desc = [9, 7, 7, 4, 1];
+        assert_eq!(1..3, equal_range_by(&desc, &7, |a, b| b.cmp(a)));
+        let people = [("amy", 20), ("bob", 30), ("cat", 30), ("dan", 41)];
+        assert_eq!(1..3, equal_range_by_key(&people, &30, |p| p.1));
+        assert_eq!(3, upper_bound_by_key(&people, &30, |p| p.1));
+        assert_eq!(1, lower_bound_by_key(&people, &21, |p| p.1));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_std(mut list in prop::collection::vec(0_u8..20, 0..100), target in 0_u8..22) {
+            list.sort();
+            prop_assert_eq!(list.partition_point(|&x| x < target), lower_bound(&list, &target));
+            prop_assert_eq!(list.partition_point(|&x| x <= target), upper_bound(&list, &target));
+            let range = equal_range(&list, &target);
+            prop_assert!(list[range.clone()].iter().all(|&x| x == target));
+            prop_assert_eq!(list.iter().filter(|&&x| x == target).count(), range.len());
+        }
+    }
+}
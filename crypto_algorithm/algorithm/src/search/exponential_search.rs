@@ -0,0 +1,97 @@
+//! # 指数查找 O(logi)
+//! 先以1、2、4、8……的步长找到第一个不小于target的位置所在的区间，再在区间内二分
+//! ```code
+//! target = 13
+//!   1   2       4               8                              16
+//!   ↓   ↓       ↓               ↓                               ↓
+//! | 1 | 3 | 4 | 6 | 7 | 9 | 10 | 12 | 13 | 15 | 17 | 20 | 21 | 22 | 25 | 28 | 30 |
+//!                                     |————————— 在[8, 17)中二分 ——————————|
+//! ```
+//! i是target的位置，查找区间的长度不超过i，因此只需要O(logi)次比较；目标靠近开头时比二分查找快
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/3
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::binary_search::partition_point;
+use std::cmp::Ordering;
+
+/// 查找target，找到时返回第一个相等元素的位置，否则返回插入位置
+pub fn exponential_search<T: Ord>(list: &[T], target: &T) -> Result<usize, usize> {
+    exponential_search_by(list, target, T::cmp)
+}
+
+/// 使用比较器，list按compare升序
+pub fn exponential_search_by<T, F: FnMut(&T, &T) -> Ordering>(list: &[T], target: &T, mut compare: F) -> Result<usize, usize> {
+    search(list, |x| compare(x, target))
+}
+
+/// 按key查找，list按f升序
+pub fn exponential_search_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &[T], key: &K, mut f: F) -> Result<usize, usize> {
+    search(list, |x| f(x).cmp(key))
+}
+
+/// probe返回元素与目标比较的结果
+fn search<T, F: FnMut(&T) -> Ordering>(list: &[T], mut probe: F) -> Result<usize, usize> {
+    let mut bound = 1;
+    while bound < list.len() && probe(&list[bound]) == Ordering::Less {
+        bound *= 2;
+    }
+    // list[bound / 2]之前的元素都小于目标，list[bound]不小于目标
+    let start = bound / 2;
+    let end = (bound + 1).min(list.len());
+    let index = start + partition_point(&list[start..end], |x| probe(x) == Ordering::Less);
+    match list.get(index) {
+        Some(x) if probe(x) == Ordering::Equal => Ok(index),
+        _ => Err(index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let list = [1, 3, 4, 6, 7, 9, 10, 12, 13, 15, 17, 20, 21, 22, 25, 28, 30];
+        assert_eq!(Ok(8), exponential_search(&list, &13));
+        assert_eq!(Ok(0), exponential_search(&list, &1));
+        assert_eq!(Ok(16), exponential_search(&list, &30));
+        assert_eq!(Err(0), exponential_search(&list, &0));
+        assert_eq!(Err(1), exponential_search(&list, &2));
+        assert_eq!(Err(17), exponential_search(&list, &31));
+        assert_eq!(Err(0), exponential_search(&[], &1));
+        assert_eq!(Ok(1), exponential_search(&[1, 2, 2, 2, 2], &2));
+        let people = [("amy", 20), ("bob", 30), ("cat", 30), ("dan", 41)];
+        assert_eq!(Ok(1), exponential_search_by_key(&people, &30, |p| p.1));
+        assert_eq!(Err(3), exponential_search_by_key(&people, &35, |p| p.1));
+        assert_eq!(Err(0), exponential_search_by_key(&people[..0], &35, |p| p.1));
+    }
+
+    #[test]
+    fn test_comparisons() {
+        // 目标在开头附近时比较次数与目标的位置有关，与长度无关
+        let list: Vec<u32> = (0..1_000_000).collect();
+        let mut comparisons = 0;
+        let found = exponential_search_by(&list, &5, |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        assert_eq!(Ok(5), found);
+        assert!(comparisons <= 8, "{} comparisons", comparisons);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_lower_bound(mut list in prop::collection::vec(0_u8..50, 0..200), target in 0_u8..52) {
+            list.sort();
+            let index = list.partition_point(|&x| x < target);
+            let expected = if list.get(index) == Some(&target) { Ok(index) } else { Err(index) };
+            prop_assert_eq!(expected, exponential_search(&list, &target));
+            prop_assert_eq!(expected, exponential_search_by_key(&list, &target, |&x| x));
+        }
+    }
+}
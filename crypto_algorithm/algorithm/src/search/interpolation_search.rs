@@ -0,0 +1,110 @@
+//! # 插值查找 平均O(loglogn)
+//! 二分查找总是取中点，插值查找按target在首尾之间的比例估计位置：
+//! ```code
+//! pos = lo + (target - list[lo]) / (list[hi] - list[lo]) * (hi - lo)
+//!
+//! target = 70
+//!  lo                                      hi
+//! | 10 | 20 | 30 | 40 | 50 | 60 | 70 | 80 | 90 |
+//!                                  ↑ (70 - 10) / (90 - 10) * 8 = 6，一次命中
+//! ```
+//! key均匀分布时期望O(loglogn)，分布很不均匀时单纯的插值会退化到O(n)。
+//! 这里一次插值如果没有让区间缩小一半，下一次就改为取中点，最坏情况仍然是O(logn)。
+//!
+//! 位置按key的数值计算，所以只能用于整数key，没有比较器版本
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/3
+//! @version:0.0.1
+//! @description:
+//! ```
+
+/// 查找target，找到时返回第一个相等元素的位置，否则返回插入位置
+pub fn interpolation_search<T: Ord + Copy + Into<i128>>(list: &[T], target: T) -> Result<usize, usize> {
+    interpolation_search_by_key(list, target.into(), |&x| x.into())
+}
+
+/// 按整数key查找，list按f升序
+pub fn interpolation_search_by_key<T, F: FnMut(&T) -> i128>(list: &[T], key: i128, mut f: F) -> Result<usize, usize> {
+    // list[..lo]都小于key，list[hi..]都不小于key
+    let (mut lo, mut hi) = (0, list.len());
+    let mut bisect = false;
+    while lo < hi {
+        let (first, last) = (f(&list[lo]), f(&list[hi - 1]));
+        if key <= first {
+            break;
+        }
+        if key > last {
+            lo = hi;
+            break;
+        }
+        // first < key <= last
+        let pos = if bisect {
+            lo + (hi - lo) / 2
+        } else {
+            let ratio = key.abs_diff(first) as f64 / last.abs_diff(first) as f64;
+            lo + ((ratio * (hi - 1 - lo) as f64) as usize).min(hi - 1 - lo)
+        };
+        let before = hi - lo;
+        if f(&list[pos]) < key {
+            lo = pos + 1;
+        } else {
+            hi = pos;
+        }
+        bisect = (hi - lo) * 2 > before;
+    }
+    match list.get(lo) {
+        Some(x) if f(x) == key => Ok(lo),
+        _ => Err(lo),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let list = [10, 20, 30, 40, 50, 60, 70, 80, 90];
+        assert_eq!(Ok(6), interpolation_search(&list, 70));
+        assert_eq!(Ok(0), interpolation_search(&list, 10));
+        assert_eq!(Ok(8), interpolation_search(&list, 90));
+        assert_eq!(Err(0), interpolation_search(&list, 5));
+        assert_eq!(Err(3), interpolation_search(&list, 35));
+        assert_eq!(Err(9), interpolation_search(&list, 95));
+        assert_eq!(Err(0), interpolation_search::<u8>(&[], 1));
+        assert_eq!(Ok(1), interpolation_search(&[1_u64, 7, 7, 7, u64::MAX], 7));
+        assert_eq!(Ok(2), interpolation_search(&[i64::MIN, -1, 0, i64::MAX], 0));
+        let people = [("amy", 20), ("bob", 30), ("cat", 30), ("dan", 41)];
+        assert_eq!(Ok(1), interpolation_search_by_key(&people, 30, |p| p.1));
+    }
+
+    #[test]
+    fn test_skewed() {
+        // 指数分布：单纯的插值每次只前进一步，这里仍然是O(logn)
+        let list: Vec<u64> = (0..64).map(|i| 1 << i).chain([u64::MAX]).collect();
+        for (i, &x) in list.iter().enumerate() {
+            let mut probes = 0;
+            let found = interpolation_search_by_key(&list, x as i128, |&y| {
+                probes += 1;
+                y as i128
+            });
+            assert_eq!(Ok(i), found);
+            assert!(probes <= 40, "{} probes for {}", probes, x);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_lower_bound(mut list in prop::collection::vec(any::<i32>(), 0..200), target in any::<i32>()) {
+            list.sort();
+            let index = list.partition_point(|&x| x < target);
+            let expected = if list.get(index) == Some(&target) { Ok(index) } else { Err(index) };
+            prop_assert_eq!(expected, interpolation_search(&list, target));
+            for &x in &list {
+                prop_assert_eq!(Ok(list.partition_point(|&y| y < x)), interpolation_search(&list, x));
+            }
+        }
+    }
+}
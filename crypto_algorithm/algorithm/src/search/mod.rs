@@ -0,0 +1,51 @@
+//! # 查找算法
+//! ## 二分查找 O(logn)
+//! 要求序列有序（或者按谓词划分好）
+//! 1. partition_point：第一个不满足谓词的位置
+//! 2. lower_bound：第一个不小于target的位置
+//! 3. upper_bound：第一个大于target的位置
+//! 4. equal_range：所有等于target的元素，`lower_bound..upper_bound`
+//! ## 其他有序查找
+//! 1. 指数查找 O(logi)：exponential_search，目标在位置i，靠近开头时比二分更快，也适合长度未知的序列
+//! 2. 插值查找 O(loglogn)：interpolation_search，按key的数值估计位置，key均匀分布时最快
+//! 3. 三分查找 O(logn)：ternary_search，单峰序列中最大值的位置
+//! ## 第k小的元素
+//! 1. 快速选择 平均O(n)：quickselect
+//! 2. 中位数的中位数 最坏O(n)：median_of_medians
+//! ## 使用
+//! 与`sort`相同，每种查找都提供三个函数：
+//! ```code
+//! lower_bound(&list, &7);                              // T: Ord
+//! lower_bound_by(&list, &7, |a, b| b.cmp(a));          // 比较器，list按比较器有序
+//! lower_bound_by_key(&people, &30, |p| p.age);         // 按key查找
+//! ```
+//! 找到时`exponential_search`和`interpolation_search`返回`Ok(第一个相等元素的位置)`，
+//! 没有找到时返回`Err(插入位置)`，与`slice::binary_search`一致
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/3
+//! @version:0.0.1
+//! @description:
+//! ```
+
+/// 二分查找
+mod binary_search;
+/// 指数查找
+mod exponential_search;
+/// 插值查找
+mod interpolation_search;
+/// 三分查找
+mod ternary_search;
+/// 第k小的元素
+mod select;
+
+pub use binary_search::{
+    equal_range, equal_range_by, equal_range_by_key, lower_bound, lower_bound_by, lower_bound_by_key, partition_point,
+    upper_bound, upper_bound_by, upper_bound_by_key,
+};
+pub use exponential_search::{exponential_search, exponential_search_by, exponential_search_by_key};
+pub use interpolation_search::{interpolation_search, interpolation_search_by_key};
+pub use select::{
+    median_of_medians, median_of_medians_by, median_of_medians_by_key, quickselect, quickselect_by, quickselect_by_key,
+};
+pub use ternary_search::{ternary_search, ternary_search_by, ternary_search_by_key};
@@ -0,0 +1,178 @@
+//! # 第k小的元素
+//! 与快速排序一样划分，但只继续处理k所在的一边：
+//! ```code
+//! k = 2
+//! | 5 | 1 | 8 | 3 | 9 | 2 | 7 |
+//! | 3 | 1 | 2 | 5 | 9 | 8 | 7 |      划分后基准5在位置3 > k，只处理左边
+//! | 1 | 2 | 3 |                      左边排好，list[2] = 3
+//! ```
+//! 返回后`list[k]`是排序后位于k的元素，`list[..k]`都不大于它，`list[k + 1..]`都不小于它
+//! 1. 快速选择：基准三数取中，平均O(n)，特意构造的输入下最坏O(n^2)
+//! 2. 中位数的中位数（BFPRT）：每5个一组取中位数，再递归地取这些中位数的中位数作为基准，
+//!    至少有3/10的元素在基准的每一边，最坏O(n)；常数较大，一般输入比快速选择慢
+//!
+//! 划分和小段的插入排序与`sort::quick_sort`相同
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/3
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use crate::sort::{insert, partition, partition_around_first, Tracker};
+use std::cmp::Ordering;
+
+/// 不超过这个长度的小段直接插入排序
+const INSERTION_CUTOFF: usize = 16;
+
+/// 快速选择：第k小（从0开始）的元素
+/// ## Panics
+/// k不小于`list.len()`时panic
+pub fn quickselect<T: Ord>(list: &mut [T], k: usize) -> &mut T {
+    quickselect_by(list, k, T::cmp)
+}
+
+/// 按key选择
+pub fn quickselect_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], k: usize, mut f: F) -> &mut T {
+    quickselect_by(list, k, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器选择
+pub fn quickselect_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], k: usize, mut compare: F) -> &mut T {
+    assert_in_range(list.len(), k);
+    select(list, k, &mut compare, |list, compare| partition(list, compare, &mut Tracker::new(&mut ())));
+    &mut list[k]
+}
+
+/// 中位数的中位数：第k小（从0开始）的元素，最坏O(n)
+/// ## Panics
+/// k不小于`list.len()`时panic
+pub fn median_of_medians<T: Ord>(list: &mut [T], k: usize) -> &mut T {
+    median_of_medians_by(list, k, T::cmp)
+}
+
+/// 按key选择
+pub fn median_of_medians_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], k: usize, mut f: F) -> &mut T {
+    median_of_medians_by(list, k, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器选择
+pub fn median_of_medians_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], k: usize, mut compare: F) -> &mut T {
+    assert_in_range(list.len(), k);
+    bfprt(list, k, &mut compare);
+    &mut list[k]
+}
+
+fn assert_in_range(len: usize, k: usize) {
+    assert!(k < len, "k is {} but the length is {}", k, len);
+}
+
+/// 把第k小的元素放到list[k]，pivot划分list并返回基准的位置
+fn select<T, F, P>(mut list: &mut [T], mut k: usize, compare: &mut F, mut pivot: P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: FnMut(&mut [T], &mut F) -> usize,
+{
+    loop {
+        if list.len() <= INSERTION_CUTOFF {
+            insert(list, compare, &mut Tracker::new(&mut ()));
+            return;
+        }
+        let p = pivot(list, compare);
+        match k.cmp(&p) {
+            Ordering::Equal => return,
+            Ordering::Less => list = &mut list[..p],
+            Ordering::Greater => {
+                list = &mut list[p + 1..];
+                k -= p + 1;
+            }
+        }
+    }
+}
+
+fn bfprt<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], k: usize, compare: &mut F) {
+    select(list, k, compare, |list, compare| {
+        let p = pivot_of_medians(list, compare);
+        list.swap(0, p);
+        partition_around_first(list, compare, &mut Tracker::new(&mut ()))
+    });
+}
+
+/// 每5个一组，组内的中位数移到list的开头，返回它们的中位数的位置
+fn pivot_of_medians<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], compare: &mut F) -> usize {
+    let groups = list.len() / 5;
+    for g in 0..groups {
+        let group = &mut list[g * 5..g * 5 + 5];
+        insert(group, compare, &mut Tracker::new(&mut ()));
+        // 位置g属于已经处理过的组，可以覆盖
+        list.swap(g, g * 5 + 2);
+    }
+    bfprt(&mut list[..groups], groups / 2, compare);
+    groups / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let mut list = [5, 1, 8, 3, 9, 2, 7];
+        assert_eq!(3, *quickselect(&mut list, 2));
+        assert_eq!(9, *median_of_medians(&mut list, 6));
+        let mut words = ["pear", "fig", "banana", "kiwi"];
+        assert_eq!("banana", *quickselect_by_key(&mut words, 3, |w| w.len()));
+        assert_eq!(8, *median_of_medians_by(&mut [5, 1, 8, 3, 9, 2, 7], 1, |a, b| b.cmp(a)));
+        *quickselect(&mut list, 0) = 0;
+        assert!(list.contains(&0));
+    }
+
+    #[test]
+    #[should_panic(expected = "k is 3 but the length is 3")]
+    fn test_out_of_range() {
+        quickselect(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    fn test_median_of_medians_is_linear() {
+        // 锯齿、有序、全部相等：比较次数都是O(n)
+        let n = 100_000_usize;
+        let inputs: Vec<Vec<usize>> = vec![
+            (0..n).collect(),
+            (0..n).rev().collect(),
+            vec![3; n],
+            (0..n).map(|i| if i % 2 == 0 { i } else { n - i }).collect(),
+        ];
+        for input in inputs {
+            let mut expected = input.clone();
+            expected.sort();
+            let mut list = input;
+            let mut comparisons = 0;
+            let median = *median_of_medians_by(&mut list, n / 2, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+            assert_eq!(expected[n / 2], median);
+            assert!(comparisons < 30 * n, "{} comparisons", comparisons);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_sort(data in prop::collection::vec(0_u16..100, 1..500), k in any::<prop::sample::Index>()) {
+            let k = k.index(data.len());
+            let mut sorted = data.clone();
+            sorted.sort();
+            for select in [quickselect::<u16>, median_of_medians::<u16>] {
+                let mut list = data.clone();
+                let kth = *select(&mut list, k);
+                prop_assert_eq!(sorted[k], kth);
+                prop_assert!(list[..k].iter().all(|&x| x <= kth));
+                prop_assert!(list[k + 1..].iter().all(|&x| x >= kth));
+                list.sort();
+                prop_assert_eq!(&sorted, &list);
+            }
+        }
+    }
+}
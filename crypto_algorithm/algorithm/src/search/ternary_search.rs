@@ -0,0 +1,89 @@
+//! # 三分查找 O(logn)
+//! 在单峰序列（先严格递增、后严格递减）中找最大值。取两个三等分点m1 < m2：
+//! - `list[m1] < list[m2]`：峰不在m1及其左边
+//! - 否则：峰不在m2及其右边
+//! ```code
+//!              m1            m2
+//!               ↓             ↓
+//! | 1 | 3 | 6 | 8 | 12 | 15 | 11 | 7 | 2 |
+//!                 |———— 8 < 11，峰在(m1, hi]中 ————|
+//! ```
+//! 每轮去掉三分之一，剩下不超过三个元素时逐个比较。
+//! 严格递增或严格递减的序列也是单峰的；除了峰以外存在相等的相邻元素时，结果不一定正确
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/3
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::cmp::Ordering;
+
+/// 单峰序列中最大元素的位置，list为空时返回None
+pub fn ternary_search<T: Ord>(list: &[T]) -> Option<usize> {
+    ternary_search_by(list, T::cmp)
+}
+
+/// 按key查找峰
+pub fn ternary_search_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &[T], mut f: F) -> Option<usize> {
+    ternary_search_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器，峰是compare认为最大的元素
+pub fn ternary_search_by<T, F: FnMut(&T, &T) -> Ordering>(list: &[T], mut compare: F) -> Option<usize> {
+    if list.is_empty() {
+        return None;
+    }
+    // 峰在list[lo..=hi]中
+    let (mut lo, mut hi) = (0, list.len() - 1);
+    while hi - lo > 2 {
+        let third = (hi - lo) / 3;
+        let (m1, m2) = (lo + third, hi - third);
+        if compare(&list[m1], &list[m2]) == Ordering::Less {
+            lo = m1 + 1;
+        } else {
+            hi = m2 - 1;
+        }
+    }
+    let mut peak = lo;
+    for i in lo + 1..=hi {
+        if compare(&list[peak], &list[i]) == Ordering::Less {
+            peak = i;
+        }
+    }
+    Some(peak)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        assert_eq!(Some(5), ternary_search(&[1, 3, 6, 8, 12, 15, 11, 7, 2]));
+        assert_eq!(None, ternary_search::<u8>(&[]));
+        assert_eq!(Some(0), ternary_search(&[4]));
+        assert_eq!(Some(5), ternary_search(&[0, 1, 2, 3, 4, 5]));
+        assert_eq!(Some(0), ternary_search(&[5, 4, 3, 2, 1, 0]));
+        // 山谷：按相反的顺序比较
+        assert_eq!(Some(3), ternary_search_by(&[9, 7, 4, 1, 2, 8], |a, b| b.cmp(a)));
+        let heights = [("a", 150), ("b", 180), ("c", 170)];
+        assert_eq!(Some(1), ternary_search_by_key(&heights, |h| h.1));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_finds_peak(values in prop::collection::btree_set(any::<i32>(), 1..200), split in any::<prop::sample::Index>()) {
+            // 互不相同的值，升序的一部分接上降序的另一部分
+            let values: Vec<i32> = values.into_iter().collect();
+            let peak = *values.last().unwrap();
+            let rest = &values[..values.len() - 1];
+            let split = split.index(rest.len() + 1);
+            let mut list: Vec<i32> = rest[..split].to_vec();
+            list.push(peak);
+            list.extend(rest[split..].iter().rev());
+            prop_assert_eq!(Some(split), ternary_search(&list));
+        }
+    }
+}
@@ -1,31 +1,82 @@
-/// 冒泡排序 O(n^2)
-/// 循环进行排序，检测元素1与元素2进行比较，若元素1>元素2，元素1与元素2进行交换，后面继续
-/// ```code
-///   |———|
-///   |   ↓
-/// |————————————————————|
-/// | 4 | 1 | 2 | 10 | 7 |
-/// |————————————————————|
-///           ↓ first
-/// |————————————————————|
-/// | 1 | 4 | 2 | 10 | 7 |   ......
-/// |————————————————————|
-/// ```
-pub fn bubble_sort<T: PartialOrd>(eles: &mut [T]) {
+//! # 冒泡排序 O(n^2)
+//! 循环进行排序，检测元素1与元素2进行比较，若元素1>元素2，元素1与元素2进行交换，后面继续
+//! ```code
+//!   |———|
+//!   |   ↓
+//! |————————————————————|
+//! | 4 | 1 | 2 | 10 | 7 |
+//! |————————————————————|
+//!           ↓ first
+//! |————————————————————|
+//! | 1 | 4 | 2 | 10 | 7 |   ......
+//! |————————————————————|
+//! ```
+//! 一轮中没有发生交换说明已经有序，可以提前结束；只交换严格逆序的相邻元素，因此是稳定的。
+//! 上面的图可以用`trace`和`render`生成
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/9
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
+
+/// 冒泡排序，升序
+pub fn bubble_sort<T: Ord>(eles: &mut [T]) {
+    bubble_sort_by(eles, T::cmp)
+}
+
+/// 按key升序
+pub fn bubble_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(eles: &mut [T], mut f: F) {
+    bubble_sort_by(eles, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn bubble_sort_by<T, F: FnMut(&T, &T) -> Ordering>(eles: &mut [T], mut compare: F) {
+    bubble(eles, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+fn bubble<T, F, O>(eles: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
     for o in 0..eles.len() {
         let mut sorted = true;
         for i in 0..(eles.len() - 1) - o {
-            if eles[i] > eles[i + 1] {
-                eles.swap(i, i + 1);
+            if tracker.cmp(eles, i, i + 1, compare) == Ordering::Greater {
+                tracker.swap(eles, i, i + 1);
                 sorted = false;
             }
         }
+        tracker.pass_complete::<T>();
         if sorted {
             return;
         }
     }
 }
 
+/// # 冒泡排序器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BubbleSort;
+
+impl<T> Sorter<T> for BubbleSort {
+    fn name(&self) -> &'static str {
+        "bubble"
+    }
+    fn is_stable(&self) -> bool {
+        true
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        bubble_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        bubble(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -34,7 +85,20 @@ mod tests {
     #[test]
     fn test_bubble() {
         let mut origin = vec![4, 1, 2, 10, 7];
-        let _ = bubble_sort(&mut origin);
+        bubble_sort(&mut origin);
         assert_eq!(vec![1, 2, 4, 7, 10], origin);
+        let mut empty: [u8; 0] = [];
+        bubble_sort(&mut empty);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bubble_by() {
+        let mut origin = vec![4, 1, 2, 10, 7];
+        bubble_sort_by(&mut origin, |a, b| b.cmp(a));
+        assert_eq!(vec![10, 7, 4, 2, 1], origin);
+        // 稳定：key相同的元素保持原来的顺序
+        let mut pairs = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+        bubble_sort_by_key(&mut pairs, |&(k, _)| k);
+        assert_eq!(vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')], pairs);
+    }
+}
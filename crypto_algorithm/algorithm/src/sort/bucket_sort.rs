@@ -0,0 +1,77 @@
+//! # 桶排序 O(n+k)
+//! 把[0, 1)平均分成n个桶，`x`放入第`floor(x * n)`个桶，每个桶内插入排序后依次取出
+//! ```code
+//! | 0.42 | 0.07 | 0.91 | 0.45 |     n = 4
+//!   [0, 0.25): 0.07
+//!   [0.25, 0.5): 0.42, 0.45
+//!   [0.5, 0.75):
+//!   [0.75, 1): 0.91
+//! ```
+//! 输入均匀分布时每个桶期望只有O(1)个元素，总体期望O(n)；分布不均匀时退化为插入排序
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/28
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::insert_sort::insert_sort_by;
+
+/// # 桶排序，升序
+/// ## Panics
+/// 存在不在[0, 1)中的元素（包括NaN）时panic
+pub fn bucket_sort(list: &mut [f64]) {
+    let n = list.len();
+    let bucket = |x: f64| ((x * n as f64) as usize).min(n - 1);
+    // 桶按顺序存放在一个数组中，starts[b]是第b个桶的起点，避免为每个桶分配Vec
+    let mut starts = vec![0_usize; n + 1];
+    for &x in list.iter() {
+        assert!((0.0..1.0).contains(&x), "bucket_sort expects values in [0, 1), got {}", x);
+        starts[bucket(x) + 1] += 1;
+    }
+    for b in 1..=n {
+        starts[b] += starts[b - 1];
+    }
+    let mut next = starts.clone();
+    let mut buf = vec![0.0; n];
+    for &x in list.iter() {
+        let b = bucket(x);
+        buf[next[b]] = x;
+        next[b] += 1;
+    }
+    for b in 0..n {
+        insert_sort_by(&mut buf[starts[b]..starts[b + 1]], f64::total_cmp);
+    }
+    list.copy_from_slice(&buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let mut list = vec![0.42, 0.07, 0.91, 0.45, 0.0, 0.999];
+        bucket_sort(&mut list);
+        assert_eq!(vec![0.0, 0.07, 0.42, 0.45, 0.91, 0.999], list);
+        bucket_sort(&mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "expects values in [0, 1)")]
+    fn test_out_of_range() {
+        bucket_sort(&mut [0.5, 1.0]);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_std(data in prop::collection::vec(0.0..1.0_f64, 0..300)) {
+            let mut expected = data.clone();
+            expected.sort_by(f64::total_cmp);
+            let mut actual = data;
+            bucket_sort(&mut actual);
+            prop_assert_eq!(expected, actual);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! # 计数排序 O(n+k)
+//! key是较小范围内的整数时，统计每个key出现的次数，由次数的前缀和直接得到每个元素的位置
+//! ```code
+//! keys:   | 3 | 1 | 3 | 0 |
+//! counts: [0]=1 [1]=1 [2]=0 [3]=2
+//! starts: [0]=0 [1]=1 [2]=2 [3]=2     前缀和：key为3的元素从位置2开始
+//! dest:   | 2 | 1 | 3 | 0 |           按原来的顺序依次分配，相同key保持原来的顺序（稳定）
+//! ```
+//! k为key的范围（最大key - 最小key + 1），需要O(k)的计数数组，适合k不超过n太多的场景。
+//! k超过`MAX_SPAN_PER_ELEMENT * n + MIN_SPAN`时不再分配计数数组，改为对下标按key做稳定的比较排序（O(n log n)），
+//! 所以key覆盖整个`usize`范围也不会溢出或者分配过多内存。
+//! 元素不需要`Clone`：计算出每个元素的目标位置后，沿着置换环原地交换
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/28
+//! @version:0.0.1
+//! @description:
+//! ```
+
+/// 计数数组的长度不超过每个元素这么多个
+const MAX_SPAN_PER_ELEMENT: usize = 8;
+/// 元素很少时也允许的计数数组长度
+const MIN_SPAN: usize = 256;
+
+/// # 计数排序，按key升序
+/// key对每个元素只计算一次，稳定
+pub fn counting_sort_by_key<T, F: FnMut(&T) -> usize>(list: &mut [T], f: F) {
+    let keys: Vec<usize> = list.iter().map(f).collect();
+    let (Some(&min), Some(&max)) = (keys.iter().min(), keys.iter().max()) else {
+        return;
+    };
+    // 最大key - 最小key，计数数组的长度为span + 1
+    let span = max - min;
+    let mut dest = if span < keys.len().saturating_mul(MAX_SPAN_PER_ELEMENT).saturating_add(MIN_SPAN) {
+        counting_dest(&keys, min, span)
+    } else {
+        sorted_dest(&keys)
+    };
+    // 把第i个元素交换到目标位置，换回来的元素继续处理，直到第i个位置放入正确的元素
+    for i in 0..list.len() {
+        while dest[i] != i {
+            let d = dest[i];
+            list.swap(i, d);
+            dest.swap(i, d);
+        }
+    }
+}
+
+/// 由计数的前缀和得到每个元素的目标位置
+fn counting_dest(keys: &[usize], min: usize, span: usize) -> Vec<usize> {
+    let mut starts = vec![0_usize; span + 1];
+    for &key in keys {
+        starts[key - min] += 1;
+    }
+    let mut offset = 0;
+    for start in starts.iter_mut() {
+        let count = *start;
+        *start = offset;
+        offset += count;
+    }
+    keys.iter()
+        .map(|&key| {
+            let pos = starts[key - min];
+            starts[key - min] += 1;
+            pos
+        })
+        .collect()
+}
+
+/// key的范围太大时，对下标按key稳定排序得到每个元素的目标位置
+fn sorted_dest(keys: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by_key(|&i| keys[i]);
+    let mut dest = vec![0; keys.len()];
+    for (pos, &i) in order.iter().enumerate() {
+        dest[i] = pos;
+    }
+    dest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let mut list = vec![(3, 'a'), (1, 'b'), (3, 'c'), (0, 'd')];
+        counting_sort_by_key(&mut list, |&(k, _)| k);
+        assert_eq!(vec![(0, 'd'), (1, 'b'), (3, 'a'), (3, 'c')], list);
+        let mut words = vec![String::from("ccc"), String::from("a"), String::from("bb")];
+        counting_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(vec!["a", "bb", "ccc"], words);
+        counting_sort_by_key(&mut Vec::<u8>::new(), |&x| x as usize);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_full_range_keys() {
+        // 翻转符号位，i64::MIN和i64::MAX分别对应0和u64::MAX，范围覆盖整个u64
+        let key = |&(x, _): &(i64, char)| (x as u64 ^ 1 << 63) as usize;
+        let mut list = vec![(i64::MAX, 'a'), (0, 'b'), (i64::MIN, 'c'), (i64::MAX, 'd'), (-1, 'e')];
+        counting_sort_by_key(&mut list, key);
+        assert_eq!(vec![(i64::MIN, 'c'), (-1, 'e'), (0, 'b'), (i64::MAX, 'a'), (i64::MAX, 'd')], list);
+        let mut list = vec![usize::MAX, 0, 7, usize::MAX - 1];
+        counting_sort_by_key(&mut list, |&x| x);
+        assert_eq!(vec![0, 7, usize::MAX - 1, usize::MAX], list);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_sparse_keys(data in prop::collection::vec((any::<usize>(), any::<u16>()), 0..300)) {
+            let mut expected = data.clone();
+            expected.sort_by_key(|&(k, _)| k);
+            let mut actual = data;
+            counting_sort_by_key(&mut actual, |&(k, _)| k);
+            prop_assert_eq!(expected, actual);
+        }
+
+        #[test]
+        fn prop_matches_std(data in prop::collection::vec((1000_usize..1100, any::<u16>()), 0..300)) {
+            let mut expected = data.clone();
+            expected.sort_by_key(|&(k, _)| k);
+            let mut actual = data;
+            counting_sort_by_key(&mut actual, |&(k, _)| k);
+            prop_assert_eq!(expected, actual);
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! # 外部排序
+//! 数据放不进内存时，分两个阶段排序：
+//! 1. 切分：读入不超过内存预算的记录，排序后写入临时文件，每个临时文件是一个有序段（run）
+//! 2. 合并：用多路归并`kway_merge`合并所有有序段，写入输出
+//! ```code
+//! input ─→ | r3 r1 r2 | r6 r4 r5 | r8 r7 |      每段不超过memory_limit
+//!              ↓ 排序      ↓           ↓
+//! runs:    | r1 r2 r3 | r4 r5 r6 | r7 r8 |      临时文件
+//!              ↘           ↓        ↙
+//!                   kway_merge(小顶堆)
+//!                          ↓
+//! output:  | r1 r2 r3 r4 r5 r6 r7 r8 |
+//! ```
+//! 每个有序段读取时需要一个缓冲区，同时合并的段数（fan-in）按内存预算计算；
+//! 段数超过fan-in时先把相邻的段合并成更长的段，直到可以一次合并完。
+//! 切分、合并都是稳定的，相等的记录保持输入中的顺序
+//!
+//! 记录可以是按行分隔（`\n`，输出时每条记录后都有`\n`）或者定长的二进制。
+//! 临时文件使用`tempfile`创建，排序结束或出错时自动删除
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/2
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::kway_merge::kway_merge_by;
+use super::merge_sort_by;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, Write};
+use std::mem;
+use std::path::PathBuf;
+
+/// 读取每个有序段使用的缓冲区大小
+const RUN_BUFFER: usize = 8 * 1024;
+/// 同时合并的段数上限，避免打开过多的文件
+const MAX_FAN_IN: usize = 64;
+
+/// # 记录格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// 按`\n`分隔，记录中不包含`\n`
+    Lines,
+    /// 每条记录固定的字节数
+    Fixed(usize),
+}
+
+/// # 排序统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExternalSortStats {
+    /// 记录数
+    pub records: u64,
+    /// 切分阶段产生的有序段数
+    pub runs: usize,
+    /// 合并的轮数，只有一个段时为0
+    pub merge_passes: usize,
+}
+
+/// # 外部排序
+/// ```code
+/// let stats = ExternalSort::new(RecordFormat::Lines, 64 << 20)
+///     .temp_dir("/data/tmp")
+///     .sort(BufReader::new(input), BufWriter::new(output))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExternalSort {
+    format: RecordFormat,
+    memory_limit: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl ExternalSort {
+    /// memory_limit是切分时缓存记录使用的字节数，每条记录额外计入一个`Vec`的大小
+    /// ## Panics
+    /// 定长记录的长度为0时panic
+    pub fn new(format: RecordFormat, memory_limit: usize) -> Self {
+        assert!(format != RecordFormat::Fixed(0), "fixed-width records must not be empty");
+        ExternalSort {
+            format,
+            memory_limit,
+            temp_dir: None,
+        }
+    }
+
+    /// 临时文件所在的目录，默认使用系统的临时目录
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    /// 按字节序升序排序
+    pub fn sort<R: BufRead, W: Write>(&self, input: R, output: W) -> io::Result<ExternalSortStats> {
+        self.sort_by(input, output, |a, b| a.cmp(b))
+    }
+
+    /// 使用比较器排序
+    pub fn sort_by<R, W, F>(&self, mut input: R, mut output: W, compare: F) -> io::Result<ExternalSortStats>
+    where
+        R: BufRead,
+        W: Write,
+        F: Fn(&[u8], &[u8]) -> Ordering,
+    {
+        let mut stats = ExternalSortStats::default();
+        let mut runs = Vec::new();
+        let mut batch: Vec<Vec<u8>> = Vec::new();
+        let mut used = 0;
+        let mut record = Vec::new();
+        while self.read_record(&mut input, &mut record)? {
+            let size = record.len() + mem::size_of::<Vec<u8>>();
+            if !batch.is_empty() && used + size > self.memory_limit {
+                runs.push(self.write_run(&mut batch, &compare)?);
+                used = 0;
+            }
+            used += size;
+            stats.records += 1;
+            batch.push(mem::take(&mut record));
+        }
+        if runs.is_empty() {
+            // 全部记录都在内存中，不需要临时文件
+            stats.runs = usize::from(!batch.is_empty());
+            merge_sort_by(&mut batch, |a, b| compare(a, b));
+            for record in &batch {
+                self.write_record(&mut output, record)?;
+            }
+            output.flush()?;
+            return Ok(stats);
+        }
+        if !batch.is_empty() {
+            runs.push(self.write_run(&mut batch, &compare)?);
+        }
+        drop(batch);
+        stats.runs = runs.len();
+        let fan_in = (self.memory_limit / RUN_BUFFER).clamp(2, MAX_FAN_IN);
+        while runs.len() > fan_in {
+            stats.merge_passes += 1;
+            let mut merged = Vec::with_capacity(runs.len().div_ceil(fan_in));
+            let mut pending = runs.into_iter();
+            loop {
+                let group: Vec<File> = pending.by_ref().take(fan_in).collect();
+                if group.is_empty() {
+                    break;
+                }
+                let mut file = self.temp_file()?;
+                self.merge(group, BufWriter::new(&mut file), &compare)?;
+                file.rewind()?;
+                merged.push(file);
+            }
+            runs = merged;
+        }
+        stats.merge_passes += 1;
+        self.merge(runs, &mut output, &compare)?;
+        Ok(stats)
+    }
+
+    /// 读取一条记录到record，没有更多记录时返回false
+    fn read_record<R: BufRead>(&self, input: &mut R, record: &mut Vec<u8>) -> io::Result<bool> {
+        record.clear();
+        match self.format {
+            RecordFormat::Lines => {
+                if input.read_until(b'\n', record)? == 0 {
+                    return Ok(false);
+                }
+                if record.last() == Some(&b'\n') {
+                    record.pop();
+                }
+                Ok(true)
+            }
+            RecordFormat::Fixed(width) => {
+                let read = input.by_ref().take(width as u64).read_to_end(record)?;
+                match read {
+                    0 => Ok(false),
+                    _ if read == width => Ok(true),
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("trailing partial record of {} bytes, expected {}", read, width),
+                    )),
+                }
+            }
+        }
+    }
+
+    fn write_record<W: Write>(&self, output: &mut W, record: &[u8]) -> io::Result<()> {
+        output.write_all(record)?;
+        if self.format == RecordFormat::Lines {
+            output.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    fn temp_file(&self) -> io::Result<File> {
+        match &self.temp_dir {
+            Some(dir) => tempfile::tempfile_in(dir),
+            None => tempfile::tempfile(),
+        }
+    }
+
+    /// 排序batch写入临时文件，返回指向文件开头的文件
+    fn write_run<F: Fn(&[u8], &[u8]) -> Ordering>(&self, batch: &mut Vec<Vec<u8>>, compare: &F) -> io::Result<File> {
+        merge_sort_by(batch, |a, b| compare(a, b));
+        let mut file = self.temp_file()?;
+        let mut writer = BufWriter::new(&mut file);
+        for record in batch.drain(..) {
+            self.write_record(&mut writer, &record)?;
+        }
+        writer.flush()?;
+        drop(writer);
+        file.rewind()?;
+        Ok(file)
+    }
+
+    /// 多路归并runs，写入output
+    fn merge<W, F>(&self, runs: Vec<File>, mut output: W, compare: &F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&[u8], &[u8]) -> Ordering,
+    {
+        let sources = runs.into_iter().map(|file| Records {
+            sorter: self,
+            input: BufReader::with_capacity(RUN_BUFFER, file),
+        });
+        // 读取出错时错误排在最前面，下一次就会取出
+        let merged = kway_merge_by(sources, |a: &io::Result<Vec<u8>>, b: &io::Result<Vec<u8>>| match (a, b) {
+            (Ok(a), Ok(b)) => compare(a, b),
+            (Err(_), _) => Ordering::Less,
+            (_, Err(_)) => Ordering::Greater,
+        });
+        for record in merged {
+            self.write_record(&mut output, &record?)?;
+        }
+        output.flush()
+    }
+}
+
+/// 依次读取有序段中的记录
+struct Records<'a, R> {
+    sorter: &'a ExternalSort,
+    input: R,
+}
+
+impl<R: BufRead> Iterator for Records<'_, R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = Vec::new();
+        match self.sorter.read_record(&mut self.input, &mut record) {
+            Ok(true) => Some(Ok(record)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn random(n: usize, seed: u64) -> Vec<u64> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x = x.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                x >> 20
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lines() {
+        let input = "pear\napple\nfig\n\nbanana";
+        let mut output = Vec::new();
+        let stats = ExternalSort::new(RecordFormat::Lines, 1 << 20).sort(input.as_bytes(), &mut output).unwrap();
+        assert_eq!("\napple\nbanana\nfig\npear\n", String::from_utf8(output).unwrap());
+        assert_eq!(ExternalSortStats { records: 5, runs: 1, merge_passes: 0 }, stats);
+        let mut output = Vec::new();
+        let stats = ExternalSort::new(RecordFormat::Lines, 0).sort(&b""[..], &mut output).unwrap();
+        assert!(output.is_empty());
+        assert_eq!(ExternalSortStats::default(), stats);
+    }
+
+    #[test]
+    fn test_tiny_memory_limit() {
+        // 每段只能放下几条记录，fan-in为2，需要多轮合并
+        let numbers = random(5000, 1);
+        let input: String = numbers.iter().map(|x| format!("{}\n", x)).collect();
+        let mut output = Vec::new();
+        let dir = tempfile::tempdir().unwrap();
+        let stats = ExternalSort::new(RecordFormat::Lines, 200)
+            .temp_dir(dir.path())
+            .sort_by(input.as_bytes(), &mut output, |a, b| {
+                let parse = |s: &[u8]| std::str::from_utf8(s).unwrap().parse::<u64>().unwrap();
+                parse(a).cmp(&parse(b))
+            })
+            .unwrap();
+        let mut expected = numbers;
+        expected.sort();
+        let actual: Vec<u64> = String::from_utf8(output).unwrap().lines().map(|l| l.parse().unwrap()).collect();
+        assert_eq!(expected, actual);
+        assert_eq!(5000, stats.records);
+        assert!(stats.runs > 500, "{:?}", stats);
+        assert_eq!(stats.runs.next_power_of_two().trailing_zeros() as usize, stats.merge_passes);
+        // 临时文件都已删除
+        assert_eq!(0, std::fs::read_dir(dir.path()).unwrap().count());
+    }
+
+    #[test]
+    fn test_fixed_width_stable() {
+        // 4字节记录：按第一个字节排序，后三个字节是输入中的位置
+        let records: Vec<[u8; 4]> = random(3000, 2)
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| [(x % 8) as u8, (i >> 16) as u8, (i >> 8) as u8, i as u8])
+            .collect();
+        let input = records.concat();
+        let mut output = Vec::new();
+        let stats = ExternalSort::new(RecordFormat::Fixed(4), 1000)
+            .sort_by(&input[..], &mut output, |a, b| a[0].cmp(&b[0]))
+            .unwrap();
+        let mut expected = records;
+        expected.sort_by_key(|r| r[0]);
+        assert_eq!(expected.concat(), output);
+        assert!(stats.runs > 1);
+    }
+
+    #[test]
+    fn test_partial_record() {
+        let err = ExternalSort::new(RecordFormat::Fixed(4), 1 << 20)
+            .sort(&[1_u8, 2, 3, 4, 5, 6][..], io::sink())
+            .unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+        assert!(err.to_string().contains("partial record of 2 bytes"), "{}", err);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn test_zero_width() {
+        ExternalSort::new(RecordFormat::Fixed(0), 1024);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        #[test]
+        fn prop_matches_std(lines in prop::collection::vec("[a-c]{0,4}", 0..200), memory_limit in 0_usize..400) {
+            let input: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+            let mut output = Vec::new();
+            ExternalSort::new(RecordFormat::Lines, memory_limit).sort(input.as_bytes(), &mut output).unwrap();
+            let mut expected = lines;
+            expected.sort();
+            let expected: String = expected.iter().map(|l| format!("{}\n", l)).collect();
+            prop_assert_eq!(expected, String::from_utf8(output).unwrap());
+        }
+    }
+}
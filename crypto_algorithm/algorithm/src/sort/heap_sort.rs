@@ -0,0 +1,110 @@
+//! # 堆排序 O(nlogn)
+//! 1. 原地建立大顶堆：从最后一个非叶子节点开始依次下沉
+//! 2. 把堆顶（最大值）与堆的最后一个元素交换，堆的长度减一，新的堆顶下沉
+//! 3. 重复2直到堆中只剩一个元素
+//! ```code
+//!        9                 | 9 | 7 | 8 | 1 | 4 |
+//!      ↙   ↘                 ↑               ↑ 交换
+//!     7     8              | 4 | 7 | 8 | 1 || 9 |   4下沉
+//!   ↙  ↘                   | 8 | 7 | 4 | 1 || 9 |
+//!  1    4
+//! ```
+//! 不需要额外空间，最坏情况也是O(nlogn)，不稳定
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/27
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
+
+/// 堆排序，升序
+pub fn heap_sort<T: Ord>(list: &mut [T]) {
+    heap_sort_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn heap_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    heap_sort_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn heap_sort_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    heap(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+pub(crate) fn heap<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    let len = list.len();
+    for i in (0..len / 2).rev() {
+        sift_down(list, i, compare, tracker);
+    }
+    tracker.pass_complete::<T>();
+    for end in (1..len).rev() {
+        tracker.swap(list, 0, end);
+        sift_down(&mut list[..end], 0, compare, tracker);
+        tracker.pass_complete::<T>();
+    }
+}
+
+/// 节点i下沉，直到不小于两个子节点
+fn sift_down<T, F, O>(heap: &mut [T], mut i: usize, compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    loop {
+        let mut child = 2 * i + 1;
+        if child >= heap.len() {
+            return;
+        }
+        if child + 1 < heap.len() && tracker.cmp(heap, child, child + 1, compare) == Ordering::Less {
+            child += 1;
+        }
+        if tracker.cmp(heap, i, child, compare) != Ordering::Less {
+            return;
+        }
+        tracker.swap(heap, i, child);
+        i = child;
+    }
+}
+
+/// # 堆排序器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeapSort;
+
+impl<T> Sorter<T> for HeapSort {
+    fn name(&self) -> &'static str {
+        "heap"
+    }
+    fn is_stable(&self) -> bool {
+        false
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        heap_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        heap(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut list = vec![9, 7, 8, 1, 4, 7];
+        heap_sort(&mut list);
+        assert_eq!(list, vec![1, 4, 7, 7, 8, 9]);
+        let mut words = vec!["pear", "fig", "banana"];
+        heap_sort_by_key(&mut words, |w| w.len());
+        assert_eq!(words, vec!["fig", "pear", "banana"]);
+    }
+}
@@ -1,6 +1,8 @@
-//! # 插入排序
+//! # 插入排序 O(n^2)
 //! 从第一个元素开始向后遍历，遍历一个选择该元素和它之前的比较找到比它小的元素放在该元素的后面
 //! 然后其他元素以此向后移动一位
+//!
+//! 只越过严格更大的元素，因此是稳定的；对基本有序的数据接近O(n)
 //! ```txt
 //! @author:syf20020816@Outlook.com
 //! @date:2023/10/9
@@ -8,13 +10,56 @@
 //! @description:
 //! ```
 
-fn insert_sort(list: &mut Vec<u32>) -> () {
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
+
+/// 插入排序，升序
+pub fn insert_sort<T: Ord>(list: &mut [T]) {
+    insert_sort_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn insert_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    insert_sort_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn insert_sort_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    insert(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+pub(crate) fn insert<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
     for i in 1..list.len() {
         let mut flag = i;
-        while flag > 0 && list[flag] < list[flag - 1] {
-            list.swap(flag, flag - 1);
+        while flag > 0 && tracker.cmp(list, flag, flag - 1, compare) == Ordering::Less {
+            tracker.swap(list, flag, flag - 1);
             flag -= 1;
         }
+        tracker.pass_complete::<T>();
+    }
+}
+
+/// # 插入排序器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InsertSort;
+
+impl<T> Sorter<T> for InsertSort {
+    fn name(&self) -> &'static str {
+        "insert"
+    }
+    fn is_stable(&self) -> bool {
+        true
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        insert_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        insert(data, &mut compare, &mut Tracker::new(observer))
     }
 }
 
@@ -28,4 +73,13 @@ mod tests {
         insert_sort(&mut list);
         assert_eq!(list, vec![1, 2, 4, 6, 7, 9]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_by() {
+        let mut list = vec!["ccc", "a", "bb", "d"];
+        insert_sort_by_key(&mut list, |s| s.len());
+        assert_eq!(list, vec!["a", "d", "bb", "ccc"]);
+        insert_sort_by(&mut list, |a, b| b.cmp(a));
+        assert_eq!(list, vec!["d", "ccc", "bb", "a"]);
+    }
+}
@@ -0,0 +1,121 @@
+//! # 多路归并
+//! 把k个有序序列合并为一个有序序列：每个序列的队首放入小顶堆，每次取出堆顶，
+//! 再把它所在序列的下一个元素放入堆中
+//! ```code
+//! run 0: 1 → 4 → 9         heap: (1, 0) (2, 1) (3, 2)
+//! run 1: 2 → 5             pop (1, 0)，push (4, 0)
+//! run 2: 3 → 6 → 7         heap: (2, 1) (3, 2) (4, 0)   ......
+//! output: 1 → 2 → 3 → 4 → 5 → 6 → 7 → 9
+//! ```
+//! 每次取出O(logk)，总共O(nlogk)。相等的元素先取编号小的序列，因此是稳定的：
+//! 按原来的顺序切分出的有序段，合并后相等元素保持原来的相对顺序。
+//! 外部排序`ExternalSort`的合并阶段就是多路归并
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/2
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::cmp::Ordering;
+use std::mem;
+use structure::{BinaryHeap, Compare, PeekMut};
+
+/// `T::cmp`
+type Ascending<T> = fn(&T, &T) -> Ordering;
+
+/// 合并k个升序序列
+pub fn kway_merge<T: Ord, I: IntoIterator<Item = T>>(sources: impl IntoIterator<Item = I>) -> KWayMerge<I::IntoIter, Ascending<T>> {
+    kway_merge_by(sources, T::cmp)
+}
+
+/// 合并k个按compare有序的序列
+pub fn kway_merge_by<T, I, F>(sources: impl IntoIterator<Item = I>, compare: F) -> KWayMerge<I::IntoIter, F>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(&T, &T) -> Ordering,
+{
+    let mut sources: Vec<I::IntoIter> = sources.into_iter().map(IntoIterator::into_iter).collect();
+    let heads = sources
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(i, source)| source.next().map(|head| (head, i)))
+        .collect();
+    KWayMerge {
+        heap: BinaryHeap::from_vec(heads, HeadOrder(compare)),
+        sources,
+    }
+}
+
+/// # 多路归并迭代器
+pub struct KWayMerge<I: Iterator, F> {
+    /// (队首, 序列编号)
+    heap: BinaryHeap<(I::Item, usize), HeadOrder<F>>,
+    sources: Vec<I>,
+}
+
+/// 小顶堆的比较器：较小的队首、编号较小的序列在堆顶
+struct HeadOrder<F>(F);
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<(T, usize)> for HeadOrder<F> {
+    fn compare(&self, a: &(T, usize), b: &(T, usize)) -> Ordering {
+        (self.0)(&b.0, &a.0).then(b.1.cmp(&a.1))
+    }
+}
+
+impl<T, I: Iterator<Item = T>, F: Fn(&T, &T) -> Ordering> Iterator for KWayMerge<I, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut top = self.heap.peek_mut()?;
+        match self.sources[top.1].next() {
+            // 用下一个元素替换堆顶，PeekMut释放时下沉
+            Some(next) => Some(mem::replace(&mut top.0, next)),
+            None => Some(PeekMut::pop(top).0),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.sources.iter().fold((self.heap.len(), Some(self.heap.len())), |(low, high), source| {
+            let (l, h) = source.size_hint();
+            (low.saturating_add(l), high.zip(h).and_then(|(a, b)| a.checked_add(b)))
+        });
+        (low, high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let merged: Vec<u32> = kway_merge(vec![vec![1, 4, 9], vec![2, 5], vec![], vec![3, 6, 7]]).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 9], merged);
+        assert_eq!(0, kway_merge(Vec::<Vec<u32>>::new()).count());
+        let merged: Vec<u32> = kway_merge_by(vec![vec![9, 4, 1], vec![5, 2]], |a: &u32, b: &u32| b.cmp(a)).collect();
+        assert_eq!(vec![9, 5, 4, 2, 1], merged);
+        assert_eq!((5, Some(5)), kway_merge(vec![vec![1, 4, 9], vec![2, 5]]).size_hint());
+    }
+
+    #[test]
+    fn test_stable() {
+        let runs = vec![vec![(1, 'a'), (2, 'b')], vec![(1, 'c'), (2, 'd')], vec![(1, 'e')]];
+        let merged: Vec<(u32, char)> = kway_merge_by(runs, |a: &(u32, char), b: &(u32, char)| a.0.cmp(&b.0)).collect();
+        assert_eq!(vec![(1, 'a'), (1, 'c'), (1, 'e'), (2, 'b'), (2, 'd')], merged);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_sort(mut runs in prop::collection::vec(prop::collection::vec(any::<i16>(), 0..50), 0..20)) {
+            for run in runs.iter_mut() {
+                run.sort();
+            }
+            let mut expected: Vec<i16> = runs.iter().flatten().copied().collect();
+            expected.sort();
+            let merged: Vec<i16> = kway_merge(runs).collect();
+            prop_assert_eq!(expected, merged);
+        }
+    }
+}
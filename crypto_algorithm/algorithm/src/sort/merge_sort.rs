@@ -1,11 +1,272 @@
-// pub fn merge_sort<T: PartialOrd>(mut list: Vec<T>) -> Vec<T> {
-//     if 1.ge(&list.len()) {
-//         return list;
-//     }
-//     // 将vec分为两个部分（一半）, 再分一半 ...
-//     let first_half = list.split_off(list.len() / 2);
-//     let second = merge_sort(list);
-//     first_half = merge_sort(first_half);
-//
-//     return list;
-// }
\ No newline at end of file
+//! # 归并排序 O(nlogn)
+//! 把序列分成两半分别排序，再把两个有序序列合并成一个
+//! ```code
+//! | 4 | 1 | 7 | 2 |        自顶向下：递归拆分到长度足够小
+//!    ↙          ↘
+//! | 4 | 1 |   | 7 | 2 |
+//!    ↓          ↓
+//! | 1 | 4 |   | 2 | 7 |    合并：每次取两边较小的队首，相等时取左边（稳定）
+//!    ↘          ↙
+//! | 1 | 2 | 4 | 7 |
+//! ```
+//! 1. 自顶向下（merge_sort）：递归拆分
+//! 2. 自底向上（merge_sort_bottom_up）：先把长度为RUN的小段插入排序，再按宽度RUN、2RUN、4RUN...两两合并，不需要递归
+//!
+//! 两种方式都只在开始时分配一个n/2的缓冲区，之后所有的合并复用这个缓冲区
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/27
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::insert_sort::insert;
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
+use std::ptr;
+
+/// 不超过这个长度的小段使用插入排序
+const RUN: usize = 16;
+
+/// 归并排序（自顶向下），升序
+pub fn merge_sort<T: Ord>(list: &mut [T]) {
+    merge_sort_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn merge_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    merge_sort_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn merge_sort_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    top_down(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+/// 归并排序（自底向上），升序
+pub fn merge_sort_bottom_up<T: Ord>(list: &mut [T]) {
+    merge_sort_bottom_up_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn merge_sort_bottom_up_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    merge_sort_bottom_up_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn merge_sort_bottom_up_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    bottom_up(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+fn top_down<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    let mut buf = Vec::with_capacity(list.len() / 2);
+    sort_recursive(list, &mut buf, compare, tracker);
+}
+
+fn bottom_up<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    let len = list.len();
+    for (i, chunk) in list.chunks_mut(RUN).enumerate() {
+        insert(chunk, compare, &mut tracker.sub(i * RUN));
+    }
+    let mut buf = Vec::with_capacity(len / 2);
+    let mut width = RUN;
+    while width < len {
+        let mut start = 0;
+        while start + width < len {
+            let end = (start + 2 * width).min(len);
+            merge(&mut list[start..end], width, &mut buf, compare, &mut tracker.sub(start));
+            start = end;
+        }
+        tracker.pass_complete::<T>();
+        width *= 2;
+    }
+}
+
+pub(crate) fn sort_recursive<T, F, O>(list: &mut [T], buf: &mut Vec<T>, compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    let len = list.len();
+    if len <= RUN {
+        insert(list, compare, tracker);
+        return;
+    }
+    let mid = len / 2;
+    sort_recursive(&mut list[..mid], buf, compare, tracker);
+    sort_recursive(&mut list[mid..], buf, compare, &mut tracker.sub(mid));
+    merge(list, mid, buf, compare, tracker);
+    tracker.pass_complete::<T>();
+}
+
+/// # 合并
+/// `list[..mid]`和`list[mid..]`分别有序，合并后整体有序
+/// 1. 左半部分移动到缓冲区，list中留下一个长度为mid的空洞
+/// 2. 比较缓冲区和右半部分的队首，较小的移动到空洞的开头，空洞随之后移
+/// 3. 右半部分先取完时，缓冲区剩下的元素正好填满空洞
+///
+/// 缓冲区只有`Vec`的容量被使用，长度始终为0，元素的所有权在缓冲区和list之间移动。
+/// 通知观察者时，缓冲区中的元素使用它在合并前的位置
+pub(crate) fn merge<T, F, O>(list: &mut [T], mid: usize, buf: &mut Vec<T>, compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    let len = list.len();
+    if mid == 0 || mid == len || tracker.cmp(list, mid, mid - 1, compare) != Ordering::Less {
+        // 左边的最大值不大于右边的最小值，已经有序
+        return;
+    }
+    buf.reserve(mid);
+    let v = list.as_mut_ptr();
+    let b = buf.as_mut_ptr();
+    // SAFETY: 缓冲区容量不小于mid，与list不重叠；复制后list[..mid]是空洞，
+    // 之后始终满足 out + (left_end - left) == right，空洞正好容纳缓冲区中剩余的元素，
+    // 比较器或观察者panic时由MergeHole把剩余元素移回空洞，每个元素仍然只有一份
+    unsafe {
+        ptr::copy_nonoverlapping(v, b, mid);
+        let mut hole = MergeHole {
+            left: b,
+            left_end: b.add(mid),
+            out: v,
+        };
+        let end = v.add(len);
+        let mut right = v.add(mid);
+        while hole.left < hole.left_end && right < end {
+            tracker.compared::<T>(right.offset_from(v) as usize, hole.left.offset_from(b) as usize);
+            // 相等时取左边，保证稳定
+            let from = if compare(&*right, &*hole.left) == Ordering::Less {
+                right = right.add(1);
+                right.sub(1)
+            } else {
+                hole.left = hole.left.add(1);
+                hole.left.sub(1)
+            };
+            ptr::copy_nonoverlapping(from, hole.out, 1);
+            hole.out = hole.out.add(1);
+            tracker.wrote(hole.out.offset_from(v) as usize - 1, &*hole.out.sub(1));
+        }
+        // 右半部分取完，缓冲区剩余的元素依次填入空洞
+        while hole.left < hole.left_end {
+            ptr::copy_nonoverlapping(hole.left, hole.out, 1);
+            hole.left = hole.left.add(1);
+            hole.out = hole.out.add(1);
+            tracker.wrote(hole.out.offset_from(v) as usize - 1, &*hole.out.sub(1));
+        }
+    }
+}
+
+/// 缓冲区中`[left, left_end)`的元素需要移动到`out`开始的空洞
+struct MergeHole<T> {
+    left: *mut T,
+    left_end: *mut T,
+    out: *mut T,
+}
+
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        // SAFETY: 见merge中的不变式，空洞长度等于剩余元素数量
+        unsafe {
+            let count = self.left_end.offset_from(self.left) as usize;
+            ptr::copy_nonoverlapping(self.left, self.out, count);
+        }
+    }
+}
+
+/// # 归并排序器（自顶向下）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeSort;
+
+impl<T> Sorter<T> for MergeSort {
+    fn name(&self) -> &'static str {
+        "merge"
+    }
+    fn is_stable(&self) -> bool {
+        true
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        merge_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        top_down(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
+
+/// # 归并排序器（自底向上）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BottomUpMergeSort;
+
+impl<T> Sorter<T> for BottomUpMergeSort {
+    fn name(&self) -> &'static str {
+        "merge_bottom_up"
+    }
+    fn is_stable(&self) -> bool {
+        true
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        merge_sort_bottom_up_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        bottom_up(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_merge_sort() {
+        let mut list: Vec<u32> = (0..100).rev().collect();
+        merge_sort(&mut list);
+        assert_eq!((0..100).collect::<Vec<_>>(), list);
+        let mut list: Vec<u32> = (0..100).map(|i| i * 37 % 101).collect();
+        merge_sort_bottom_up(&mut list);
+        assert!(list.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_stable() {
+        let data: Vec<(u8, usize)> = (0..200).map(|i| ((i * 7 % 5) as u8, i)).collect();
+        let mut expected = data.clone();
+        expected.sort_by_key(|&(k, _)| k);
+        let mut list = data.clone();
+        merge_sort_by_key(&mut list, |&(k, _)| k);
+        assert_eq!(expected, list);
+        let mut list = data;
+        merge_sort_bottom_up_by_key(&mut list, |&(k, _)| k);
+        assert_eq!(expected, list);
+    }
+
+    #[test]
+    fn test_panic_in_compare() {
+        // 比较器panic后每个元素仍然恰好存在一份：既没有被drop两次也没有泄漏
+        let item = Rc::new(());
+        // 两半各自有序，panic发生在最后一次合并的中途
+        let mut list: Vec<(u32, Rc<()>)> = (50..100).chain(0..50).map(|i| (i, Rc::clone(&item))).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            merge_sort_by(&mut list, |a, b| {
+                assert!(a.0 != 25 || b.0 < 50, "comparator failure");
+                a.0.cmp(&b.0)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(101, Rc::strong_count(&item));
+        let mut keys: Vec<u32> = list.iter().map(|(k, _)| *k).collect();
+        keys.sort();
+        assert_eq!((0..100).collect::<Vec<_>>(), keys);
+        drop(list);
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+}
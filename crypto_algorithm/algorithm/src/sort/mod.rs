@@ -4,21 +4,36 @@
 //! - 非比较类排序：不通过比较来决定元素间的相对次序，它可以突破基于比较排序的时间下界，以线性时间运行，因此也称为线性时间非比较类排序。
 //! ## 比较类排序
 //! 1. 交换排序
-//!     - 冒泡排序 O(n^2)
-//!     - 快速排序 O(nlog2^n)
+//!     - 冒泡排序 O(n^2)：bubble_sort
+//!     - 快速排序 O(nlog2^n)：quick_sort（内省排序）
 //! 2. 插入排序
-//!     - 简单插入排序 O(n^2)
-//!     - 希尔排序 O(n^1.3)
+//!     - 简单插入排序 O(n^2)：insert_sort
+//!     - 希尔排序 O(n^1.3)：shell_sort
 //! 3. 选择排序
-//!     - 简单选择排序 O(n^2)
-//!     - 堆排序 O(nlog2^n)
+//!     - 简单选择排序 O(n^2)：select_sort
+//!     - 堆排序 O(nlog2^n)：heap_sort
 //! 4. 归并排序 O(nlog2^n)
-//!     - 二路归并排序
-//!     - 多路归并排序
+//!     - 二路归并排序：merge_sort（自顶向下）、merge_sort_bottom_up（自底向上）
+//!     - 多路归并排序：kway_merge（k个有序序列的多路归并）、ExternalSort（文件的外部排序）
+//! 5. 并行排序：par_merge_sort、par_quick_sort，多一个threads参数
 //! ## 非比较类排序
-//! 1. 计数排序 O(n+k)
-//! 2. 桶排序 O(n+k)
-//! 3. 基数排序 O(n*k)
+//! 1. 计数排序 O(n+k)：counting_sort_by_key
+//! 2. 桶排序 O(n+k)：bucket_sort（[0, 1)中的f64）
+//! 3. 基数排序 O(n*k)：radix_sort（整数、浮点数）、msd_radix_sort（字节串）
+//!
+//! 非比较类排序依赖key的结构，没有`_by`比较器版本，也不实现`Sorter`
+//! ## 使用
+//! 每种算法都提供三个函数，以及一个实现了`Sorter`的排序器：
+//! ```code
+//! bubble_sort(&mut data);                         // T: Ord，升序
+//! bubble_sort_by(&mut data, |a, b| b.cmp(a));     // 比较器
+//! bubble_sort_by_key(&mut data, |x| x.abs());     // 按key升序
+//! let sorter = find_sorter::<u32>("bubble").unwrap();
+//! sorter.sort(&mut data);                         // 运行时选择算法
+//! ```
+//! ## 观察与追踪
+//! 排序器的`sort_observed`在比较、交换、写入时通知`SortObserver`；`SortStats`统计次数，
+//! `trace`记录完整的`SortEvent`序列，`render`把事件重放为字符画
 //! ```txt
 //! @author:syf20020816@Outlook.com
 //! @date:2023/9/9
@@ -26,6 +41,7 @@
 //! @description:
 //! ```
 
+use std::cmp::Ordering;
 
 ///冒泡排序
 mod bubble_sort;
@@ -35,3 +51,171 @@ mod merge_sort;
 mod select_sort;
 /// 插入排序
 mod insert_sort;
+/// 快速排序
+mod quick_sort;
+/// 希尔排序
+mod shell_sort;
+/// 堆排序
+mod heap_sort;
+/// 计数排序
+mod counting_sort;
+/// 桶排序
+mod bucket_sort;
+/// 基数排序
+mod radix_sort;
+/// 排序观察者
+mod observer;
+/// 排序过程追踪
+mod trace;
+/// 并行排序
+mod parallel_sort;
+/// 多路归并
+mod kway_merge;
+/// 外部排序
+mod external_sort;
+
+pub use bubble_sort::{bubble_sort, bubble_sort_by, bubble_sort_by_key, BubbleSort};
+pub use bucket_sort::bucket_sort;
+pub use counting_sort::counting_sort_by_key;
+pub use external_sort::{ExternalSort, ExternalSortStats, RecordFormat};
+pub use heap_sort::{heap_sort, heap_sort_by, heap_sort_by_key, HeapSort};
+pub use insert_sort::{insert_sort, insert_sort_by, insert_sort_by_key, InsertSort};
+pub use kway_merge::{kway_merge, kway_merge_by, KWayMerge};
+pub use observer::{SortObserver, SortStats};
+pub use parallel_sort::{
+    par_merge_sort, par_merge_sort_by, par_merge_sort_by_key, par_quick_sort, par_quick_sort_by, par_quick_sort_by_key,
+    PARALLEL_THRESHOLD,
+};
+pub use merge_sort::{
+    merge_sort, merge_sort_bottom_up, merge_sort_bottom_up_by, merge_sort_bottom_up_by_key, merge_sort_by,
+    merge_sort_by_key, BottomUpMergeSort, MergeSort,
+};
+pub use quick_sort::{quick_sort, quick_sort_by, quick_sort_by_key, QuickSort};
+pub use radix_sort::{msd_radix_sort, radix_sort, RadixKey};
+pub use select_sort::{select_sort, select_sort_by, select_sort_by_key, SelectSort};
+pub use shell_sort::{shell_sort, shell_sort_by, shell_sort_by_key, ShellSort};
+pub use trace::{frames, render, trace, Frames, SortEvent};
+
+pub(crate) use insert_sort::insert;
+pub(crate) use observer::Tracker;
+pub(crate) use quick_sort::{partition, partition_around_first};
+
+/// # 排序器
+/// 比较排序算法的统一接口，可以作为`dyn Sorter<T>`在运行时选择算法
+pub trait Sorter<T> {
+    /// 算法名称，用于`find_sorter`查找
+    fn name(&self) -> &'static str;
+    /// 是否稳定：相等的元素保持原来的相对顺序
+    fn is_stable(&self) -> bool;
+    /// 使用比较器排序
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering);
+    /// 使用比较器排序，比较、交换和写入元素时通知observer
+    fn sort_observed(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>);
+    /// 升序排序
+    fn sort(&self, data: &mut [T])
+    where
+        T: Ord,
+    {
+        self.sort_by(data, &mut T::cmp)
+    }
+    /// 按key升序排序
+    fn sort_by_key<K: Ord>(&self, data: &mut [T], mut f: impl FnMut(&T) -> K)
+    where
+        Self: Sized,
+    {
+        self.sort_by(data, &mut |a, b| f(a).cmp(&f(b)))
+    }
+}
+
+/// 全部排序器
+pub fn sorters<T>() -> Vec<Box<dyn Sorter<T>>> {
+    vec![
+        Box::new(BubbleSort),
+        Box::new(InsertSort),
+        Box::new(SelectSort),
+        Box::new(ShellSort),
+        Box::new(MergeSort),
+        Box::new(BottomUpMergeSort),
+        Box::new(QuickSort),
+        Box::new(HeapSort),
+    ]
+}
+
+/// 按名称查找排序器
+pub fn find_sorter<T>(name: &str) -> Option<Box<dyn Sorter<T>>> {
+    sorters().into_iter().find(|sorter| sorter.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_find_sorter() {
+        let mut data = vec![3, 1, 2];
+        find_sorter::<u32>("select").unwrap().sort(&mut data);
+        assert_eq!(vec![1, 2, 3], data);
+        assert!(find_sorter::<u32>("bogo").is_none());
+        let mut stats = SortStats::default();
+        BubbleSort.sort_observed(&mut data, &mut u32::cmp, &mut stats);
+        assert_eq!(SortStats { comparisons: 2, swaps: 0, writes: 0 }, stats);
+        let mut data = vec![2, 1];
+        MergeSort.sort_observed(&mut data, &mut u32::cmp, &mut stats);
+        assert_eq!(SortStats { comparisons: 3, swaps: 1, writes: 0 }, stats);
+        let mut words = vec!["bb", "a", "ccc"];
+        InsertSort.sort_by_key(&mut words, |w| std::cmp::Reverse(w.len()));
+        assert_eq!(vec!["ccc", "bb", "a"], words);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_observer_replays_sort(data in prop::collection::vec(0_u16..50, 0..300)) {
+            // 通知中的下标必须是整个切片中的位置：按通知重放后与排序结果一致
+            for sorter in sorters::<u16>() {
+                let mut events = Vec::new();
+                let mut actual = data.clone();
+                let mut calls = 0;
+                sorter.sort_observed(&mut actual, &mut |a, b| { calls += 1; a.cmp(b) }, &mut events);
+                prop_assert!(actual.windows(2).all(|w| w[0] <= w[1]), "{}", sorter.name());
+                let mut shadow = data.clone();
+                let mut comparisons = 0;
+                for event in &events {
+                    if let SortEvent::Compare(i, j) = *event {
+                        prop_assert!(i < data.len() && j < data.len());
+                        comparisons += 1;
+                    }
+                    event.apply(&mut shadow);
+                }
+                prop_assert_eq!(&actual, &shadow, "{}", sorter.name());
+                prop_assert_eq!(calls, comparisons, "{}", sorter.name());
+            }
+        }
+
+        #[test]
+        fn prop_matches_std(data in prop::collection::vec(any::<i16>(), 0..1000)) {
+            let mut expected = data.clone();
+            expected.sort();
+            for sorter in sorters::<i16>() {
+                let mut actual = data.clone();
+                sorter.sort(&mut actual);
+                prop_assert_eq!(&expected, &actual, "{}", sorter.name());
+            }
+        }
+
+        #[test]
+        fn prop_by_key_matches_std(data in prop::collection::vec((0_u8..4, any::<u16>()), 0..200)) {
+            // key重复很多，检查比较器只看key；稳定的算法结果与std完全一致
+            let mut expected = data.clone();
+            expected.sort_by_key(|&(k, _)| k);
+            for sorter in sorters::<(u8, u16)>() {
+                let mut actual = data.clone();
+                sorter.sort_by(&mut actual, &mut |a, b| a.0.cmp(&b.0));
+                prop_assert!(actual.windows(2).all(|w| w[0].0 <= w[1].0), "{}", sorter.name());
+                if sorter.is_stable() {
+                    prop_assert_eq!(&expected, &actual, "{}", sorter.name());
+                }
+            }
+        }
+    }
+}
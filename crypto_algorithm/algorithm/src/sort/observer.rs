@@ -0,0 +1,112 @@
+//! # 排序观察者
+//! 比较类排序在比较、交换、写入元素时通知观察者，可以用来统计次数或者记录排序过程
+//! ```code
+//! let mut stats = SortStats::default();
+//! QuickSort.sort_observed(&mut data, &mut u32::cmp, &mut stats);
+//! println!("{} comparisons, {} swaps", stats.comparisons, stats.swaps);
+//! ```
+//! 通知中的下标都是元素在整个切片中的位置，算法在子切片上递归时由`Tracker`换算。
+//! 不需要观察时算法使用`()`作为观察者，通知在编译时被优化掉。
+//! 需要完整的事件序列时使用`trace`，见`SortEvent`
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/30
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::cmp::Ordering;
+
+/// # 排序观察者
+/// 所有方法默认什么都不做，只需要实现关心的部分
+pub trait SortObserver<T> {
+    /// 比较了位置i和位置j的元素，顺序与传给比较器的参数一致
+    fn compare(&mut self, _i: usize, _j: usize) {}
+    /// 交换了位置i和位置j的元素
+    fn swap(&mut self, _i: usize, _j: usize) {}
+    /// 位置i写入了value，归并排序移动元素而不是交换
+    fn write(&mut self, _i: usize, _value: &T) {}
+    /// 完成了一轮，每种算法的“一轮”见`SortEvent::PassComplete`
+    fn pass_complete(&mut self) {}
+}
+
+impl<T> SortObserver<T> for () {}
+
+/// # 次数统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub writes: u64,
+}
+
+impl<T> SortObserver<T> for SortStats {
+    fn compare(&mut self, _i: usize, _j: usize) {
+        self.comparisons += 1;
+    }
+    fn swap(&mut self, _i: usize, _j: usize) {
+        self.swaps += 1;
+    }
+    fn write(&mut self, _i: usize, _value: &T) {
+        self.writes += 1;
+    }
+}
+
+/// # 带偏移的观察者
+/// 算法只看到当前的子切片，base是子切片在整个切片中的起点
+pub(crate) struct Tracker<'a, O: ?Sized> {
+    observer: &'a mut O,
+    base: usize,
+}
+
+impl<'a, O: ?Sized> Tracker<'a, O> {
+    pub(crate) fn new(observer: &'a mut O) -> Self {
+        Tracker { observer, base: 0 }
+    }
+    /// 子切片`list[offset..]`使用的Tracker
+    pub(crate) fn sub(&mut self, offset: usize) -> Tracker<'_, O> {
+        Tracker {
+            observer: self.observer,
+            base: self.base + offset,
+        }
+    }
+    /// 比较`list[i]`和`list[j]`
+    pub(crate) fn cmp<T, F: FnMut(&T, &T) -> Ordering>(&mut self, list: &[T], i: usize, j: usize, compare: &mut F) -> Ordering
+    where
+        O: SortObserver<T>,
+    {
+        self.observer.compare(self.base + i, self.base + j);
+        compare(&list[i], &list[j])
+    }
+    /// 交换`list[i]`和`list[j]`，i与j相同时不算一次交换
+    pub(crate) fn swap<T>(&mut self, list: &mut [T], i: usize, j: usize)
+    where
+        O: SortObserver<T>,
+    {
+        if i != j {
+            list.swap(i, j);
+            self.observer.swap(self.base + i, self.base + j);
+        }
+    }
+    /// 只通知比较，用于比较的元素不在list中的情况（如归并排序的缓冲区）
+    pub(crate) fn compared<T>(&mut self, i: usize, j: usize)
+    where
+        O: SortObserver<T>,
+    {
+        self.observer.compare(self.base + i, self.base + j);
+    }
+    /// 通知位置i写入了value
+    pub(crate) fn wrote<T>(&mut self, i: usize, value: &T)
+    where
+        O: SortObserver<T>,
+    {
+        self.observer.write(self.base + i, value);
+    }
+    /// 通知完成了一轮
+    pub(crate) fn pass_complete<T>(&mut self)
+    where
+        O: SortObserver<T>,
+    {
+        self.observer.pass_complete();
+    }
+}
@@ -0,0 +1,231 @@
+//! # 并行排序
+//! 用`std::thread::scope`把切片分给多个线程，不需要额外的线程池
+//! ```code
+//! threads = 4
+//! | ............................ list ............................ |
+//! | ........ 2 ........ | ........ 2 ........ |     每次一分为二，线程数也一分为二
+//! | .. 1 .. | .. 1 .. | | .. 1 .. | .. 1 .. |     只剩一个线程时顺序排序
+//! ```
+//! 1. 并行归并排序：两半并行排序后再并行合并；稳定，结果与线程数无关
+//! 2. 并行快速排序：当前线程划分后两边并行排序，线程数按两边的长度分配；
+//!    划分方式与`quick_sort`完全相同，结果也与`quick_sort`一致
+//!
+//! ## 并行合并
+//! 取较长一边的中间元素p，在另一边二分出p的位置，两边都分成p之前和p之后两段，
+//! 旋转中间的两段后得到两个互不相关的合并，分给两组线程：
+//! ```code
+//! | L1 | L2 | R1 | R2 |   →   | L1 | R1 | L2 | R2 |
+//!      ↑ mid                 |— 合并 —|— 合并 —|
+//! ```
+//! 比较都分散到各个线程中，剩下的顺序部分只有旋转，即每一层一次O(n)的内存移动
+//!
+//! 长度不超过`PARALLEL_THRESHOLD`的段不再拆分，threads为0或1时就是顺序排序。
+//! 比较器会被多个线程同时调用，因此要求`Fn + Sync`，元素要求`Send`
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/11/1
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::merge_sort::{merge, sort_recursive};
+use super::observer::Tracker;
+use super::quick_sort::{depth_limit, introsort, partition};
+use crate::search::partition_point;
+use std::cmp::Ordering;
+use std::thread;
+
+/// 不超过这个长度的段在当前线程顺序排序，再拆分时创建线程的开销超过收益
+pub const PARALLEL_THRESHOLD: usize = 1 << 13;
+
+/// 并行归并排序，升序
+pub fn par_merge_sort<T: Ord + Send>(list: &mut [T], threads: usize) {
+    par_merge_sort_by(list, threads, T::cmp)
+}
+
+/// 按key升序
+pub fn par_merge_sort_by_key<T: Send, K: Ord, F: Fn(&T) -> K + Sync>(list: &mut [T], threads: usize, f: F) {
+    par_merge_sort_by(list, threads, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn par_merge_sort_by<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(list: &mut [T], threads: usize, compare: F) {
+    par_merge(list, threads, &compare)
+}
+
+fn par_merge<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(list: &mut [T], threads: usize, compare: &F) {
+    let mut buf = Vec::new();
+    if threads <= 1 || list.len() <= PARALLEL_THRESHOLD {
+        sort_recursive(list, &mut buf, &mut { compare }, &mut Tracker::new(&mut ()));
+        return;
+    }
+    // 与顺序版本在同样的位置拆分
+    let mid = list.len() / 2;
+    let (left, right) = list.split_at_mut(mid);
+    thread::scope(|s| {
+        s.spawn(|| par_merge(left, threads / 2, compare));
+        par_merge(right, threads - threads / 2, compare);
+    });
+    par_merge_halves(list, mid, threads, compare);
+}
+
+/// `list[..mid]`和`list[mid..]`分别有序，用threads个线程合并
+fn par_merge_halves<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(list: &mut [T], mid: usize, threads: usize, compare: &F) {
+    if threads <= 1 || list.len() <= PARALLEL_THRESHOLD || mid == 0 || mid == list.len() {
+        merge(list, mid, &mut Vec::new(), &mut { compare }, &mut Tracker::new(&mut ()));
+        return;
+    }
+    // 与p相等的元素左边的在前，保证稳定
+    let (left, right) = list.split_at(mid);
+    let (a, b) = if left.len() >= right.len() {
+        let a = left.len() / 2;
+        (a, partition_point(right, |x| compare(x, &left[a]) == Ordering::Less))
+    } else {
+        let b = right.len() / 2;
+        (partition_point(left, |x| compare(x, &right[b]) != Ordering::Greater), b)
+    };
+    list[a..mid + b].rotate_left(mid - a);
+    let (first, second) = list.split_at_mut(a + b);
+    thread::scope(|s| {
+        s.spawn(|| par_merge_halves(first, a, threads / 2, compare));
+        par_merge_halves(second, mid - a, threads - threads / 2, compare);
+    });
+}
+
+/// 并行快速排序，升序
+pub fn par_quick_sort<T: Ord + Send>(list: &mut [T], threads: usize) {
+    par_quick_sort_by(list, threads, T::cmp)
+}
+
+/// 按key升序
+pub fn par_quick_sort_by_key<T: Send, K: Ord, F: Fn(&T) -> K + Sync>(list: &mut [T], threads: usize, f: F) {
+    par_quick_sort_by(list, threads, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn par_quick_sort_by<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(list: &mut [T], threads: usize, compare: F) {
+    par_quick(list, threads, depth_limit(list.len()), &compare)
+}
+
+fn par_quick<T: Send, F: Fn(&T, &T) -> Ordering + Sync>(list: &mut [T], threads: usize, depth_limit: u32, compare: &F) {
+    if threads <= 1 || list.len() <= PARALLEL_THRESHOLD || depth_limit == 0 {
+        introsort(list, depth_limit, &mut { compare }, &mut Tracker::new(&mut ()));
+        return;
+    }
+    let mid = partition(list, &mut { compare }, &mut Tracker::new(&mut ()));
+    let (left, right) = list.split_at_mut(mid);
+    let right = &mut right[1..];
+    // 线程按长度分配，两边至少各一个
+    let left_threads = (threads * left.len() / (left.len() + right.len())).clamp(1, threads - 1);
+    thread::scope(|s| {
+        s.spawn(|| par_quick(left, left_threads, depth_limit - 1, compare));
+        par_quick(right, threads - left_threads, depth_limit - 1, compare);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::quick_sort_by;
+    use proptest::prelude::*;
+
+    /// 足够多次拆分的长度
+    const LEN: usize = PARALLEL_THRESHOLD * 8 + 123;
+
+    fn random(n: usize, seed: u64) -> Vec<u32> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x = x.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                (x >> 33) as u32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        let mut expected = random(LEN, 1);
+        let input = expected.clone();
+        expected.sort();
+        for threads in [0, 1, 2, 3, 8] {
+            let mut list = input.clone();
+            par_merge_sort(&mut list, threads);
+            assert_eq!(expected, list, "merge, {} threads", threads);
+            let mut list = input.clone();
+            par_quick_sort(&mut list, threads);
+            assert_eq!(expected, list, "quick, {} threads", threads);
+        }
+        let mut list: Vec<u32> = (0..LEN as u32).collect();
+        par_quick_sort_by(&mut list, 4, |a, b| b.cmp(a));
+        assert!(list.windows(2).all(|w| w[0] >= w[1]));
+        par_merge_sort(&mut [0_u8; 0], 4);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        // key只有16种，稳定排序的结果唯一；并行快速排序的结果与顺序版本一致
+        let input: Vec<(u32, u32)> = random(LEN, 2).into_iter().enumerate().map(|(i, x)| (x % 16, i as u32)).collect();
+        let mut stable = input.clone();
+        stable.sort_by_key(|&(k, _)| k);
+        let mut quick = input.clone();
+        quick_sort_by(&mut quick, |a, b| a.0.cmp(&b.0));
+        for threads in [2, 3, 8] {
+            let mut list = input.clone();
+            par_merge_sort_by_key(&mut list, threads, |&(k, _)| k);
+            assert_eq!(stable, list, "merge, {} threads", threads);
+            let mut list = input.clone();
+            par_quick_sort_by_key(&mut list, threads, |&(k, _)| k);
+            assert_eq!(quick, list, "quick, {} threads", threads);
+        }
+    }
+
+    #[test]
+    fn test_merge_uneven_halves() {
+        // 两边长度悬殊，key重复：拆分点落在相等元素之间时仍然稳定
+        for (left, right) in [(LEN / 10, LEN), (LEN, LEN / 10), (LEN, 1)] {
+            let tag = |side: u32, keys: Vec<u32>| -> Vec<(u32, u32)> {
+                let mut keys: Vec<u32> = keys.into_iter().map(|k| k % 64).collect();
+                keys.sort();
+                keys.into_iter().map(|k| (k, side)).collect()
+            };
+            let mut list = tag(0, random(left, 3));
+            list.extend(tag(1, random(right, 4)));
+            let mut expected = list.clone();
+            expected.sort();
+            for threads in [2, 3, 8] {
+                let mut merged = list.clone();
+                par_merge_halves(&mut merged, left, threads, &|a: &(u32, u32), b: &(u32, u32)| a.0.cmp(&b.0));
+                assert_eq!(expected, merged, "{} + {}, {} threads", left, right, threads);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adversarial() {
+        // 全部相等、锯齿：划分不均匀时仍然正确
+        let inputs: Vec<Vec<u32>> = vec![vec![7; LEN], (0..LEN as u32).map(|i| i % 2).collect()];
+        for input in inputs {
+            let mut expected = input.clone();
+            expected.sort();
+            let mut list = input;
+            par_quick_sort(&mut list, 4);
+            assert_eq!(expected, list);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+        #[test]
+        fn prop_matches_std(seed in any::<u64>(), len in 0..PARALLEL_THRESHOLD * 4, threads in 0_usize..6) {
+            let input = random(len, seed);
+            let mut expected = input.clone();
+            expected.sort();
+            let mut list = input.clone();
+            par_merge_sort(&mut list, threads);
+            prop_assert_eq!(&expected, &list);
+            let mut list = input;
+            par_quick_sort(&mut list, threads);
+            prop_assert_eq!(&expected, &list);
+        }
+    }
+}
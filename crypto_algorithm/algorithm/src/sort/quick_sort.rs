@@ -0,0 +1,215 @@
+//! # 快速排序（内省排序，introsort）O(nlogn)
+//! 选一个基准（pivot），把小于基准的元素放在左边、大于基准的放在右边，再分别排序两边
+//! ```code
+//!  pivot
+//!    ↓   l→                 ←r
+//! | 5 | 1 | 8 | 3 | 9 | 2 | 7 |      l停在>=5的元素，r停在<=5的元素，交换后继续
+//! | 5 | 1 | 2 | 3 | 9 | 8 | 7 |      l和r相遇
+//! | 3 | 1 | 2 | 5 | 9 | 8 | 7 |      基准与左半部分的最后一个元素交换
+//! ```
+//! 1. 三数取中：基准取首、中、尾三个元素的中位数，避免有序输入退化到O(n^2)
+//! 2. 与基准相等的元素两边都会停下交换，大量重复元素时仍然能均匀划分
+//! 3. 长度不超过16的小段使用插入排序
+//! 4. 递归深度超过2logn时改用堆排序，保证最坏情况O(nlogn)
+//! 5. 只递归较短的一边，较长的一边继续循环，栈深度不超过logn
+//!
+//! 不稳定
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/27
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::heap_sort::heap;
+use super::insert_sort::insert;
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
+
+/// 不超过这个长度的小段使用插入排序
+const INSERTION_CUTOFF: usize = 16;
+
+/// 快速排序，升序
+pub fn quick_sort<T: Ord>(list: &mut [T]) {
+    quick_sort_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn quick_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    quick_sort_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn quick_sort_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    quick(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+fn quick<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    introsort(list, depth_limit(list.len()), compare, tracker);
+}
+
+/// 递归深度上限2logn
+pub(crate) fn depth_limit(len: usize) -> u32 {
+    2 * (usize::BITS - len.leading_zeros())
+}
+
+pub(crate) fn introsort<T, F, O>(mut list: &mut [T], mut depth_limit: u32, compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    // list在整个切片中的起点
+    let mut base = 0;
+    loop {
+        if list.len() <= INSERTION_CUTOFF {
+            insert(list, compare, &mut tracker.sub(base));
+            return;
+        }
+        if depth_limit == 0 {
+            heap(list, compare, &mut tracker.sub(base));
+            return;
+        }
+        depth_limit -= 1;
+        let mid = partition(list, compare, &mut tracker.sub(base));
+        tracker.pass_complete::<T>();
+        let (left, right) = list.split_at_mut(mid);
+        let right = &mut right[1..];
+        if left.len() < right.len() {
+            introsort(left, depth_limit, compare, &mut tracker.sub(base));
+            list = right;
+            base += mid + 1;
+        } else {
+            introsort(right, depth_limit, compare, &mut tracker.sub(base + mid + 1));
+            list = left;
+        }
+    }
+}
+
+/// 把首、中、尾的中位数放到list[0]作为基准
+fn median_of_three<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    let (a, b, c) = (0, list.len() / 2, list.len() - 1);
+    if tracker.cmp(list, b, a, compare) == Ordering::Less {
+        tracker.swap(list, a, b);
+    }
+    if tracker.cmp(list, c, b, compare) == Ordering::Less {
+        tracker.swap(list, b, c);
+        if tracker.cmp(list, b, a, compare) == Ordering::Less {
+            tracker.swap(list, a, b);
+        }
+    }
+    tracker.swap(list, 0, b);
+}
+
+/// # 划分
+/// 返回基准的最终位置p：`list[..p]`都不大于基准，`list[p + 1..]`都不小于基准
+pub(crate) fn partition<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    median_of_three(list, compare, tracker);
+    partition_around_first(list, compare, tracker)
+}
+
+/// 以`list[0]`为基准划分，返回值与`partition`相同
+pub(crate) fn partition_around_first<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    // 基准在list[0]，list[1..l]不大于基准，list[r..]不小于基准
+    let (mut l, mut r) = (1, list.len());
+    loop {
+        while l < r && tracker.cmp(list, l, 0, compare) == Ordering::Less {
+            l += 1;
+        }
+        while l < r && tracker.cmp(list, r - 1, 0, compare) == Ordering::Greater {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        tracker.swap(list, l, r);
+        l += 1;
+    }
+    // list[l - 1]不大于基准，与基准交换
+    tracker.swap(list, 0, l - 1);
+    l - 1
+}
+
+/// # 快速排序器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuickSort;
+
+impl<T> Sorter<T> for QuickSort {
+    fn name(&self) -> &'static str {
+        "quick"
+    }
+    fn is_stable(&self) -> bool {
+        false
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        quick_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        quick(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut list = vec![5, 1, 8, 3, 9, 2, 7];
+        quick_sort(&mut list);
+        assert_eq!(list, vec![1, 2, 3, 5, 7, 8, 9]);
+        let mut list: Vec<u32> = (0..1000).rev().collect();
+        quick_sort_by_key(&mut list, |&x| x / 10);
+        assert!(list.windows(2).all(|w| w[0] / 10 <= w[1] / 10));
+    }
+
+    #[test]
+    fn test_partition() {
+        let mut list: Vec<u32> = (0..100).map(|i| i * 37 % 100).collect();
+        let p = partition(&mut list, &mut u32::cmp, &mut Tracker::new(&mut ()));
+        assert!(list[..p].iter().all(|x| *x <= list[p]));
+        assert!(list[p + 1..].iter().all(|x| *x >= list[p]));
+    }
+
+    #[test]
+    fn test_adversarial_inputs() {
+        // 全部相等、有序、逆序、锯齿，比较次数都应保持在O(nlogn)
+        let n = 10_000_usize;
+        let inputs: Vec<Vec<usize>> = vec![
+            vec![7; n],
+            (0..n).collect(),
+            (0..n).rev().collect(),
+            (0..n).map(|i| i % 2).collect(),
+            (0..n).map(|i| if i < n / 2 { i } else { n - i }).collect(),
+        ];
+        for input in inputs {
+            let mut expected = input.clone();
+            expected.sort();
+            let mut list = input;
+            let mut comparisons = 0;
+            quick_sort_by(&mut list, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+            assert_eq!(expected, list);
+            assert!(comparisons < 4 * n * 14, "{} comparisons", comparisons);
+        }
+    }
+}
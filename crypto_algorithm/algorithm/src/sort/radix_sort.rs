@@ -0,0 +1,279 @@
+//! # 基数排序 O(n*k)
+//! 不比较元素，按key的每一个字节分配到256个桶中
+//! ## LSD（最低位优先）：radix_sort
+//! 从最低字节到最高字节，每一轮按当前字节做一次稳定的计数排序
+//! ```code
+//!            按低字节           按高字节
+//! | 0x0203 |          | 0x0101 |          | 0x0101 |
+//! | 0x0101 |   ——>    | 0x0102 |   ——>    | 0x0102 |
+//! | 0x0102 |          | 0x0203 |          | 0x0203 |
+//! ```
+//! 整数和浮点数先转换成无符号数，使无符号数的大小顺序与原来的顺序一致：
+//! 1. 有符号整数：翻转符号位
+//! 2. 浮点数：正数翻转符号位，负数翻转所有位，结果与`total_cmp`的顺序一致（-NaN < -inf < -0 < +0 < inf < NaN）
+//!
+//! 所有元素在某一字节上都相同时跳过这一轮
+//! ## MSD（最高位优先）：msd_radix_sort
+//! 按第一个字节分桶，再按下一个字节排序每个桶，较短的字符串（已经结束）排在最前面。
+//! 桶内原地交换（American flag sort），小桶使用插入排序。
+//! 待排序的桶放在一个显式的栈中而不是递归，很长的公共前缀不会导致栈溢出
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/28
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::insert_sort::insert_sort_by;
+
+/// # 基数排序的key
+/// `byte(i)`是第i个字节（0为最低字节），按字节从高到低比较与原来的顺序一致
+pub trait RadixKey: Copy {
+    /// 字节数
+    const BYTES: usize;
+    fn byte(&self, i: usize) -> u8;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($t:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            fn byte(&self, i: usize) -> u8 {
+                (*self >> (8 * i)) as u8
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_radix_key_signed {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            fn byte(&self, i: usize) -> u8 {
+                ((*self as $u ^ (1 << (<$u>::BITS - 1))) >> (8 * i)) as u8
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_radix_key_float {
+    ($($t:ty => $u:ty),*) => {$(
+        impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            fn byte(&self, i: usize) -> u8 {
+                let bits = self.to_bits();
+                let sign = 1 << (<$u>::BITS - 1);
+                let bits = if bits & sign == 0 { bits ^ sign } else { !bits };
+                (bits >> (8 * i)) as u8
+            }
+        }
+    )*};
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+impl_radix_key_float!(f32 => u32, f64 => u64);
+
+/// # LSD基数排序，升序
+/// 需要一个与list等长的缓冲区，稳定
+pub fn radix_sort<T: RadixKey>(list: &mut [T]) {
+    if list.len() < 2 {
+        return;
+    }
+    let mut buf = list.to_vec();
+    // 数据当前是否在buf中
+    let mut in_buf = false;
+    for i in 0..T::BYTES {
+        let (src, dst) = if in_buf {
+            (&buf[..], &mut list[..])
+        } else {
+            (&list[..], &mut buf[..])
+        };
+        let mut counts = [0_usize; 256];
+        for x in src {
+            counts[x.byte(i) as usize] += 1;
+        }
+        if counts.contains(&src.len()) {
+            // 所有元素这一字节都相同
+            continue;
+        }
+        let mut offset = 0;
+        for count in counts.iter_mut() {
+            let c = *count;
+            *count = offset;
+            offset += c;
+        }
+        for x in src {
+            let bucket = &mut counts[x.byte(i) as usize];
+            dst[*bucket] = *x;
+            *bucket += 1;
+        }
+        in_buf = !in_buf;
+    }
+    if in_buf {
+        list.copy_from_slice(&buf);
+    }
+}
+
+/// 不超过这个长度的桶使用插入排序
+const MSD_CUTOFF: usize = 32;
+
+/// # MSD基数排序，按字节的字典序升序
+/// 原地排序，不稳定
+pub fn msd_radix_sort<T: AsRef<[u8]>>(list: &mut [T]) {
+    // 待排序的区间，区间内所有元素的前depth个字节都相同
+    let mut pending = vec![(0, list.len(), 0)];
+    while let Some((start, end, depth)) = pending.pop() {
+        let part = &mut list[start..end];
+        if part.len() <= MSD_CUTOFF {
+            insert_sort_by(part, |a, b| suffix(a, depth).cmp(suffix(b, depth)));
+            continue;
+        }
+        let counts = distribute(part, depth);
+        // 桶0中的字符串已经结束，彼此相等
+        let mut offset = start + counts[0];
+        for &count in &counts[1..] {
+            if count > 1 {
+                pending.push((offset, offset + count, depth + 1));
+            }
+            offset += count;
+        }
+    }
+}
+
+/// 第depth个字节，字符串已经结束时为0，否则为字节值加1
+fn bucket_of<T: AsRef<[u8]>>(item: &T, depth: usize) -> usize {
+    item.as_ref().get(depth).map_or(0, |&b| b as usize + 1)
+}
+
+/// 从第depth个字节开始的部分
+fn suffix<T: AsRef<[u8]>>(item: &T, depth: usize) -> &[u8] {
+    let bytes = item.as_ref();
+    &bytes[depth.min(bytes.len())..]
+}
+
+/// 按第depth个字节把list原地分到257个桶中，返回每个桶的元素个数
+fn distribute<T: AsRef<[u8]>>(list: &mut [T], depth: usize) -> [usize; 257] {
+    let mut counts = [0_usize; 257];
+    for item in list.iter() {
+        counts[bucket_of(item, depth)] += 1;
+    }
+    // 每个桶的起点，next是桶中下一个待放置的位置
+    let mut starts = [0_usize; 257];
+    let mut offset = 0;
+    for (start, count) in starts.iter_mut().zip(counts.iter()) {
+        *start = offset;
+        offset += count;
+    }
+    let mut next = starts;
+    for bucket in 0..257 {
+        let end = starts[bucket] + counts[bucket];
+        while next[bucket] < end {
+            let i = next[bucket];
+            let target = bucket_of(&list[i], depth);
+            if target == bucket {
+                next[bucket] += 1;
+            } else {
+                // 把元素交换到它所在的桶，换回来的元素继续判断
+                list.swap(i, next[target]);
+                next[target] += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_radix_sort() {
+        let mut list = vec![0x0203_u16, 0x0101, 0x0102, 0, u16::MAX];
+        radix_sort(&mut list);
+        assert_eq!(vec![0, 0x0101, 0x0102, 0x0203, u16::MAX], list);
+        let mut list = vec![3_i64, -1, i64::MIN, 0, i64::MAX, -300];
+        radix_sort(&mut list);
+        assert_eq!(vec![i64::MIN, -300, -1, 0, 3, i64::MAX], list);
+        let mut list = vec![1.5_f64, -0.0, f64::NEG_INFINITY, 0.0, -2.5, f64::INFINITY, f64::NAN];
+        radix_sort(&mut list);
+        assert_eq!("[-inf, -2.5, -0.0, 0.0, 1.5, inf, NaN]", format!("{:?}", list));
+    }
+
+    #[test]
+    fn test_msd_radix_sort() {
+        let mut list = vec!["banana", "", "apple", "app", "b", "apple", "Zebra"];
+        msd_radix_sort(&mut list);
+        assert_eq!(vec!["", "Zebra", "app", "apple", "apple", "b", "banana"], list);
+        let mut list: Vec<Vec<u8>> = (0..200_u32).rev().map(|i| i.to_string().into_bytes()).collect();
+        let mut expected = list.clone();
+        expected.sort();
+        msd_radix_sort(&mut list);
+        assert_eq!(expected, list);
+    }
+
+    #[test]
+    fn test_msd_radix_sort_long_prefix() {
+        // 每一层只有一个非空的桶，按层递归会栈溢出
+        let mut list = vec![vec![b'a'; 5000]; 40];
+        list[7].push(b'b');
+        list[20].pop();
+        let mut expected = list.clone();
+        expected.sort();
+        msd_radix_sort(&mut list);
+        assert_eq!(expected, list);
+        // 每一层只分出一个已经结束的字符串
+        let mut list: Vec<Vec<u8>> = (0..3000).rev().map(|len| vec![b'a'; len]).collect();
+        let mut expected = list.clone();
+        expected.sort();
+        msd_radix_sort(&mut list);
+        assert_eq!(expected, list);
+    }
+
+    macro_rules! prop_radix {
+        ($($name:ident: $t:ty),*) => {
+            proptest! {$(
+                #[test]
+                fn $name(data in prop::collection::vec(any::<$t>(), 0..300)) {
+                    let mut expected = data.clone();
+                    expected.sort();
+                    let mut actual = data;
+                    radix_sort(&mut actual);
+                    prop_assert_eq!(expected, actual);
+                }
+            )*}
+        };
+    }
+
+    prop_radix!(
+        prop_u8: u8, prop_u16: u16, prop_u32: u32, prop_u64: u64, prop_u128: u128, prop_usize: usize,
+        prop_i8: i8, prop_i16: i16, prop_i32: i32, prop_i64: i64, prop_i128: i128, prop_isize: isize
+    );
+
+    proptest! {
+        #[test]
+        fn prop_floats(data in prop::collection::vec(any::<f64>(), 0..300), small in prop::collection::vec(any::<f32>(), 0..300)) {
+            let mut expected = data.clone();
+            expected.sort_by(f64::total_cmp);
+            let mut actual = data;
+            radix_sort(&mut actual);
+            prop_assert!(expected.iter().map(|x| x.to_bits()).eq(actual.iter().map(|x| x.to_bits())));
+            let mut expected = small.clone();
+            expected.sort_by(f32::total_cmp);
+            let mut actual = small;
+            radix_sort(&mut actual);
+            prop_assert!(expected.iter().map(|x| x.to_bits()).eq(actual.iter().map(|x| x.to_bits())));
+        }
+
+        #[test]
+        fn prop_byte_strings(data in prop::collection::vec(prop::collection::vec(0_u8..4, 0..6), 0..400)) {
+            // 字母表很小，产生大量公共前缀和相等的字符串
+            let mut expected = data.clone();
+            expected.sort();
+            let mut actual = data;
+            msd_radix_sort(&mut actual);
+            prop_assert_eq!(expected, actual);
+        }
+    }
+}
@@ -1,5 +1,7 @@
-//! # 选择排序
+//! # 选择排序 O(n^2)
 //! 选择第一位开始比较，遍历剩下的元素，找到比第一位小的与第一位交换
+//!
+//! 交换会越过中间的元素，因此不稳定；交换次数最多n-1次
 //! ```txt
 //! @author:syf20020816@Outlook.com
 //! @date:2023/10/9
@@ -7,24 +9,62 @@
 //! @description:
 //! ```
 
-use std::fmt::Debug;
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
 
-fn select_sort(list: &mut Vec<u32>) -> () {
-    for i in 0..list.len() - 1 {
+/// 选择排序，升序
+pub fn select_sort<T: Ord>(list: &mut [T]) {
+    select_sort_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn select_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    select_sort_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn select_sort_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    select(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+fn select<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    for i in 0..list.len() {
         let mut smaller_index = i;
-        let mut smaller = list[i];
         for j in (i + 1)..list.len() {
-            if smaller >= list[j] {
+            if tracker.cmp(list, j, smaller_index, compare) == Ordering::Less {
                 // 记录更小的元素下标
                 smaller_index = j;
-                smaller = list[j];
             }
         }
         //交换元素
-        list.swap(i, smaller_index);
+        tracker.swap(list, i, smaller_index);
+        tracker.pass_complete::<T>();
     }
 }
 
+/// # 选择排序器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectSort;
+
+impl<T> Sorter<T> for SelectSort {
+    fn name(&self) -> &'static str {
+        "select"
+    }
+    fn is_stable(&self) -> bool {
+        false
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        select_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        select(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -35,6 +75,16 @@ mod tests {
         let mut list = vec![4, 2, 6, 7, 1, 9];
         select_sort(&mut list);
         assert_eq!(list, vec![1, 2, 4, 6, 7, 9]);
+        let mut empty: Vec<u32> = vec![];
+        select_sort(&mut empty);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_by() {
+        let mut list = vec![-4_i32, 2, -6, 7, 1];
+        select_sort_by_key(&mut list, |x| x.abs());
+        assert_eq!(list, vec![1, 2, -4, -6, 7]);
+        select_sort_by(&mut list, |a, b| b.cmp(a));
+        assert_eq!(list, vec![7, 2, 1, -4, -6]);
+    }
+}
@@ -0,0 +1,111 @@
+//! # 希尔排序 O(n^1.3)
+//! 按间隔gap把序列分成若干组，每组做插入排序；gap逐渐缩小，最后一轮gap为1就是普通的插入排序
+//! ```code
+//! gap = 4: | 8 | 3 | 6 | 1 | 2 | 7 | 5 | 4 |
+//!            ↑_______________↑                 每组两个元素
+//! gap = 1: | 2 | 3 | 5 | 1 | 8 | 7 | 6 | 4 |   前面几轮之后已经基本有序
+//! ```
+//! 间隔使用Ciura序列`1, 4, 10, 23, 57, 132, 301, 701, 1750`，更大的间隔按2.25倍扩展；不稳定
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/27
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::observer::Tracker;
+use super::{SortObserver, Sorter};
+use std::cmp::Ordering;
+
+/// Ciura通过实验得到的间隔序列
+const CIURA_GAPS: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+
+/// 希尔排序，升序
+pub fn shell_sort<T: Ord>(list: &mut [T]) {
+    shell_sort_by(list, T::cmp)
+}
+
+/// 按key升序
+pub fn shell_sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(list: &mut [T], mut f: F) {
+    shell_sort_by(list, |a, b| f(a).cmp(&f(b)))
+}
+
+/// 使用比较器排序
+pub fn shell_sort_by<T, F: FnMut(&T, &T) -> Ordering>(list: &mut [T], mut compare: F) {
+    shell(list, &mut compare, &mut Tracker::new(&mut ()))
+}
+
+fn shell<T, F, O>(list: &mut [T], compare: &mut F, tracker: &mut Tracker<'_, O>)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    O: SortObserver<T> + ?Sized,
+{
+    for gap in gaps(list.len()).into_iter().rev() {
+        for i in gap..list.len() {
+            let mut flag = i;
+            while flag >= gap && tracker.cmp(list, flag, flag - gap, compare) == Ordering::Less {
+                tracker.swap(list, flag, flag - gap);
+                flag -= gap;
+            }
+        }
+        tracker.pass_complete::<T>();
+    }
+}
+
+/// 小于len的间隔，从小到大
+fn gaps(len: usize) -> Vec<usize> {
+    let mut gaps: Vec<usize> = CIURA_GAPS.iter().copied().take_while(|&gap| gap < len).collect();
+    if gaps.len() == CIURA_GAPS.len() {
+        let mut gap = CIURA_GAPS[CIURA_GAPS.len() - 1];
+        loop {
+            gap = gap * 9 / 4;
+            if gap >= len {
+                break;
+            }
+            gaps.push(gap);
+        }
+    }
+    gaps
+}
+
+/// # 希尔排序器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShellSort;
+
+impl<T> Sorter<T> for ShellSort {
+    fn name(&self) -> &'static str {
+        "shell"
+    }
+    fn is_stable(&self) -> bool {
+        false
+    }
+    fn sort_by(&self, data: &mut [T], compare: &mut dyn FnMut(&T, &T) -> Ordering) {
+        shell_sort_by(data, compare)
+    }
+    fn sort_observed(&self, data: &mut [T], mut compare: &mut dyn FnMut(&T, &T) -> Ordering, observer: &mut dyn SortObserver<T>) {
+        shell(data, &mut compare, &mut Tracker::new(observer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut list = vec![8, 3, 6, 1, 2, 7, 5, 4];
+        shell_sort(&mut list);
+        assert_eq!(list, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut list: Vec<i32> = (0..5000).map(|i| (i * 7919) % 5003 - 2500).collect();
+        shell_sort_by(&mut list, |a, b| b.cmp(a));
+        assert!(list.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_gaps() {
+        assert!(gaps(0).is_empty());
+        assert_eq!(vec![1], gaps(2));
+        assert_eq!(vec![1, 4, 10], gaps(23));
+        assert_eq!(vec![1, 4, 10, 23, 57, 132, 301, 701, 1750, 3937, 8858], gaps(10_000));
+    }
+}
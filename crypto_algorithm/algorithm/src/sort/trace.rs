@@ -0,0 +1,286 @@
+//! # 排序过程追踪
+//! 记录排序过程中的每一次比较、交换、写入，再按顺序重放为字符画
+//! ```code
+//! let mut data = vec![4, 1, 2, 10, 7];
+//! let events = trace(&BubbleSort, &mut data);
+//! print!("{}", render(&[4, 1, 2, 10, 7], &events));
+//! ```
+//! ```code
+//! swap(0, 1)
+//!   |————|
+//!   ↓    ↓
+//! |————————————————————————|
+//! | 1  | 4  | 2  | 10 | 7  |
+//! |————————————————————————|
+//! ```
+//! 排序算法中没有随机性，同样的输入总是得到同样的事件序列，可以直接与快照比较
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/31
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use super::{SortObserver, Sorter};
+use std::fmt::Display;
+
+/// # 排序事件
+/// 下标都是元素在整个切片中的位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SortEvent<T> {
+    /// 比较了位置i和位置j的元素
+    Compare(usize, usize),
+    /// 交换了位置i和位置j的元素
+    Swap(usize, usize),
+    /// 位置i写入了一个值，只有归并排序会产生
+    Write(usize, T),
+    /// 完成了一轮：
+    /// - bubble：一趟冒泡
+    /// - insert：插入一个元素
+    /// - select：确定一个位置
+    /// - shell：一个间隔
+    /// - heap：建堆，之后每取出一次堆顶
+    /// - merge：一次合并；merge_bottom_up：同一宽度的所有合并
+    /// - quick：一次划分
+    ///
+    /// quick和merge中的小段使用插入排序，也会产生插入排序的事件
+    PassComplete,
+}
+
+impl<T: Clone> SortEvent<T> {
+    /// 把事件作用到data上，比较和完成一轮不改变data
+    pub fn apply(&self, data: &mut [T]) {
+        match self {
+            SortEvent::Swap(i, j) => data.swap(*i, *j),
+            SortEvent::Write(i, value) => data[*i] = value.clone(),
+            SortEvent::Compare(..) | SortEvent::PassComplete => {}
+        }
+    }
+}
+
+impl<T: Clone> SortObserver<T> for Vec<SortEvent<T>> {
+    fn compare(&mut self, i: usize, j: usize) {
+        self.push(SortEvent::Compare(i, j));
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        self.push(SortEvent::Swap(i, j));
+    }
+    fn write(&mut self, i: usize, value: &T) {
+        self.push(SortEvent::Write(i, value.clone()));
+    }
+    fn pass_complete(&mut self) {
+        self.push(SortEvent::PassComplete);
+    }
+}
+
+/// 升序排序data，返回排序过程中的全部事件
+pub fn trace<T: Ord + Clone>(sorter: &dyn Sorter<T>, data: &mut [T]) -> Vec<SortEvent<T>> {
+    let mut events = Vec::new();
+    sorter.sort_observed(data, &mut T::cmp, &mut events);
+    events
+}
+
+/// # 逐帧重放
+/// 第一帧是初始状态，之后每个事件一帧，帧中是事件作用之后的状态
+pub struct Frames<'a, T> {
+    state: Vec<String>,
+    events: std::slice::Iter<'a, SortEvent<T>>,
+    /// 每个格子中值的宽度
+    width: usize,
+    passes: usize,
+    started: bool,
+}
+
+/// 从初始状态initial开始重放events
+pub fn frames<'a, T: Display>(initial: &[T], events: &'a [SortEvent<T>]) -> Frames<'a, T> {
+    let state: Vec<String> = initial.iter().map(T::to_string).collect();
+    let written = events.iter().filter_map(|event| match event {
+        SortEvent::Write(_, value) => Some(value.to_string().chars().count()),
+        _ => None,
+    });
+    let width = state.iter().map(|s| s.chars().count()).chain(written).max().unwrap_or(1);
+    Frames {
+        state,
+        events: events.iter(),
+        width,
+        passes: 0,
+        started: false,
+    }
+}
+
+/// 把所有帧拼接在一起，帧之间空一行
+pub fn render<T: Display>(initial: &[T], events: &[SortEvent<T>]) -> String {
+    frames(initial, events).collect::<Vec<_>>().join("\n")
+}
+
+impl<T: Display> Iterator for Frames<'_, T> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if !self.started {
+            self.started = true;
+            return Some(self.frame("initial".to_string(), &[]));
+        }
+        let event = self.events.next()?;
+        let (title, markers) = match event {
+            SortEvent::Compare(i, j) => (format!("compare({}, {})", i, j), vec![self.arrows(&[*i, *j])]),
+            SortEvent::Swap(i, j) => {
+                self.state.swap(*i, *j);
+                (format!("swap({}, {})", i, j), vec![self.bracket(*i, *j), self.arrows(&[*i, *j])])
+            }
+            SortEvent::Write(i, value) => {
+                self.state[*i] = value.to_string();
+                (format!("write({}, {})", i, value), vec![self.arrows(&[*i])])
+            }
+            SortEvent::PassComplete => {
+                self.passes += 1;
+                (format!("pass {} complete", self.passes), vec![])
+            }
+        };
+        Some(self.frame(title, &markers))
+    }
+}
+
+impl<T> Frames<'_, T> {
+    /// 第i个格子中值的第一个字符所在的列
+    fn column(&self, i: usize) -> usize {
+        i * (self.width + 3) + 2
+    }
+
+    /// 指向格子的箭头
+    fn arrows(&self, cells: &[usize]) -> String {
+        let mut line = vec![' '; self.column(cells.iter().copied().max().unwrap_or(0)) + 1];
+        for &i in cells {
+            line[self.column(i)] = '↓';
+        }
+        line.into_iter().collect()
+    }
+
+    /// 连接两个格子的括号
+    fn bracket(&self, i: usize, j: usize) -> String {
+        let (from, to) = (self.column(i.min(j)), self.column(i.max(j)));
+        let mut line = vec![' '; to + 1];
+        for c in &mut line[from..to] {
+            *c = '—';
+        }
+        line[from] = '|';
+        line[to] = '|';
+        line.into_iter().collect()
+    }
+
+    fn frame(&self, title: String, markers: &[String]) -> String {
+        let cells: String = self.state.iter().map(|s| format!("| {:<w$} ", s, w = self.width)).collect();
+        let row = cells + "|";
+        let len = row.chars().count();
+        let border = if len < 2 {
+            "|".to_string()
+        } else {
+            format!("|{}|", "—".repeat(len - 2))
+        };
+        let mut lines = vec![title];
+        lines.extend(markers.iter().cloned());
+        lines.extend([border.clone(), row, border]);
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::{sorters, BubbleSort, InsertSort, MergeSort};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_bubble_frames() {
+        let events = trace(&BubbleSort, &mut [4, 1, 2, 10, 7]);
+        let expected = "\
+initial
+|————————————————————————|
+| 4  | 1  | 2  | 10 | 7  |
+|————————————————————————|
+
+compare(0, 1)
+  ↓    ↓
+|————————————————————————|
+| 4  | 1  | 2  | 10 | 7  |
+|————————————————————————|
+
+swap(0, 1)
+  |————|
+  ↓    ↓
+|————————————————————————|
+| 1  | 4  | 2  | 10 | 7  |
+|————————————————————————|
+";
+        let frames: Vec<String> = frames(&[4, 1, 2, 10, 7], &events).collect();
+        assert_eq!(expected, frames[..3].join("\n"));
+        assert_eq!(
+            "\
+pass 2 complete
+|————————————————————————|
+| 1  | 2  | 4  | 7  | 10 |
+|————————————————————————|
+",
+            frames[frames.len() - 1]
+        );
+        assert_eq!(events.len() + 1, frames.len());
+    }
+
+    #[test]
+    fn test_events() {
+        use SortEvent::*;
+        let mut data = [3, 1, 2];
+        assert_eq!(
+            vec![
+                Compare(1, 0),
+                Swap(1, 0),
+                PassComplete,
+                Compare(2, 1),
+                Swap(2, 1),
+                Compare(1, 0),
+                PassComplete
+            ],
+            trace(&InsertSort, &mut data)
+        );
+        assert_eq!([1, 2, 3], data);
+        // 不超过16个元素时归并排序直接使用插入排序
+        assert_eq!(vec![Compare(1, 0), PassComplete], trace(&MergeSort, &mut [1, 2]));
+    }
+
+    #[test]
+    fn test_render_empty_and_wide() {
+        assert_eq!("initial\n|\n|\n|\n", render::<u8>(&[], &[]));
+        let events = vec![SortEvent::Write(0, 1000), SortEvent::Compare(1, 0)];
+        assert_eq!(
+            "\
+initial
+|—————————————|
+| 9    | 8    |
+|—————————————|
+
+write(0, 1000)
+  ↓
+|—————————————|
+| 1000 | 8    |
+|—————————————|
+
+compare(1, 0)
+  ↓      ↓
+|—————————————|
+| 1000 | 8    |
+|—————————————|
+",
+            render(&[9, 8], &events)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_trace_is_deterministic(data in prop::collection::vec(0_u8..20, 0..100)) {
+            for sorter in sorters::<u8>() {
+                let (mut a, mut b) = (data.clone(), data.clone());
+                prop_assert_eq!(trace(sorter.as_ref(), &mut a), trace(sorter.as_ref(), &mut b), "{}", sorter.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! # HashMap基准测试
+//! 与`std::collections::HashMap`对比插入、命中查询、未命中查询和删除
+//! ```txt
+//! cargo bench -p structure --bench hash_map
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::HashMap as StdHashMap;
+use structure::HashMap;
+
+const SIZES: [u64; 3] = [1_000, 10_000, 100_000];
+
+/// 简单的线性同余生成器，保证每次运行使用相同的键
+fn keys(n: u64) -> Vec<u64> {
+    let mut x = 0x2545_f491_4f6c_dd1d_u64;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            x
+        })
+        .collect()
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+    for n in SIZES {
+        let keys = keys(n);
+        group.bench_with_input(BenchmarkId::new("structure", n), &keys, |b, keys| {
+            b.iter(|| {
+                let mut map = HashMap::new();
+                for &k in keys {
+                    map.insert(k, k);
+                }
+                black_box(map)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("std", n), &keys, |b, keys| {
+            b.iter(|| {
+                let mut map = StdHashMap::new();
+                for &k in keys {
+                    map.insert(k, k);
+                }
+                black_box(map)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get");
+    for n in SIZES {
+        let keys = keys(n * 2);
+        let (present, absent) = keys.split_at(n as usize);
+        let ours: HashMap<u64, u64> = present.iter().map(|&k| (k, k)).collect();
+        let std: StdHashMap<u64, u64> = present.iter().map(|&k| (k, k)).collect();
+        group.bench_function(BenchmarkId::new("structure/hit", n), |b| {
+            b.iter(|| present.iter().filter_map(|k| ours.get(k)).count())
+        });
+        group.bench_function(BenchmarkId::new("std/hit", n), |b| {
+            b.iter(|| present.iter().filter_map(|k| std.get(k)).count())
+        });
+        group.bench_function(BenchmarkId::new("structure/miss", n), |b| {
+            b.iter(|| absent.iter().filter_map(|k| ours.get(k)).count())
+        });
+        group.bench_function(BenchmarkId::new("std/miss", n), |b| {
+            b.iter(|| absent.iter().filter_map(|k| std.get(k)).count())
+        });
+    }
+    group.finish();
+}
+
+fn bench_remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("remove");
+    for n in SIZES {
+        let keys = keys(n);
+        let ours: HashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        let std: StdHashMap<u64, u64> = keys.iter().map(|&k| (k, k)).collect();
+        group.bench_function(BenchmarkId::new("structure", n), |b| {
+            b.iter_batched(
+                || ours.clone(),
+                |mut map| {
+                    for k in &keys {
+                        map.remove(k);
+                    }
+                    map
+                },
+                criterion::BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(BenchmarkId::new("std", n), |b| {
+            b.iter_batched(
+                || std.clone(),
+                |mut map| {
+                    for k in &keys {
+                        map.remove(k);
+                    }
+                    map
+                },
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_get, bench_remove);
+criterion_main!(benches);
@@ -4,10 +4,57 @@ pub mod rs_array;
 mod rs_stack;
 /// 队列结构
 mod rs_queue;
+/// 双端队列结构
+mod rs_deque;
 /// 链表结构
 mod rs_linked_list;
+/// 双向链表结构
+mod rs_doubly_linked_list;
+/// 堆结构
+mod rs_heap;
+/// 哈希表结构
+pub mod rs_hash_map;
+/// 有序映射结构
+pub mod rs_ordered_map;
+/// 图结构
+pub mod rs_graph;
+/// 并查集
+mod rs_union_find;
+/// 字典树
+pub mod rs_trie;
+/// 线段树
+pub mod rs_segment_tree;
+/// 并发栈
+mod rs_concurrent_stack;
+/// 有界MPMC队列
+mod rs_mpmc_queue;
+/// 持久化链表
+mod rs_persistent_list;
+/// 持久化向量
+mod rs_persistent_vector;
+/// 序列化支持
+#[cfg(feature = "serde")]
+mod serde_impls;
+/// 并发容器使用的同步原语
+mod sync;
 /// vector结构
-mod rs_vec;
-pub use rs_stack::Stack;
+pub mod rs_vec;
+pub use rs_stack::{Doubling, Fixed, GrowthPolicy, OneThird, Stack, StackOverflow};
 pub use rs_queue::Queue;
-pub use rs_linked_list::*;
\ No newline at end of file
+pub use rs_deque::Deque;
+pub use rs_vec::Vector;
+pub use rs_hash_map::HashMap;
+pub use rs_ordered_map::OrderedMap;
+pub use rs_graph::{Edge, Graph};
+pub use rs_union_find::UnionFind;
+pub use rs_trie::Trie;
+pub use rs_segment_tree::{Monoid, SegmentTree};
+pub use rs_concurrent_stack::ConcurrentStack;
+pub use rs_mpmc_queue::BoundedMpmcQueue;
+pub use rs_persistent_list::PersistentList;
+pub use rs_persistent_vector::PersistentVector;
+pub use rs_heap::{BinaryHeap, Compare, IndexedPriorityQueue, Max, Min, PeekMut};
+pub use rs_linked_list::*;
+pub use rs_doubly_linked_list::{CursorMut, DoublyLinkedList};
+#[cfg(feature = "serde")]
+pub use serde_impls::WithCapacity;
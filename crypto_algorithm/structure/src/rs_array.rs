@@ -13,20 +13,28 @@
 //!        ---------------------------------
 //! index: | 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 |
 //!        ---------------------------------
-//!        |->           len             <-|
-//!          ↑
-//!       current
+//!        |->    len    <-|
+//!                          ↑
+//!                       current
 //! ```
+//! 有界数组在`new_bind`时预先占满全部槽位，`current`之后是尚未写入的占位元素。
+//! 除`push`会覆盖占位元素外，所有读写、删除和迭代都只作用于已写入的`[0, current)`
 //!
 //! ## Functions
 //!
 //! 1. new (init an empty array)
 //! 2. new_bind (init an array with binding length)
-//! 3. push (add a new element into array)
+//! 3. push / try_push (write a new element at the `current` cursor)
 //! 4. pop (remove the end element from array)
-//! 5. remove (remove an array element with a specified index)
+//! 5. remove / try_remove (remove an array element with a specified index)
 //! 6. is_empty (judge array is empty or not)
-//! 7. is_full (judge if the array is full)
+//! 7. is_full (judge if a bounded array is full)
+//! 8. get / set (read or write an element with a specified index)
+//! 9. insert / try_insert (insert an element at a specified index)
+//! 10. swap / splice / retain (index-aware edits, element indices always match positions)
+//! 11. iter / iter_mut / enumerate (iterate over element data)
+//!
+//! `Array<T>` also implements `Index`/`IndexMut`, `IntoIterator`, `FromIterator` and `Extend`
 //!
 //!
 //!
@@ -38,16 +46,49 @@
 //! ```
 
 
-use std::ops::{Deref, DerefMut};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
 
-/// 数组
+/// # 数组
+/// - `length`：当前持有的槽位数量，包括占位元素
+/// - `max`：容量上限，`new_bind`创建的有界数组为`Some(capacity)`，`new`创建的数组为`None`
+/// - `current`：写入游标，指向下一次`push`写入的位置，也是数组的长度。
+///   有界数组中`[0, current)`为已写入的元素，`[current, length)`为`new_bind`预填充的占位元素，
+///   `push`会依次覆盖这些占位元素，占位元素对外不可见；无界数组中`current`始终等于`length`
 #[derive(Debug, Clone)]
 pub struct Array<T> {
     elements: Vec<Element<T>>,
     length: u32,
     current: u32,
+    max: Option<u32>,
+}
+
+/// # 数组操作错误
+/// 1. Full：有界数组已满
+/// 2. Empty：数组为空
+/// 3. OutOfBounds：下标越界
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayError {
+    Full,
+    Empty,
+    OutOfBounds { index: u32, length: u32 },
 }
 
+impl Display for ArrayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrayError::Full => f.write_str("array is full"),
+            ArrayError::Empty => f.write_str("array is empty"),
+            ArrayError::OutOfBounds { index, length } => {
+                write!(f, "index {} out of bounds for array of length {}", index, length)
+            }
+        }
+    }
+}
+
+impl Error for ArrayError {}
+
 /// # 数组的元素
 /// ## Function
 /// 1. new
@@ -55,89 +96,397 @@ pub struct Array<T> {
 /// 3. get_data (get data use clone)
 /// 4. get_data_ref (get ref)
 /// 5. get_index (index ref)
-/// 6. mount_next (set next element)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Element<T> {
     data: T,
     index: u32,
 }
 
-macro_rules! element_impl {
-    ($($el:ty)*) => ($(
-    impl Element<$el>{
-        pub fn new(data: $el, index: u32) -> Self {
-           Element {
-                data,
-                index
-           }
-        }
-        pub fn set_data(&mut self, data: $el) -> () {
-            self.data = data
-        }
-        pub fn get_data(&self) -> $el {
-            self.data.clone()
-        }
-        pub fn get_data_ref(&self) -> &$el {
-            &self.data
-        }
-        pub fn get_index(&self) -> &u32 {
-            &self.index
-        }
+impl<T> Element<T> {
+    pub fn new(data: T, index: u32) -> Self {
+        Element { data, index }
+    }
+    pub fn set_data(&mut self, data: T) {
+        self.data = data
+    }
+    pub fn get_data(&self) -> T
+    where
+        T: Clone,
+    {
+        self.data.clone()
+    }
+    pub fn get_data_ref(&self) -> &T {
+        &self.data
+    }
+    /// 获取数据域的可变引用
+    pub fn get_data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+    pub fn get_index(&self) -> &u32 {
+        &self.index
+    }
+    /// 取出数据域
+    pub fn into_data(self) -> T {
+        self.data
     }
-    )*);
 }
 
-element_impl! {u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 bool f32 f64 &'static str String}
-
-
-impl Array<u8> {
+impl<T> Array<T> {
+    /// 初始化无界空数组
     pub fn new() -> Self {
-        //empty
-        // if use new_bind : self.max = Some(u32)
-        // new : self.max = None
-        Array { elements: Vec::new(), length: 0, current: 0 }
-    }
-    /// 预先分配
-    pub fn new_bind(length: u32) -> Self {
-        let mut elements = Vec::new();
-        for i in 0..length {
-            elements.push(Element::<u8>::new(0 as u8, i as u32))
-        };
+        Array { elements: Vec::new(), length: 0, current: 0, max: None }
+    }
+    /// # 预先分配
+    /// 创建容量为`length`的有界数组，使用`T::default()`填充每一位，写入游标位于0
+    pub fn new_bind(length: u32) -> Self
+    where
+        T: Default,
+    {
+        let elements = (0..length)
+            .map(|i| Element::new(T::default(), i))
+            .collect();
         Array {
             elements,
             length,
             current: 0,
+            max: Some(length),
         }
     }
-    fn get_current(&self) -> &u32 {
+    /// 写入游标
+    pub fn get_current(&self) -> &u32 {
         &self.current
     }
-    fn get_length(&self) -> &u32 {
-        &self.length
+    /// 数组长度：已写入的元素数量，不包括占位元素
+    pub fn len(&self) -> usize {
+        self.current as usize
+    }
+    /// 容量上限，无界数组返回None
+    pub fn capacity(&self) -> Option<u32> {
+        self.max
     }
     pub fn is_empty(&self) -> bool {
-        0_u32.eq(self.get_length())
+        0_u32.eq(self.get_current())
     }
+    /// # 判断是否已满
+    /// 只有有界数组会满：写入游标到达容量上限
     pub fn is_full(&self) -> bool {
-        self.get_current().eq(self.get_length())
+        self.max.is_some_and(|max| self.get_current().eq(&max))
+    }
+    /// 从`from`开始重新编号，保证元素的index与其位置一致
+    fn reindex(&mut self, from: usize) {
+        for (i, el) in self.elements.iter_mut().enumerate().skip(from) {
+            el.index = i as u32;
+        }
     }
-    pub fn push(&mut self, data: u8) -> () {
-        let _ = self.elements.push(Element::<u8>::new(data, self.get_length().clone()));
+    /// 已写入的元素
+    fn written(&self) -> &[Element<T>] {
+        &self.elements[..self.current as usize]
+    }
+    fn written_mut(&mut self) -> &mut [Element<T>] {
+        &mut self.elements[..self.current as usize]
+    }
+    fn check_index(&self, index: u32) -> Result<(), ArrayError> {
+        if index < self.current {
+            Ok(())
+        } else {
+            Err(ArrayError::OutOfBounds { index, length: self.current })
+        }
+    }
+    /// # 添加元素
+    /// 1. 写入游标处仍有预填充的占位元素：覆盖该元素
+    /// 2. 否则追加到末尾
+    /// 3. 游标后移一位
+    pub fn try_push(&mut self, data: T) -> Result<(), ArrayError> {
+        if self.is_full() {
+            return Err(ArrayError::Full);
+        }
+        if self.current < self.length {
+            self.elements[self.current as usize].set_data(data);
+        } else {
+            self.elements.push(Element::new(data, self.length));
+            self.length += 1;
+        }
+        self.current += 1;
+        Ok(())
+    }
+    /// # 添加元素
+    /// ## Panics
+    /// 有界数组已满时panic，需要处理该情况请使用`try_push`
+    pub fn push(&mut self, data: T) {
+        if let Err(e) = self.try_push(data) {
+            panic!("{}", e)
+        }
+    }
+    /// # 在指定位置插入元素
+    /// 1. index可以等于数组长度（等同于追加），超过长度返回OutOfBounds，不会写入占位元素之间
+    /// 2. 有界数组槽位用尽时，丢弃末尾的一个占位元素腾出位置；没有占位元素则返回Full
+    /// 3. 游标后移一位
+    pub fn try_insert(&mut self, index: u32, data: T) -> Result<(), ArrayError> {
+        if index > self.current {
+            return Err(ArrayError::OutOfBounds { index, length: self.current });
+        }
+        if self.max.is_some_and(|max| self.length >= max) {
+            if self.current >= self.length {
+                return Err(ArrayError::Full);
+            }
+            let _ = self.elements.pop();
+            self.length -= 1;
+        }
+        self.elements.insert(index as usize, Element::new(data, index));
         self.length += 1;
+        self.current += 1;
+        self.reindex(index as usize + 1);
+        Ok(())
+    }
+    /// # 在指定位置插入元素
+    /// ## Panics
+    /// 下标越界或有界数组已满时panic，需要处理该情况请使用`try_insert`
+    pub fn insert(&mut self, index: u32, data: T) {
+        if let Err(e) = self.try_insert(index, data) {
+            panic!("{}", e)
+        }
     }
-    pub fn pop(&mut self) -> () {
-        let _ = self.elements.pop();
+    /// # 交换两个位置的元素
+    /// 交换后两个元素的index随位置更新，越界返回OutOfBounds
+    pub fn swap(&mut self, i: u32, j: u32) -> Result<(), ArrayError> {
+        self.check_index(i)?;
+        self.check_index(j)?;
+        self.elements.swap(i as usize, j as usize);
+        self.elements[i as usize].index = i;
+        self.elements[j as usize].index = j;
+        Ok(())
+    }
+    /// # 替换一段范围内的元素
+    /// 1. 移除`range`内的元素并在原位置插入`replace_with`，返回被移除的数据
+    /// 2. 范围超出已写入的元素返回OutOfBounds
+    /// 3. 有界数组超出容量时先丢弃末尾的占位元素，仍然不够则返回Full，数组保持不变
+    /// 4. 游标随移除和插入的数量移动
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Result<Vec<T>, ArrayError>
+    where
+        R: RangeBounds<u32>,
+        I: IntoIterator<Item = T>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.saturating_add(1),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.current,
+        };
+        if end > self.current {
+            return Err(ArrayError::OutOfBounds { index: end, length: self.current });
+        }
+        if start > end {
+            return Err(ArrayError::OutOfBounds { index: start, length: end });
+        }
+        let replace_with: Vec<T> = replace_with.into_iter().collect();
+        let inserted = replace_with.len() as u32;
+        let current = self.current - (end - start) + inserted;
+        let new_length = self.length - (end - start) + inserted;
+        let overflow = self.max.map_or(0, |max| new_length.saturating_sub(max));
+        if overflow > new_length - current {
+            return Err(ArrayError::Full);
+        }
+        let removed = self
+            .elements
+            .splice(
+                start as usize..end as usize,
+                replace_with.into_iter().map(|data| Element::new(data, 0)),
+            )
+            .map(Element::into_data)
+            .collect();
+        self.elements.truncate((new_length - overflow) as usize);
+        self.length = new_length - overflow;
+        self.current = current;
+        self.reindex(start as usize);
+        Ok(removed)
+    }
+    /// # 只保留满足条件的元素
+    /// 只检查已写入的元素，移除后剩余元素重新编号，游标随移除的数量前移
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let placeholders = self.elements.split_off(self.current as usize);
+        self.elements.retain(|el| f(el.get_data_ref()));
+        self.current = self.elements.len() as u32;
+        self.elements.extend(placeholders);
+        self.length = self.elements.len() as u32;
+        self.reindex(0);
+    }
+    /// # 取出末尾元素
+    /// 取出游标前的最后一个元素，空数组返回None；有界数组用最后一个占位元素补上空出的槽位
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let last = self.current as usize - 1;
+        let el = self.elements.swap_remove(last);
+        if let Some(moved) = self.elements.get_mut(last) {
+            moved.index = last as u32;
+        }
         self.length -= 1;
+        self.current -= 1;
+        Some(el.into_data())
     }
-    pub fn remove(&mut self, index: u32) -> () {
-        let _ = self.elements.remove(index as usize);
+    /// # 移除指定位置的元素
+    /// 1. 空数组返回Empty，越界返回OutOfBounds
+    /// 2. 移除的元素位于游标之前时游标前移一位
+    pub fn try_remove(&mut self, index: u32) -> Result<T, ArrayError> {
+        if self.is_empty() {
+            return Err(ArrayError::Empty);
+        }
+        self.check_index(index)?;
+        let el = self.elements.remove(index as usize);
         self.length -= 1;
+        self.reindex(index as usize);
+        if index < self.current {
+            self.current -= 1;
+        }
+        Ok(el.into_data())
+    }
+    /// # 移除指定位置的元素
+    /// ## Panics
+    /// 空数组或下标越界时panic，需要处理该情况请使用`try_remove`
+    pub fn remove(&mut self, index: u32) -> T {
+        match self.try_remove(index) {
+            Ok(data) => data,
+            Err(e) => panic!("{}", e),
+        }
+    }
+    /// 获取指定位置的元素，越界返回None
+    pub fn get(&self, index: u32) -> Option<&Element<T>> {
+        self.written().get(index as usize)
+    }
+    /// 设置指定位置元素的数据域，越界返回OutOfBounds
+    pub fn set(&mut self, index: u32, value: T) -> Result<(), ArrayError> {
+        self.check_index(index)?;
+        self.elements[index as usize].set_data(value);
+        Ok(())
+    }
+    /// 不可变引用迭代器（数据域）
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.written().iter())
+    }
+    /// 可变引用迭代器（数据域）
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(self.written_mut().iter_mut())
+    }
+    /// 带下标的不可变引用迭代器，返回`(index, &T)`
+    pub fn enumerate(&self) -> Enumerate<'_, T> {
+        Enumerate(self.written().iter())
+    }
+}
+
+impl<T> Default for Array<T> {
+    fn default() -> Self {
+        Array::new()
+    }
+}
+
+/// 通过下标直接访问数据域
+impl<T> Index<usize> for Array<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.written()[index].get_data_ref()
+    }
+}
+
+impl<T> IndexMut<usize> for Array<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.written_mut()[index].get_data_mut()
     }
-    pub fn get(&self, index: u32) -> &Element<u8> {
-        &self.elements.get(index as usize).unwrap()
+}
+
+impl<T> FromIterator<T> for Array<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Array::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T> Extend<T> for Array<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push(data);
+        }
+    }
+}
+
+/// 转为迭代器
+pub struct IntoIter<T>(std::vec::IntoIter<Element<T>>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Element::into_data)
     }
-    pub fn set(&mut self, index: u32, value: u8) -> () {
-        let _ = self.elements.get_mut(index as usize).unwrap().set_data(value);
+}
+
+/// 转为不可变引用迭代器
+pub struct Iter<'a, T: 'a>(std::slice::Iter<'a, Element<T>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Element::get_data_ref)
+    }
+}
+
+/// 转为带下标的不可变引用迭代器
+pub struct Enumerate<'a, T: 'a>(std::slice::Iter<'a, Element<T>>);
+
+impl<'a, T> Iterator for Enumerate<'a, T> {
+    type Item = (u32, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|el| (el.index, el.get_data_ref()))
+    }
+}
+
+/// 转为可变引用迭代器
+pub struct IterMut<'a, T: 'a>(std::slice::IterMut<'a, Element<T>>);
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Element::get_data_mut)
+    }
+}
+
+impl<T> IntoIterator for Array<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.elements.truncate(self.current as usize);
+        IntoIter(self.elements.into_iter())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Array<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Array<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
@@ -146,42 +495,193 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_element() -> () {
+    fn test_element() {
         let mut e = Element::<String>::new("你好".to_string(), 0);
         println!("{:?}", e);
         println!("{}", e.get_data());
-        let _ = e.set_data("hello".to_string());
+        e.set_data("hello".to_string());
         println!("{}", e.get_data_ref());
     }
 
 
     #[test]
-    fn test_array_new() -> () {
+    fn test_array_new() {
         let mut arr = Array::<u8>::new();
         println!("{:?}", &arr);
         println!("{}", arr.is_empty());
         println!("{}", arr.is_full());
-        let _ = arr.push(56);
-        let _ = arr.push(99);
-        let _ = arr.push(110);
+        arr.push(56);
+        arr.push(99);
+        arr.push(110);
         println!("{:?}", &arr);
         println!("{}", arr.is_empty());
         println!("{}", arr.is_full());
     }
 
     #[test]
-    fn test_array_bind() -> () {
+    fn test_array_bind() {
         let mut arr = Array::<u8>::new_bind(5);
-        arr.pop();
-        arr.remove(1);
+        assert_eq!(None, arr.pop());
+        assert_eq!(Err(ArrayError::Empty), arr.try_remove(1));
         println!("{:?}", &arr);
         println!("{}", arr.is_empty());
         println!("{}", arr.is_full());
-        let _ = arr.push(56);
-        arr.set(2,110);
+        arr.push(56);
+        arr.push(0);
+        arr.set(1, 110).unwrap();
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 2, length: 2 }), arr.set(2, 110));
         println!("{:?}", &arr);
         println!("{}", arr.is_empty());
         println!("{}", arr.is_full());
         println!("{:?}", arr.get(1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_array_generic() {
+        let mut stamps = Array::<i64>::new_bind(2);
+        stamps.push(1_688_428_800);
+        stamps.push(0);
+        stamps[1] = -1;
+        assert_eq!(vec![1_688_428_800, -1], stamps.iter().copied().collect::<Vec<_>>());
+
+        let mut readings: Array<f64> = vec![0.5, 1.5].into_iter().collect();
+        readings.extend([2.5]);
+        for r in &mut readings {
+            *r *= 2.0;
+        }
+        assert_eq!(3.0, readings[1]);
+        assert_eq!(5.0, *readings.get(2).unwrap().get_data_ref());
+
+        let names: Array<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(&1, names.get(1).unwrap().get_index());
+        assert_eq!(vec!["a", "b"], names.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_array_bounded() {
+        let mut arr = Array::<u8>::new_bind(3);
+        assert_eq!(Some(3), arr.capacity());
+        assert!(!arr.is_full());
+        arr.try_push(1).unwrap();
+        arr.try_push(2).unwrap();
+        assert_eq!(&2, arr.get_current());
+        assert_eq!(2, arr.len());
+        arr.try_insert(0, 9).unwrap();
+        assert_eq!(vec![9, 1, 2], arr.iter().copied().collect::<Vec<_>>());
+        assert!(arr.is_full());
+        assert_eq!(Err(ArrayError::Full), arr.try_push(3));
+        assert_eq!(Err(ArrayError::Full), arr.try_insert(1, 3));
+        assert_eq!(Ok(1), arr.try_remove(1));
+        assert_eq!(&2, arr.get_current());
+        arr.try_push(4).unwrap();
+        assert_eq!(vec![9, 2, 4], arr.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_array_errors() {
+        let mut arr = Array::<u8>::new();
+        assert!(!arr.is_full());
+        assert_eq!(None, arr.pop());
+        assert_eq!(Err(ArrayError::Empty), arr.try_remove(0));
+        assert!(arr.get(0).is_none());
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 0, length: 0 }), arr.set(0, 1));
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 1, length: 0 }), arr.try_insert(1, 1));
+        arr.push(7);
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 3, length: 1 }), arr.try_remove(3));
+        assert_eq!(Some(7), arr.pop());
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn test_array_bounded_placeholders() {
+        // 占位元素不计入长度，也不会被迭代、取出或删除
+        let mut arr = Array::<u8>::new_bind(3);
+        assert_eq!(0, arr.len());
+        assert!(arr.is_empty());
+        assert_eq!(None, arr.pop());
+        assert_eq!(0, arr.iter().count());
+        assert!(arr.get(0).is_none());
+        arr.push(7);
+        assert_eq!(vec![7], arr.clone().into_iter().collect::<Vec<_>>());
+        assert_eq!(vec![(0, &7)], arr.enumerate().collect::<Vec<_>>());
+        arr.retain(|&x| x != 0);
+        assert_eq!(1, arr.len());
+        assert_eq!(Some(7), arr.pop());
+        assert!(arr.is_empty());
+        arr.push(1);
+        arr.push(2);
+        arr.push(3);
+        assert!(arr.is_full());
+        assert_eq!(Some(3), arr.pop());
+        arr.push(4);
+        assert_eq!(vec![1, 2, 4], arr.iter().copied().collect::<Vec<_>>());
+        assert_eq!(Err(ArrayError::Full), arr.try_push(5));
+    }
+
+    #[test]
+    fn test_array_bounded_insert_then_push() {
+        // 在游标之后插入会被占位元素覆盖，因此拒绝
+        let mut arr = Array::<u8>::new_bind(3);
+        arr.push(1);
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 2, length: 1 }), arr.try_insert(2, 9));
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 2, length: 1 }), arr.splice(2..2, [9]));
+        arr.try_insert(1, 9).unwrap();
+        arr.push(5);
+        assert_eq!(Err(ArrayError::Full), arr.try_push(6));
+        assert_eq!(vec![1, 9, 5], arr.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "array is full")]
+    fn test_array_push_full() {
+        let mut arr = Array::<u8>::new_bind(1);
+        arr.push(1);
+        arr.push(2);
+    }
+
+    #[test]
+    fn test_array_index_ops() {
+        fn check<T>(arr: &Array<T>) {
+            for (i, el) in arr.elements.iter().enumerate() {
+                assert_eq!(i as u32, *el.get_index());
+            }
+        }
+        let mut arr: Array<u32> = (0..6).collect();
+        arr.remove(1);
+        check(&arr);
+        arr.insert(0, 10);
+        check(&arr);
+        assert_eq!(vec![10, 0, 2, 3, 4, 5], arr.iter().copied().collect::<Vec<_>>());
+        arr.swap(0, 5).unwrap();
+        check(&arr);
+        assert_eq!(Err(ArrayError::OutOfBounds { index: 6, length: 6 }), arr.swap(0, 6));
+        let removed = arr.splice(1..3, [7, 8, 9]).unwrap();
+        check(&arr);
+        assert_eq!(vec![0, 2], removed);
+        assert_eq!(vec![5, 7, 8, 9, 3, 4, 10], arr.iter().copied().collect::<Vec<_>>());
+        arr.retain(|x| x % 2 == 0);
+        check(&arr);
+        assert_eq!(
+            vec![(0, &8), (1, &4), (2, &10)],
+            arr.enumerate().collect::<Vec<_>>()
+        );
+        assert_eq!(&3, arr.get_current());
+    }
+
+    #[test]
+    fn test_array_bounded_splice() {
+        let mut arr = Array::<u8>::new_bind(4);
+        arr.push(1);
+        arr.push(2);
+        assert_eq!(Ok(vec![1]), arr.splice(..1, [5, 6]));
+        assert_eq!(vec![5, 6, 2], arr.iter().copied().collect::<Vec<_>>());
+        assert_eq!(&3, arr.get_current());
+        assert_eq!(Err(ArrayError::Full), arr.splice(0..0, [7, 8]));
+        assert_eq!(Ok(vec![]), arr.splice(3..3, [7]));
+        assert_eq!(vec![5, 6, 2, 7], arr.iter().copied().collect::<Vec<_>>());
+        assert!(arr.is_full());
+        arr.retain(|&x| x != 6);
+        assert_eq!(&3, arr.get_current());
+        assert_eq!(Some(&2), arr.get(2).map(Element::get_index));
+    }
+}
@@ -0,0 +1,274 @@
+//! # 并发栈（Treiber Stack）
+//! 无锁栈：栈顶是一个原子指针，push和pop都通过CAS修改栈顶
+//! ## 结构
+//! ```code
+//!   head(AtomicPtr)
+//!       ↓
+//!   |——————————————|    |——————————————|    |——————————————|
+//!   | data | next ——————>| data | next ——————>| data | null  |
+//!   |——————————————|    |——————————————|    |——————————————|
+//! ```
+//! ## 内存回收
+//! 节点被CAS摘下后，其他正在pop的线程可能仍持有它的指针并读取next，不能立即释放。
+//! 这里使用"pop计数"方案：
+//! 1. 每个pop开始时threads_in_pop加1，结束时减1
+//! 2. 摘下节点的线程如果是唯一的pop线程，就可以释放该节点以及待删除链表中的节点
+//! 3. 否则把节点挂到待删除链表，由之后某个独占的pop线程统一释放
+//!
+//! 节点只会在没有其他pop线程时释放，因此也不会出现ABA问题
+//! ## 常用方法
+//! 1. push:入栈，不会阻塞
+//! 2. try_pop:出栈，栈为空时立即返回None
+//! 3. pop:出栈，栈为空时等待直到有元素
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/20
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use crate::sync::{AtomicPtr, AtomicUsize, Backoff, Ordering};
+use std::fmt::{Debug, Formatter};
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+struct Node<T> {
+    /// 数据被pop读走后节点才会进入待删除链表，释放节点时不再drop数据
+    data: ManuallyDrop<T>,
+    /// 栈中指向下一个节点；进入待删除链表后复用为链表指针
+    next: AtomicPtr<Node<T>>,
+}
+
+/// # 并发栈
+/// 可以通过`Arc`在多个线程间共享
+pub struct ConcurrentStack<T> {
+    head: AtomicPtr<Node<T>>,
+    threads_in_pop: AtomicUsize,
+    to_be_deleted: AtomicPtr<Node<T>>,
+}
+
+unsafe impl<T: Send> Send for ConcurrentStack<T> {}
+unsafe impl<T: Send> Sync for ConcurrentStack<T> {}
+
+impl<T> ConcurrentStack<T> {
+    pub fn new() -> Self {
+        ConcurrentStack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            threads_in_pop: AtomicUsize::new(0),
+            to_be_deleted: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+    /// 是否为空，并发修改时只是一个瞬时的结果
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::SeqCst).is_null()
+    }
+    /// 入栈
+    pub fn push(&self, data: T) {
+        let node = Box::into_raw(Box::new(Node {
+            data: ManuallyDrop::new(data),
+            next: AtomicPtr::new(ptr::null_mut()),
+        }));
+        let mut head = self.head.load(Ordering::SeqCst);
+        loop {
+            // node还没有发布，其他线程看不到
+            unsafe { (*node).next.store(head, Ordering::Relaxed) };
+            match self.head.compare_exchange_weak(head, node, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+    /// # 出栈（非阻塞）
+    /// 栈为空时返回None
+    pub fn try_pop(&self) -> Option<T> {
+        self.threads_in_pop.fetch_add(1, Ordering::SeqCst);
+        let mut head = self.head.load(Ordering::SeqCst);
+        loop {
+            if head.is_null() {
+                self.threads_in_pop.fetch_sub(1, Ordering::SeqCst);
+                return None;
+            }
+            // threads_in_pop包含当前线程，head不会被释放
+            let next = unsafe { (*head).next.load(Ordering::SeqCst) };
+            match self.head.compare_exchange_weak(head, next, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+        // 只有摘下节点的线程会读取数据
+        let data = unsafe { ptr::read(&*(*head).data) };
+        self.try_reclaim(head);
+        Some(data)
+    }
+    /// # 出栈（阻塞）
+    /// 栈为空时等待其他线程push
+    pub fn pop(&self) -> T {
+        let mut backoff = Backoff::new();
+        loop {
+            if let Some(data) = self.try_pop() {
+                return data;
+            }
+            backoff.snooze();
+        }
+    }
+    /// 回收已摘下的节点，并把当前线程移出threads_in_pop
+    fn try_reclaim(&self, node: *mut Node<T>) {
+        if self.threads_in_pop.load(Ordering::SeqCst) == 1 {
+            // 当前线程是唯一的pop线程：先接管待删除链表
+            let pending = self.to_be_deleted.swap(ptr::null_mut(), Ordering::SeqCst);
+            if self.threads_in_pop.fetch_sub(1, Ordering::SeqCst) == 1 {
+                // 接管之后仍然没有新的pop线程，链表中的节点都不会再被访问
+                unsafe { Self::delete_nodes(pending) };
+            } else if !pending.is_null() {
+                // 新的pop线程可能拿到了链表中的节点，放回去
+                self.chain_pending(pending);
+            }
+            // node在threads_in_pop为1时已经摘下，新来的pop线程读不到它
+            unsafe { drop(Box::from_raw(node)) };
+        } else {
+            unsafe { (*node).next.store(ptr::null_mut(), Ordering::SeqCst) };
+            self.chain_pending(node);
+            self.threads_in_pop.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+    /// 把以first开头的一串节点挂到待删除链表
+    fn chain_pending(&self, first: *mut Node<T>) {
+        let mut last = first;
+        unsafe {
+            loop {
+                let next = (*last).next.load(Ordering::SeqCst);
+                if next.is_null() {
+                    break;
+                }
+                last = next;
+            }
+        }
+        let mut pending = self.to_be_deleted.load(Ordering::SeqCst);
+        loop {
+            unsafe { (*last).next.store(pending, Ordering::SeqCst) };
+            match self.to_be_deleted.compare_exchange_weak(pending, first, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return,
+                Err(current) => pending = current,
+            }
+        }
+    }
+    /// 释放一串已经被读走数据的节点
+    unsafe fn delete_nodes(mut node: *mut Node<T>) {
+        while !node.is_null() {
+            let next = (*node).next.load(Ordering::SeqCst);
+            drop(Box::from_raw(node));
+            node = next;
+        }
+    }
+}
+
+impl<T> Drop for ConcurrentStack<T> {
+    fn drop(&mut self) {
+        while self.try_pop().is_some() {}
+        unsafe { Self::delete_nodes(self.to_be_deleted.load(Ordering::SeqCst)) };
+    }
+}
+
+impl<T> Default for ConcurrentStack<T> {
+    fn default() -> Self {
+        ConcurrentStack::new()
+    }
+}
+
+impl<T> Debug for ConcurrentStack<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConcurrentStack")
+            .field("is_empty", &self.is_empty())
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_push_pop() {
+        let stack = ConcurrentStack::new();
+        assert!(stack.is_empty());
+        assert_eq!(None, stack.try_pop());
+        stack.push(1);
+        stack.push(2);
+        assert_eq!(Some(2), stack.try_pop());
+        assert_eq!(1, stack.pop());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        let item = Arc::new(());
+        let stack = ConcurrentStack::new();
+        for _ in 0..10 {
+            stack.push(Arc::clone(&item));
+        }
+        stack.try_pop();
+        drop(stack);
+        assert_eq!(1, Arc::strong_count(&item));
+    }
+
+    #[test]
+    fn test_blocking_pop() {
+        let stack = Arc::new(ConcurrentStack::new());
+        let consumer = {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || (0..100).map(|_| stack.pop()).sum::<u32>())
+        };
+        for i in 0..100 {
+            stack.push(i);
+        }
+        assert_eq!(4950, consumer.join().unwrap());
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn loom_concurrent_push_pop() {
+        loom::model(|| {
+            let stack = Arc::new(ConcurrentStack::new());
+            let handles: Vec<_> = (0..2)
+                .map(|i| {
+                    let stack = Arc::clone(&stack);
+                    thread::spawn(move || {
+                        stack.push(i);
+                        stack.try_pop()
+                    })
+                })
+                .collect();
+            let mut popped: Vec<_> = handles.into_iter().filter_map(|h| h.join().unwrap()).collect();
+            popped.extend(std::iter::from_fn(|| stack.try_pop()));
+            popped.sort();
+            assert_eq!(vec![0, 1], popped);
+        });
+    }
+
+    #[test]
+    fn loom_concurrent_pops_reclaim() {
+        loom::model(|| {
+            let stack = Arc::new(ConcurrentStack::new());
+            stack.push(1);
+            stack.push(2);
+            let other = {
+                let stack = Arc::clone(&stack);
+                thread::spawn(move || stack.try_pop())
+            };
+            let mine = stack.try_pop();
+            let theirs = other.join().unwrap();
+            let mut popped = vec![mine.unwrap(), theirs.unwrap()];
+            popped.sort();
+            assert_eq!(vec![1, 2], popped);
+            assert!(stack.is_empty());
+        });
+    }
+}
@@ -0,0 +1,569 @@
+//! # 双端队列Deque
+//! 队首和队尾都可以入队、出队
+//! ## 结构
+//! ```code
+//!                   head
+//!                    ↓
+//!       | *5 | ?? | *1 | *2 | *3 | *4 |
+//!              ↑
+//!            tail
+//!  push_front/pop_front ←— *1 ... *5 —→ push_back/pop_back
+//! ```
+//! 底层是可增长的环形缓冲区：槽位用完时容量翻倍并把元素按顺序搬到新缓冲区，
+//! 两端的入队和出队都只移动head和len，均摊O(1)
+//! ## 方法
+//! 1. new:初始化可无限增长的双端队列
+//! 2. new_bind:初始化限定容量的双端队列（与`Queue::new(len)`一致）
+//! 3. push_front / push_back / try_push_front / try_push_back:入队
+//! 4. pop_front / pop_back:出队
+//! 5. get / get_mut:随机访问
+//! 6. rotate_left / rotate_right:旋转
+//! 7. make_contiguous:整理为连续内存并返回切片
+//! 8. iter / iter_mut:双向遍历
+//! ## 场景
+//! 1. 滑动窗口：窗口右移时从队尾入队，过期元素从队首出队
+//! 2. 工作窃取调度：线程从自己队列的一端取任务，其他线程从另一端窃取
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/8/10
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::iter::Chain;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut};
+use std::{ptr, slice};
+
+pub struct Deque<T> {
+    buf: Vec<MaybeUninit<T>>,
+    head: usize,
+    len: usize,
+    max: Option<usize>,
+}
+
+impl<T> Deque<T> {
+    /// # 创建一个空的双端队列
+    /// 不限定容量，第一次入队时才分配
+    pub fn new() -> Self {
+        Deque {
+            buf: Vec::new(),
+            head: 0,
+            len: 0,
+            max: None,
+        }
+    }
+    /// # 创建一个限定容量的空双端队列
+    /// 队满时`try_push_*`返回传入的元素
+    pub fn new_bind(max: usize) -> Self {
+        Deque {
+            buf: Vec::new(),
+            head: 0,
+            len: 0,
+            max: Some(max),
+        }
+    }
+    /// # 队列长度
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// # 是否空队
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// # 是否队满
+    /// 只有限定容量的双端队列会满
+    pub fn is_full(&self) -> bool {
+        self.max.is_some_and(|max| self.len >= max)
+    }
+    /// 当前缓冲区的槽位数量
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+    /// 限定的容量，不限定时返回None
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+    /// 第offset个元素在环形缓冲区中的下标
+    fn slot(&self, offset: usize) -> usize {
+        let i = self.head + offset;
+        if i >= self.capacity() {
+            i - self.capacity()
+        } else {
+            i
+        }
+    }
+    /// # 扩容
+    /// 1. 新容量为原来的两倍（至少为4），不超过限定容量
+    /// 2. 元素按顺序搬到新缓冲区开头，head归零
+    fn grow(&mut self) {
+        let mut new_cap = (self.capacity() * 2).max(4);
+        if let Some(max) = self.max {
+            new_cap = new_cap.min(max);
+        }
+        let mut buf: Vec<MaybeUninit<T>> = (0..new_cap).map(|_| MaybeUninit::uninit()).collect();
+        for (offset, slot) in buf.iter_mut().enumerate().take(self.len) {
+            let from = self.slot(offset);
+            unsafe {
+                slot.write(self.buf[from].assume_init_read());
+            }
+        }
+        // 旧缓冲区中的元素已经搬走，MaybeUninit不会重复释放
+        self.buf = buf;
+        self.head = 0;
+    }
+    fn reserve_one(&mut self) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+    }
+    /// # 队首入队
+    /// 1. 槽位用完时扩容
+    /// 2. head前移一位（环形）后写入
+    pub fn try_push_front(&mut self, el: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(el);
+        }
+        self.reserve_one();
+        self.head = if self.head == 0 { self.capacity() - 1 } else { self.head - 1 };
+        self.buf[self.head].write(el);
+        self.len += 1;
+        Ok(())
+    }
+    /// # 队尾入队
+    /// 1. 槽位用完时扩容
+    /// 2. 写入`(head + len) % capacity`
+    pub fn try_push_back(&mut self, el: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(el);
+        }
+        self.reserve_one();
+        let tail = self.slot(self.len);
+        self.buf[tail].write(el);
+        self.len += 1;
+        Ok(())
+    }
+    /// # 队首入队
+    /// ## Panics
+    /// 限定容量的双端队列已满时panic，需要处理该情况请使用`try_push_front`
+    pub fn push_front(&mut self, el: T) {
+        if self.try_push_front(el).is_err() {
+            panic!("deque is full")
+        }
+    }
+    /// # 队尾入队
+    /// ## Panics
+    /// 限定容量的双端队列已满时panic，需要处理该情况请使用`try_push_back`
+    pub fn push_back(&mut self, el: T) {
+        if self.try_push_back(el).is_err() {
+            panic!("deque is full")
+        }
+    }
+    /// # 队首出队
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let el = unsafe { self.buf[self.head].assume_init_read() };
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(el)
+    }
+    /// # 队尾出队
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let tail = self.slot(self.len);
+        unsafe { Some(self.buf[tail].assume_init_read()) }
+    }
+    /// # 查看队首元素
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+    /// # 查看队尾元素
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+    /// # 随机访问
+    /// index从队首开始计数，越界返回None
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            unsafe { Some(self.buf[self.slot(index)].assume_init_ref()) }
+        } else {
+            None
+        }
+    }
+    /// # 随机访问（可变）
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            let slot = self.slot(index);
+            unsafe { Some(self.buf[slot].assume_init_mut()) }
+        } else {
+            None
+        }
+    }
+    /// # 以两段切片的形式返回所有元素
+    /// 第一段从head到缓冲区末尾，第二段是绕回缓冲区开头的部分
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.ranges();
+        unsafe {
+            (
+                &*(&self.buf[front] as *const [MaybeUninit<T>] as *const [T]),
+                &*(&self.buf[back] as *const [MaybeUninit<T>] as *const [T]),
+            )
+        }
+    }
+    /// # 以两段可变切片的形式返回所有元素
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.ranges();
+        let (wrapped, rest) = self.buf.split_at_mut(front.start);
+        unsafe {
+            (
+                &mut *(&mut rest[..front.len()] as *mut [MaybeUninit<T>] as *mut [T]),
+                &mut *(&mut wrapped[back] as *mut [MaybeUninit<T>] as *mut [T]),
+            )
+        }
+    }
+    fn ranges(&self) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        if self.head + self.len <= self.capacity() {
+            (self.head..self.head + self.len, 0..0)
+        } else {
+            let wrapped = self.head + self.len - self.capacity();
+            (self.head..self.capacity(), 0..wrapped)
+        }
+    }
+    /// # 整理为连续内存
+    /// 元素绕回缓冲区开头时旋转整个缓冲区，使head归零，返回全部元素的切片
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head + self.len > self.capacity() {
+            self.buf.rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+    /// # 向左旋转
+    /// 前n个元素依次移到队尾
+    /// ## Panics
+    /// n大于长度时panic
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "rotate by {} out of bounds for length {}", n, self.len);
+        if self.len == self.capacity() {
+            // 缓冲区已满时只需要移动head
+            if self.len != 0 {
+                self.head = self.slot(n);
+            }
+        } else {
+            self.make_contiguous().rotate_left(n);
+        }
+    }
+    /// # 向右旋转
+    /// 后n个元素依次移到队首
+    /// ## Panics
+    /// n大于长度时panic
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "rotate by {} out of bounds for length {}", n, self.len);
+        self.rotate_left(self.len - n);
+    }
+    /// # 清空
+    /// 保留缓冲区
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back): (*mut [T], *mut [T]) = (front, back);
+        // 先置零长度，元素的drop发生panic时也不会重复释放
+        self.len = 0;
+        self.head = 0;
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+    /// # 从队首到队尾遍历
+    /// 支持`rev()`从队尾到队首遍历
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter(front.iter().chain(back.iter()))
+    }
+    /// # 从队首到队尾遍历（可变）
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut(front.iter_mut().chain(back.iter_mut()))
+    }
+}
+
+impl<T> Drop for Deque<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::new()
+    }
+}
+
+impl<T: Debug> Debug for Deque<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for Deque<T> {
+    fn clone(&self) -> Self {
+        let mut deque = match self.max {
+            Some(max) => Deque::new_bind(max),
+            None => Deque::new(),
+        };
+        deque.extend(self.iter().cloned());
+        deque
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for Deque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+/// 依次从队尾入队，限定容量的双端队列已满时panic
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for el in iter {
+            self.push_back(el);
+        }
+    }
+}
+
+/// 转为不可变引用迭代器
+pub struct Iter<'a, T: 'a>(Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+/// 转为可变引用迭代器
+pub struct IterMut<'a, T: 'a>(Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>);
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+/// 转为迭代器
+pub struct IntoIter<T>(Deque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_deque_both_ends() {
+        let mut deque = Deque::new();
+        assert!(deque.is_empty());
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        deque.push_front(0);
+        deque.push_back(4);
+        assert_eq!(5, deque.len());
+        assert_eq!(vec![0, 1, 2, 3, 4], deque.iter().copied().collect::<Vec<_>>());
+        assert_eq!(vec![4, 3, 2, 1, 0], deque.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(Some(&0), deque.front());
+        assert_eq!(Some(&4), deque.back());
+        assert_eq!(Some(4), deque.pop_back());
+        assert_eq!(Some(0), deque.pop_front());
+        assert_eq!(Some(&2), deque.get(1));
+        assert_eq!(None, deque.get(3));
+        deque[0] = 10;
+        for el in deque.iter_mut().rev() {
+            *el += 1;
+        }
+        assert_eq!(vec![11, 3, 4], deque.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_deque_sliding_window_max() {
+        // 单调队列求滑动窗口最大值，队中保存下标
+        let data = [1, 3, -1, -3, 5, 3, 6, 7];
+        let mut window: Deque<usize> = Deque::new();
+        let mut result = vec![];
+        for (i, &x) in data.iter().enumerate() {
+            while window.back().is_some_and(|&j| data[j] <= x) {
+                window.pop_back();
+            }
+            window.push_back(i);
+            if window.front().is_some_and(|&j| j + 3 <= i) {
+                window.pop_front();
+            }
+            if i >= 2 {
+                result.push(data[window[0]]);
+            }
+        }
+        assert_eq!(vec![3, 3, 5, 5, 6, 7], result);
+    }
+
+    #[test]
+    fn test_deque_rotate_and_contiguous() {
+        let mut deque: Deque<u32> = Deque::new();
+        for i in 3..7 {
+            deque.push_back(i);
+        }
+        for i in (0..3).rev() {
+            deque.push_front(i);
+        }
+        // 队首的元素绕回到了缓冲区末尾
+        assert!(!deque.as_slices().1.is_empty());
+        deque.rotate_left(2);
+        assert_eq!(vec![2, 3, 4, 5, 6, 0, 1], deque.iter().copied().collect::<Vec<_>>());
+        deque.rotate_right(3);
+        assert_eq!(vec![6, 0, 1, 2, 3, 4, 5], deque.iter().copied().collect::<Vec<_>>());
+        deque.push_back(7);
+        deque.rotate_left(8);
+        deque.rotate_right(1);
+        assert_eq!(&mut [7, 6, 0, 1, 2, 3, 4, 5], deque.make_contiguous());
+        assert!(deque.as_slices().1.is_empty());
+        deque.make_contiguous().sort();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7], deque.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_deque_bounded() {
+        let mut deque = Deque::new_bind(3);
+        assert_eq!(Ok(()), deque.try_push_back('b'));
+        assert_eq!(Ok(()), deque.try_push_front('a'));
+        assert_eq!(Ok(()), deque.try_push_back('c'));
+        assert!(deque.is_full());
+        assert_eq!(3, deque.capacity());
+        assert_eq!(Err('z'), deque.try_push_front('z'));
+        assert_eq!(Err('z'), deque.try_push_back('z'));
+        assert_eq!(Some('a'), deque.pop_front());
+        assert_eq!(Ok(()), deque.try_push_back('d'));
+        assert_eq!("['b', 'c', 'd']", format!("{:?}", deque));
+        assert_eq!(deque.clone(), deque);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque is full")]
+    fn test_deque_push_full() {
+        let mut deque = Deque::new_bind(0);
+        deque.push_front(1);
+    }
+
+    #[test]
+    fn test_deque_drop() {
+        let counter = Rc::new(());
+        let mut deque = Deque::new();
+        for i in 0..10 {
+            if i % 2 == 0 {
+                deque.push_back(Rc::clone(&counter));
+            } else {
+                deque.push_front(Rc::clone(&counter));
+            }
+        }
+        deque.make_contiguous();
+        let _ = deque.pop_front();
+        assert_eq!(10, Rc::strong_count(&counter));
+        let mut iter = deque.into_iter();
+        let _ = iter.next_back();
+        drop(iter);
+        assert_eq!(1, Rc::strong_count(&counter));
+    }
+}
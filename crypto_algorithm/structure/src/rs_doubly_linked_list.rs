@@ -0,0 +1,710 @@
+//! # 双向链表
+//! 每个节点同时指向前一个和后一个节点，可以从两端遍历
+//! ## 结构
+//! ```code
+//!  head                              tail
+//!   ↓                                 ↓
+//!   *1  ⇄  *2  ⇄  *3  ⇄  *4  ⇄  *5
+//!
+//!  each el structure
+//!   |—————————————————————————————————————|
+//!   |  prev pointer |  data  | next pointer |
+//!   |—————————————————————————————————————|
+//! ```
+//! ## 常用方法
+//! 1. push_front / push_back:两端入链
+//! 2. pop_front / pop_back:两端出链
+//! 3. front / back:查看两端元素
+//! 4. iter / iter_mut / into_iter:双向遍历
+//! 5. cursor_front_mut / cursor_back_mut:获取游标
+//! ## 游标（CursorMut）
+//! 游标停留在某个节点上，可以前后移动，并在O(1)时间内于游标前后插入或移除游标处的节点。
+//! 链表尾部和头部之间有一个不存在的"幽灵"位置，游标越过两端时停在这里，此时`index`返回None
+//! ```code
+//!    ghost → *1 ⇄ *2 ⇄ *3 → ghost
+//!                  ↑
+//!                cursor
+//! ```
+//! ## 使用场景
+//! 1. LRU缓存：命中时把节点从中间摘下挂到头部，淘汰时从尾部移除
+//! 2. 文本编辑器：光标处的插入和删除
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/8/10
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+type Link<T> = Option<NonNull<DNode<T>>>;
+
+/// # 单个节点
+/// 1. 数据域
+/// 2. 前驱指针
+/// 3. 后继指针
+struct DNode<T> {
+    data: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+impl<T> DNode<T> {
+    /// 在堆上创建节点并交出所有权，由链表负责释放
+    fn alloc(data: T, prev: Link<T>, next: Link<T>) -> NonNull<DNode<T>> {
+        let node = Box::new(DNode { data, prev, next });
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+}
+
+pub struct DoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _marker: PhantomData<Box<DNode<T>>>,
+}
+
+unsafe impl<T: Send> Send for DoublyLinkedList<T> {}
+
+unsafe impl<T: Sync> Sync for DoublyLinkedList<T> {}
+
+impl<T> DoublyLinkedList<T> {
+    /// 初始化空链表
+    pub fn new() -> Self {
+        DoublyLinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+    /// 获取链表长度
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 判断是否为空链表
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// # 在prev和next之间链入新节点
+    /// prev为None表示新节点成为头节点，next为None表示成为尾节点
+    fn link_between(&mut self, data: T, prev: Link<T>, next: Link<T>) -> NonNull<DNode<T>> {
+        let node = DNode::alloc(data, prev, next);
+        unsafe {
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(node),
+                None => self.head = Some(node),
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(node),
+                None => self.tail = Some(node),
+            }
+        }
+        self.len += 1;
+        node
+    }
+    /// # 摘下节点并取回数据
+    /// 前驱和后继直接相连
+    unsafe fn unlink(&mut self, node: NonNull<DNode<T>>) -> T {
+        let node = Box::from_raw(node.as_ptr());
+        match node.prev {
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+        node.data
+    }
+    /// 头部入链
+    pub fn push_front(&mut self, el: T) {
+        self.link_between(el, None, self.head);
+    }
+    /// 尾部入链
+    pub fn push_back(&mut self, el: T) {
+        self.link_between(el, self.tail, None);
+    }
+    /// 头部出链
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|node| unsafe { self.unlink(node) })
+    }
+    /// 尾部出链
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|node| unsafe { self.unlink(node) })
+    }
+    /// 获取头节点数据域的引用
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+    /// 获取尾节点数据域的引用
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+    /// 获取头节点数据域的可变引用
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+    /// 获取尾节点数据域的可变引用
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+    /// 清空
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+    /// 是否包含某个元素
+    pub fn contains(&self, el: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|data| data == el)
+    }
+    /// 从头到尾遍历，支持`rev()`
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+    /// 从头到尾遍历（可变），支持`rev()`
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+    /// 获取停在头节点上的游标，空链表时停在幽灵位置
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: self.head,
+            index: self.head.map(|_| 0),
+            list: self,
+        }
+    }
+    /// 获取停在尾节点上的游标，空链表时停在幽灵位置
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: self.tail,
+            index: self.len.checked_sub(1),
+            list: self,
+        }
+    }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        DoublyLinkedList::new()
+    }
+}
+
+impl<T: Debug> Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for DoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DoublyLinkedList<T> {}
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// 依次添加到尾部
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for el in iter {
+            self.push_back(el);
+        }
+    }
+}
+
+/// # 游标
+/// - cur:游标所在节点，None表示幽灵位置
+/// - index:游标所在位置的下标，幽灵位置为None
+pub struct CursorMut<'a, T: 'a> {
+    list: &'a mut DoublyLinkedList<T>,
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// 游标所在位置的下标
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+    /// 游标处的元素
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.cur.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+    /// 游标之后的元素，幽灵位置之后为头节点
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.cur {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        };
+        next.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+    /// 游标之前的元素，幽灵位置之前为尾节点
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.cur {
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        };
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+    /// # 后移
+    /// 越过尾节点后停在幽灵位置，再后移回到头节点
+    pub fn move_next(&mut self) {
+        match self.cur {
+            Some(node) => {
+                self.cur = unsafe { (*node.as_ptr()).next };
+                self.index = match self.cur {
+                    Some(_) => self.index.map(|i| i + 1),
+                    None => None,
+                };
+            }
+            None => {
+                self.cur = self.list.head;
+                self.index = self.cur.map(|_| 0);
+            }
+        }
+    }
+    /// # 前移
+    /// 越过头节点后停在幽灵位置，再前移回到尾节点
+    pub fn move_prev(&mut self) {
+        match self.cur {
+            Some(node) => {
+                self.cur = unsafe { (*node.as_ptr()).prev };
+                self.index = match self.cur {
+                    Some(_) => self.index.map(|i| i - 1),
+                    None => None,
+                };
+            }
+            None => {
+                self.cur = self.list.tail;
+                self.index = self.list.len.checked_sub(1);
+            }
+        }
+    }
+    /// # 在游标之前插入
+    /// 游标在幽灵位置时插入到尾部，游标位置不变
+    pub fn insert_before(&mut self, el: T) {
+        match self.cur {
+            Some(node) => {
+                let prev = unsafe { (*node.as_ptr()).prev };
+                self.list.link_between(el, prev, Some(node));
+                self.index = self.index.map(|i| i + 1);
+            }
+            None => self.list.push_back(el),
+        }
+    }
+    /// # 在游标之后插入
+    /// 游标在幽灵位置时插入到头部，游标位置不变
+    pub fn insert_after(&mut self, el: T) {
+        match self.cur {
+            Some(node) => {
+                let next = unsafe { (*node.as_ptr()).next };
+                self.list.link_between(el, Some(node), next);
+            }
+            None => self.list.push_front(el),
+        }
+    }
+    /// # 移除游标处的元素
+    /// 游标移到下一个节点（下标不变），幽灵位置返回None
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.cur?;
+        unsafe {
+            self.cur = (*node.as_ptr()).next;
+            if self.cur.is_none() {
+                self.index = None;
+            }
+            Some(self.list.unlink(node))
+        }
+    }
+}
+
+/// 转为不可变引用迭代器，两端相遇时结束
+pub struct Iter<'a, T: 'a> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.front.map(|node| unsafe {
+            self.len -= 1;
+            self.front = (*node.as_ptr()).next;
+            &(*node.as_ptr()).data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| unsafe {
+            self.len -= 1;
+            self.back = (*node.as_ptr()).prev;
+            &(*node.as_ptr()).data
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// 转为可变引用迭代器，两端相遇时结束
+pub struct IterMut<'a, T: 'a> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.front.map(|node| unsafe {
+            self.len -= 1;
+            self.front = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| unsafe {
+            self.len -= 1;
+            self.back = (*node.as_ptr()).prev;
+            &mut (*node.as_ptr()).data
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// 转为迭代器
+pub struct IntoIter<T>(DoublyLinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// 每个用例的最大操作数，Miri下逐条解释执行，缩短操作序列
+    const MAX_OPS: usize = if cfg!(miri) { 40 } else { 200 };
+
+    fn to_vec<T: Clone>(list: &DoublyLinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        assert_eq!(None, list.pop_front());
+        assert_eq!(None, list.pop_back());
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(Some(&1), list.front());
+        assert_eq!(Some(&3), list.back());
+        *list.back_mut().unwrap() = 30;
+        assert_eq!(vec![30, 2, 1], list.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(Some(30), list.pop_back());
+        assert_eq!(Some(1), list.pop_front());
+        assert_eq!(Some(2), list.pop_back());
+        assert!(list.is_empty());
+        assert_eq!(None, list.front());
+    }
+
+    #[test]
+    fn test_iter_meet_in_middle() {
+        let mut list: DoublyLinkedList<u32> = (0..5).collect();
+        let mut iter = list.iter();
+        assert_eq!(Some(&0), iter.next());
+        assert_eq!(Some(&4), iter.next_back());
+        assert_eq!(3, iter.len());
+        assert_eq!(vec![&1, &2, &3], iter.collect::<Vec<_>>());
+        let mut iter = list.iter_mut();
+        *iter.next_back().unwrap() *= 10;
+        *iter.next().unwrap() += 100;
+        assert_eq!(vec![40, 3, 2, 1, 100], list.clone().into_iter().rev().collect::<Vec<_>>());
+        assert_eq!("[100, 1, 2, 3, 40]", format!("{:?}", list));
+    }
+
+    #[test]
+    fn test_cursor_move() {
+        let mut list: DoublyLinkedList<char> = "abc".chars().collect();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(Some(0), cursor.index());
+        assert_eq!(Some(&mut 'a'), cursor.current());
+        assert_eq!(None, cursor.peek_prev());
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(Some(2), cursor.index());
+        cursor.move_next();
+        assert_eq!(None, cursor.index());
+        assert_eq!(None, cursor.current());
+        assert_eq!(Some(&mut 'a'), cursor.peek_next());
+        assert_eq!(Some(&mut 'c'), cursor.peek_prev());
+        cursor.move_next();
+        assert_eq!(Some(0), cursor.index());
+        cursor.move_prev();
+        assert_eq!(None, cursor.index());
+        cursor.move_prev();
+        assert_eq!(Some(2), cursor.index());
+        assert_eq!(Some(&mut 'c'), cursor.current());
+
+        let mut empty = DoublyLinkedList::<u8>::new();
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(None, cursor.index());
+        cursor.move_next();
+        assert_eq!(None, cursor.index());
+        cursor.insert_after(1);
+        cursor.insert_before(2);
+        assert_eq!(vec![1, 2], to_vec(&empty));
+    }
+
+    #[test]
+    fn test_cursor_edit() {
+        let mut list: DoublyLinkedList<u32> = (0..5).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_before(10);
+        assert_eq!(Some(3), cursor.index());
+        cursor.insert_after(20);
+        assert_eq!(Some(2), cursor.remove_current());
+        assert_eq!(Some(3), cursor.index());
+        assert_eq!(Some(&mut 20), cursor.current());
+        assert_eq!(vec![0, 1, 10, 20, 3, 4], to_vec(&list));
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(Some(4), cursor.remove_current());
+        assert_eq!(None, cursor.index());
+        assert_eq!(None, cursor.remove_current());
+        assert_eq!(Some(&3), list.back());
+        assert_eq!(5, list.len());
+    }
+
+    #[test]
+    fn test_lru_move_to_front() {
+        // 命中的节点摘下后挂到头部
+        let mut lru: DoublyLinkedList<&str> = ["a", "b", "c", "d"].into_iter().collect();
+        let mut cursor = lru.cursor_front_mut();
+        while cursor.current().is_some_and(|key| *key != "c") {
+            cursor.move_next();
+        }
+        let hit = cursor.remove_current().unwrap();
+        lru.push_front(hit);
+        assert_eq!(vec!["c", "a", "b", "d"], to_vec(&lru));
+        assert_eq!(Some("d"), lru.pop_back());
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        PushFront(u8),
+        PushBack(u8),
+        PopFront,
+        PopBack,
+    }
+
+    #[derive(Debug, Clone)]
+    enum CursorOp {
+        MoveNext,
+        MovePrev,
+        InsertBefore(u8),
+        InsertAfter(u8),
+        Remove,
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            any::<u8>().prop_map(Op::PushFront),
+            any::<u8>().prop_map(Op::PushBack),
+            Just(Op::PopFront),
+            Just(Op::PopBack),
+        ]
+    }
+
+    fn cursor_op() -> impl Strategy<Value = CursorOp> {
+        prop_oneof![
+            Just(CursorOp::MoveNext),
+            Just(CursorOp::MovePrev),
+            any::<u8>().prop_map(CursorOp::InsertBefore),
+            any::<u8>().prop_map(CursorOp::InsertAfter),
+            Just(CursorOp::Remove),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_std_linked_list(ops in prop::collection::vec(op(), 0..MAX_OPS)) {
+            let mut list = DoublyLinkedList::new();
+            let mut model = std::collections::LinkedList::new();
+            for op in ops {
+                match op {
+                    Op::PushFront(v) => { list.push_front(v); model.push_front(v); }
+                    Op::PushBack(v) => { list.push_back(v); model.push_back(v); }
+                    Op::PopFront => prop_assert_eq!(model.pop_front(), list.pop_front()),
+                    Op::PopBack => prop_assert_eq!(model.pop_back(), list.pop_back()),
+                }
+                prop_assert_eq!(model.len(), list.len());
+                prop_assert_eq!(model.front(), list.front());
+                prop_assert_eq!(model.back(), list.back());
+            }
+            prop_assert!(model.iter().eq(list.iter()));
+            prop_assert!(model.iter().rev().eq(list.iter().rev()));
+            prop_assert!(model.into_iter().rev().eq(list.into_iter().rev()));
+        }
+
+        #[test]
+        fn prop_cursor_matches_vec_model(
+            init in prop::collection::vec(any::<u8>(), 0..20),
+            ops in prop::collection::vec(cursor_op(), 0..MAX_OPS / 2),
+        ) {
+            let mut list: DoublyLinkedList<u8> = init.iter().copied().collect();
+            let mut model = init;
+            // 幽灵位置用model.len()表示
+            let mut pos = 0;
+            let mut cursor = list.cursor_front_mut();
+            for op in ops {
+                let ghost = model.len();
+                match op {
+                    CursorOp::MoveNext => pos = if pos == ghost { 0 } else { pos + 1 },
+                    CursorOp::MovePrev => pos = if pos == 0 { ghost } else { pos - 1 },
+                    CursorOp::InsertBefore(v) => {
+                        cursor.insert_before(v);
+                        model.insert(pos, v);
+                        pos += 1;
+                    }
+                    CursorOp::InsertAfter(v) => {
+                        cursor.insert_after(v);
+                        if pos == ghost {
+                            model.insert(0, v);
+                            pos += 1;
+                        } else {
+                            model.insert(pos + 1, v);
+                        }
+                    }
+                    CursorOp::Remove => {
+                        let expected = (pos < ghost).then(|| model.remove(pos));
+                        prop_assert_eq!(expected, cursor.remove_current());
+                    }
+                }
+                match op {
+                    CursorOp::MoveNext => cursor.move_next(),
+                    CursorOp::MovePrev => cursor.move_prev(),
+                    _ => {}
+                }
+                let ghost = model.len();
+                prop_assert_eq!((pos < ghost).then_some(pos), cursor.index());
+                prop_assert_eq!(model.get(pos).copied(), cursor.current().copied());
+            }
+            prop_assert_eq!(model.len(), list.len());
+            prop_assert!(model.iter().eq(list.iter()));
+            prop_assert!(model.iter().rev().eq(list.iter().rev()));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! # 图（Graph）
+//! 由顶点和边组成，使用邻接表存储：每个顶点保存一个出边列表
+//! ## 结构
+//! ```code
+//!     (0) ——5——> (1)          nodes:     | N0 | N1 | N2 |
+//!      |          |            edges:     | 0→1:5 | 0→2:1 | 1→2:2 |
+//!      1          2            adjacency: 0: [(1, e0), (2, e1)]
+//!      ↓          ↓                       1: [(2, e2)]
+//!     (2) <———————                        2: []
+//! ```
+//! ## 有向图与无向图
+//! 1. 有向图：边`a → b`只出现在a的邻接表中
+//! 2. 无向图：边`a — b`同时出现在a和b的邻接表中，两边共用同一个边编号
+//! ## 常用方法
+//! 1. add_node / add_edge:添加顶点和边，返回编号
+//! 2. node / edge:按编号查询
+//! 3. neighbors / edges_from:遍历某个顶点的邻居和出边
+//! 4. edges:遍历所有边
+//!
+//! 遍历、最短路径、最小生成树等算法位于`algorithm::graph`
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/16
+//! @version:0.0.1
+//! @description:
+//! ```
+
+/// # 边
+/// 无向图中from和to只表示添加时的顺序
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge<E> {
+    from: usize,
+    to: usize,
+    weight: E,
+}
+
+impl<E> Edge<E> {
+    pub fn from(&self) -> usize {
+        self.from
+    }
+    pub fn to(&self) -> usize {
+        self.to
+    }
+    pub fn weight(&self) -> &E {
+        &self.weight
+    }
+    /// 给定一个端点，返回另一个端点
+    pub fn other(&self, node: usize) -> usize {
+        if node == self.from {
+            self.to
+        } else {
+            self.from
+        }
+    }
+}
+
+/// # 图
+/// 顶点和边都用插入顺序作为编号
+#[derive(Debug, Clone)]
+pub struct Graph<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<Edge<E>>,
+    /// 邻接表：(邻居, 边编号)
+    adjacency: Vec<Vec<(usize, usize)>>,
+    directed: bool,
+}
+
+impl<N, E> Graph<N, E> {
+    /// 有向图
+    pub fn new_directed() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            adjacency: Vec::new(),
+            directed: true,
+        }
+    }
+    /// 无向图
+    pub fn new_undirected() -> Self {
+        Graph {
+            directed: false,
+            ..Graph::new_directed()
+        }
+    }
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+    /// 顶点数量
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+    /// 边数量
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+    /// 添加顶点，返回顶点编号
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.nodes.push(node);
+        self.adjacency.push(Vec::new());
+        self.nodes.len() - 1
+    }
+    /// # 添加边
+    /// 返回边编号，无向图中同时登记到两个端点的邻接表（自环只登记一次）
+    /// ## panic
+    /// 端点不存在时panic
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: E) -> usize {
+        let count = self.nodes.len();
+        assert!(from < count && to < count, "edge {from} -> {to} references a missing node (node count is {count})");
+        let id = self.edges.len();
+        self.edges.push(Edge { from, to, weight });
+        self.adjacency[from].push((to, id));
+        if !self.directed && from != to {
+            self.adjacency[to].push((from, id));
+        }
+        id
+    }
+    pub fn node(&self, index: usize) -> Option<&N> {
+        self.nodes.get(index)
+    }
+    pub fn node_mut(&mut self, index: usize) -> Option<&mut N> {
+        self.nodes.get_mut(index)
+    }
+    pub fn edge(&self, index: usize) -> Option<&Edge<E>> {
+        self.edges.get(index)
+    }
+    /// 按编号遍历所有顶点
+    pub fn nodes(&self) -> std::slice::Iter<'_, N> {
+        self.nodes.iter()
+    }
+    /// 按编号遍历所有边
+    pub fn edges(&self) -> std::slice::Iter<'_, Edge<E>> {
+        self.edges.iter()
+    }
+    /// 按添加顺序遍历某个顶点的邻居
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacency[node].iter().map(|&(to, _)| to)
+    }
+    /// 遍历某个顶点的出边：(邻居, 边编号, 权重)
+    pub fn edges_from(&self, node: usize) -> impl Iterator<Item = (usize, usize, &E)> + '_ {
+        self.adjacency[node]
+            .iter()
+            .map(move |&(to, id)| (to, id, &self.edges[id].weight))
+    }
+    /// 出度（无向图中为度）
+    pub fn degree(&self, node: usize) -> usize {
+        self.adjacency[node].len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed() {
+        let mut graph = Graph::new_directed();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let ab = graph.add_edge(a, b, 5);
+        graph.add_edge(a, c, 1);
+        graph.add_edge(b, c, 2);
+        assert_eq!(3, graph.node_count());
+        assert_eq!(3, graph.edge_count());
+        assert_eq!(vec![b, c], graph.neighbors(a).collect::<Vec<_>>());
+        assert_eq!(0, graph.degree(c));
+        assert_eq!(Some(&5), graph.edge(ab).map(Edge::weight));
+        assert_eq!(vec![(c, 2, &2)], graph.edges_from(b).collect::<Vec<_>>());
+        *graph.node_mut(a).unwrap() = "A";
+        assert_eq!(vec!["A", "b", "c"], graph.nodes().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut graph = Graph::new_undirected();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let e = graph.add_edge(a, b, 1.5);
+        graph.add_edge(b, b, 0.0);
+        assert!(!graph.is_directed());
+        assert_eq!(vec![b], graph.neighbors(a).collect::<Vec<_>>());
+        assert_eq!(vec![a, b], graph.neighbors(b).collect::<Vec<_>>());
+        assert_eq!(a, graph.edge(e).unwrap().other(b));
+    }
+
+    #[test]
+    #[should_panic(expected = "missing node")]
+    fn test_missing_node() {
+        let mut graph: Graph<(), ()> = Graph::new_directed();
+        graph.add_node(());
+        graph.add_edge(0, 1, ());
+    }
+}
@@ -0,0 +1,836 @@
+//! # 哈希表（Hash Table）
+//! 根据键的哈希值直接定位存储位置，实现O(1)的查找、插入和删除
+//! ## 结构（开放寻址 + Robin Hood）
+//! ```code
+//!  hash(k) & mask ——→ 理想位置
+//!
+//!  index: |  0  |  1  |  2  |  3  |  4  |  5  |  6  |  7  |
+//!  slot:  | k1  | k9  | --- | k3  | k4  | k7  | --- | --- |
+//!  dist:  |  0  |  1  |     |  0  |  1  |  2  |     |     |
+//! ```
+//! 所有元素直接存放在槽位数组中，冲突时向后线性探测。`dist`是元素离理想位置的距离（探测距离）：
+//! 1. 插入：探测途中遇到探测距离比自己小的"富"元素时，与它交换位置，让被挤出的元素继续向后找位置（劫富济贫）
+//! 2. 查找：遇到空槽或探测距离比当前探测次数小的元素时即可断定不存在
+//! 3. 删除：后移删除（backward shift），把后面探测距离不为0的元素依次前移一位，不需要墓碑
+//! ## 扩容
+//! 元素数量超过`容量 * 负载因子`时容量翻倍并重新插入所有元素，负载因子默认为0.875，可通过`with_load_factor`配置
+//! ## 常用方法
+//! 1. insert / get / get_mut / remove:增查改删
+//! 2. entry:基于条目的原地更新
+//! 3. iter / iter_mut / keys / values:遍历（顺序不保证）
+//! 4. with_hasher:传入自定义的`BuildHasher`
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/8/10
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt::{Debug, Formatter};
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+use std::ops::Index;
+
+/// 默认负载因子
+const DEFAULT_LOAD_FACTOR: f64 = 0.875;
+/// 第一次分配时的槽位数量
+const MIN_CAPACITY: usize = 8;
+
+/// # 槽位中的元素
+/// 缓存哈希值，探测和扩容时不需要重新计算
+#[derive(Clone)]
+struct Bucket<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+/// # 哈希表
+/// - slots:槽位数组，长度总是2的幂
+/// - len:元素数量
+/// - load_factor:负载因子
+/// - hasher:哈希函数构造器
+#[derive(Clone)]
+pub struct HashMap<K, V, S = RandomState> {
+    slots: Vec<Option<Bucket<K, V>>>,
+    len: usize,
+    load_factor: f64,
+    hasher: S,
+}
+
+impl<K, V> HashMap<K, V> {
+    /// 创建空的哈希表，第一次插入时才分配
+    pub fn new() -> Self {
+        HashMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V> HashMap<K, V> {
+    /// 创建能容纳`capacity`个元素而不需要扩容的哈希表
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashMap::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> {
+    /// 使用自定义的`BuildHasher`创建空的哈希表
+    pub fn with_hasher(hasher: S) -> Self {
+        HashMap {
+            slots: Vec::new(),
+            len: 0,
+            load_factor: DEFAULT_LOAD_FACTOR,
+            hasher,
+        }
+    }
+    /// # 设置负载因子
+    /// 元素数量超过`容量 * load_factor`时扩容
+    /// ## Panics
+    /// load_factor不在(0, 1)之间时panic
+    pub fn with_load_factor(mut self, load_factor: f64) -> Self {
+        assert!(
+            load_factor > 0.0 && load_factor < 1.0,
+            "load factor must be in (0, 1), got {}",
+            load_factor
+        );
+        self.load_factor = load_factor;
+        self
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 不扩容时最多能容纳的元素数量
+    pub fn capacity(&self) -> usize {
+        (self.slots.len() as f64 * self.load_factor) as usize
+    }
+    /// 负载因子
+    pub fn load_factor(&self) -> f64 {
+        self.load_factor
+    }
+    /// 哈希函数构造器
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+    /// 哈希值为hash的元素位于index时的探测距离
+    fn probe_distance(&self, hash: u64, index: usize) -> usize {
+        index.wrapping_sub(hash as usize) & self.mask()
+    }
+    /// 清空，保留槽位
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+    /// 遍历所有键值对（顺序不保证）
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            len: self.len,
+        }
+    }
+    /// 遍历所有键值对，值可变
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            len: self.len,
+        }
+    }
+    /// 遍历所有键
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+    /// 遍历所有值
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+    /// 遍历所有值（可变）
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> HashMap<K, V, S> {
+    /// 使用自定义的`BuildHasher`创建能容纳`capacity`个元素的哈希表
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut map = HashMap::with_hasher(hasher);
+        map.reserve(capacity);
+        map
+    }
+    fn hash<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+    /// # 预留空间
+    /// 保证还能再插入`additional`个元素而不需要扩容
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed <= self.capacity() {
+            return;
+        }
+        let mut slots = self.slots.len().max(MIN_CAPACITY);
+        while (slots as f64 * self.load_factor) < needed as f64 {
+            slots = slots.checked_mul(2).expect("capacity overflow");
+        }
+        self.resize(slots);
+    }
+    /// # 重新分配槽位
+    /// 按新的掩码重新插入所有元素
+    fn resize(&mut self, slots: usize) {
+        let old = mem::replace(&mut self.slots, (0..slots).map(|_| None).collect());
+        for bucket in old.into_iter().flatten() {
+            self.place(bucket);
+        }
+    }
+    /// # 查找键所在的槽位
+    /// 从理想位置开始探测，遇到空槽或探测距离更小的元素时停止
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.len == 0 {
+            return None;
+        }
+        let hash = self.hash(key);
+        let mut index = hash as usize & self.mask();
+        let mut dist = 0;
+        while let Some(bucket) = &self.slots[index] {
+            if self.probe_distance(bucket.hash, index) < dist {
+                return None;
+            }
+            if bucket.hash == hash && bucket.key.borrow() == key {
+                return Some(index);
+            }
+            index = (index + 1) & self.mask();
+            dist += 1;
+        }
+        None
+    }
+    /// # Robin Hood插入
+    /// 调用前需保证键不存在且有空槽，返回新元素最终所在的槽位
+    fn place(&mut self, mut bucket: Bucket<K, V>) -> usize {
+        let mask = self.mask();
+        let mut index = bucket.hash as usize & mask;
+        let mut dist = 0;
+        let mut placed = None;
+        loop {
+            match &mut self.slots[index] {
+                slot @ None => {
+                    *slot = Some(bucket);
+                    return placed.unwrap_or(index);
+                }
+                Some(resident) => {
+                    let resident_dist = index.wrapping_sub(resident.hash as usize) & mask;
+                    if resident_dist < dist {
+                        // 劫富济贫：占据探测距离更小的元素的位置，让它继续向后找
+                        mem::swap(resident, &mut bucket);
+                        placed.get_or_insert(index);
+                        dist = resident_dist;
+                    }
+                }
+            }
+            index = (index + 1) & mask;
+            dist += 1;
+        }
+    }
+    /// # 插入
+    /// 键已存在时替换值并返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(index) = self.find(&key) {
+            let bucket = self.slots[index].as_mut().unwrap();
+            return Some(mem::replace(&mut bucket.value, value));
+        }
+        self.reserve(1);
+        let hash = self.hash(&key);
+        self.place(Bucket { hash, key, value });
+        self.len += 1;
+        None
+    }
+    /// 查询
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|index| &self.slots[index].as_ref().unwrap().value)
+    }
+    /// 查询键值对
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|index| {
+            let bucket = self.slots[index].as_ref().unwrap();
+            (&bucket.key, &bucket.value)
+        })
+    }
+    /// 查询（可变）
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(&mut self.slots[index].as_mut().unwrap().value)
+    }
+    /// 是否包含某个键
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+    /// # 后移删除
+    /// 1. 取出index处的元素
+    /// 2. 后续探测距离不为0的元素依次前移一位，直到空槽或探测距离为0的元素
+    fn remove_at(&mut self, mut index: usize) -> Bucket<K, V> {
+        let removed = self.slots[index].take().unwrap();
+        loop {
+            let next = (index + 1) & self.mask();
+            match &self.slots[next] {
+                Some(bucket) if self.probe_distance(bucket.hash, next) > 0 => {
+                    self.slots[index] = self.slots[next].take();
+                    index = next;
+                }
+                _ => break,
+            }
+        }
+        self.len -= 1;
+        removed
+    }
+    /// 删除，返回被删除的值
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+    /// 删除，返回被删除的键值对
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        let bucket = self.remove_at(index);
+        Some((bucket.key, bucket.value))
+    }
+    /// 只保留满足条件的键值对
+    /// 从一个空槽之后开始绕一圈：空槽在删除过程中始终为空，后移删除不会越过它，
+    /// 已经访问过的元素不会被移回来，每个元素只检查一次
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        // 负载因子小于1，分配了槽位就一定有空槽
+        let Some(empty) = self.slots.iter().position(Option::is_none) else {
+            return;
+        };
+        let mut step = 1;
+        while step < self.slots.len() {
+            let index = (empty + step) & self.mask();
+            let keep = match &mut self.slots[index] {
+                Some(bucket) => f(&bucket.key, &mut bucket.value),
+                None => true,
+            };
+            if keep {
+                step += 1;
+            } else {
+                // 后移删除会把后面的元素移到index，需要再检查一次
+                self.remove_at(index);
+            }
+        }
+    }
+    /// # 获取条目
+    /// 先预留一个位置，保证空条目插入时不会扩容
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        self.reserve(1);
+        match self.find(&key) {
+            Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+}
+
+impl<K, V> Default for HashMap<K, V> {
+    fn default() -> Self {
+        HashMap::new()
+    }
+}
+
+impl<K: Debug, V: Debug, S> Debug for HashMap<K, V, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq, S: BuildHasher> PartialEq for HashMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for HashMap<K, V, S> {}
+
+impl<K, Q, V, S> Index<&Q> for HashMap<K, V, S>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.get(key).expect("key not found")
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for HashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = HashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for HashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+/// # 条目
+/// 1. Occupied:键已存在
+/// 2. Vacant:键不存在
+pub enum Entry<'a, K: 'a, V: 'a, S: 'a> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+impl<'a, K: Hash + Eq, V, S: BuildHasher> Entry<'a, K, V, S> {
+    /// 键不存在时插入default，返回值的可变引用
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+    /// 键不存在时插入f的返回值
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+    /// 键不存在时插入默认值
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+    /// 键存在时修改值
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            vacant => vacant,
+        }
+    }
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+/// 已存在的条目，记录所在槽位
+pub struct OccupiedEntry<'a, K: 'a, V: 'a, S: 'a> {
+    map: &'a mut HashMap<K, V, S>,
+    index: usize,
+}
+
+impl<'a, K: Hash + Eq, V, S: BuildHasher> OccupiedEntry<'a, K, V, S> {
+    fn bucket(&self) -> &Bucket<K, V> {
+        self.map.slots[self.index].as_ref().unwrap()
+    }
+    pub fn key(&self) -> &K {
+        &self.bucket().key
+    }
+    pub fn get(&self) -> &V {
+        &self.bucket().value
+    }
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.slots[self.index].as_mut().unwrap().value
+    }
+    /// 转为与哈希表生命周期相同的可变引用
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.slots[self.index].as_mut().unwrap().value
+    }
+    /// 替换值，返回旧值
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+    /// 删除该条目，返回值
+    pub fn remove(self) -> V {
+        self.map.remove_at(self.index).value
+    }
+}
+
+/// 不存在的条目，持有待插入的键
+pub struct VacantEntry<'a, K: 'a, V: 'a, S: 'a> {
+    map: &'a mut HashMap<K, V, S>,
+    key: K,
+}
+
+impl<'a, K: Hash + Eq, V, S: BuildHasher> VacantEntry<'a, K, V, S> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+    /// 取回键
+    pub fn into_key(self) -> K {
+        self.key
+    }
+    /// # 插入值
+    /// 获取条目时已预留位置，这里不会扩容，直接使用新元素落下的槽位
+    pub fn insert(self, value: V) -> &'a mut V {
+        let hash = self.map.hash(&self.key);
+        let index = self.map.place(Bucket {
+            hash,
+            key: self.key,
+            value,
+        });
+        self.map.len += 1;
+        &mut self.map.slots[index].as_mut().unwrap().value
+    }
+}
+
+/// 不可变引用迭代器
+pub struct Iter<'a, K: 'a, V: 'a> {
+    slots: std::slice::Iter<'a, Option<Bucket<K, V>>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.slots.by_ref().flatten().next()?;
+        self.len -= 1;
+        Some((&bucket.key, &bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// 可变引用迭代器
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    slots: std::slice::IterMut<'a, Option<Bucket<K, V>>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.slots.by_ref().flatten().next()?;
+        self.len -= 1;
+        Some((&bucket.key, &mut bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+/// 转为迭代器
+pub struct IntoIter<K, V> {
+    slots: std::vec::IntoIter<Option<Bucket<K, V>>>,
+    len: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.slots.by_ref().flatten().next()?;
+        self.len -= 1;
+        Some((bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V, S> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            slots: self.slots.into_iter(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// 所有键哈希到同一个值，强制产生最长的探测链
+    #[derive(Default)]
+    struct Collide;
+
+    impl Hasher for Collide {
+        fn finish(&self) -> u64 {
+            0
+        }
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    /// 恒等哈希，便于构造确定的冲突
+    #[derive(Default)]
+    struct Identity(u64);
+
+    impl Hasher for Identity {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for &b in bytes {
+                self.0 = (self.0 << 8) | b as u64;
+            }
+        }
+        fn write_u64(&mut self, n: u64) {
+            self.0 = n;
+        }
+    }
+
+    /// 检查Robin Hood不变式：每个元素都能从理想位置连续探测到
+    fn check<K, V, S>(map: &HashMap<K, V, S>) {
+        let mut count = 0;
+        for (index, slot) in map.slots.iter().enumerate() {
+            if let Some(bucket) = slot {
+                count += 1;
+                let dist = map.probe_distance(bucket.hash, index);
+                for d in 1..=dist {
+                    let prev = index.wrapping_sub(d) & map.mask();
+                    let prev = map.slots[prev].as_ref().expect("gap in probe sequence");
+                    assert!(map.probe_distance(prev.hash, index.wrapping_sub(d) & map.mask()) + d >= dist);
+                }
+            }
+        }
+        assert_eq!(count, map.len());
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = HashMap::new();
+        assert_eq!(None, map.get("a"));
+        assert_eq!(None, map.insert("a".to_string(), 1));
+        assert_eq!(None, map.insert("b".to_string(), 2));
+        assert_eq!(Some(1), map.insert("a".to_string(), 10));
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&10), map.get("a"));
+        *map.get_mut("b").unwrap() += 1;
+        assert_eq!(3, map["b"]);
+        assert!(map.contains_key("b"));
+        assert_eq!(Some(("b".to_string(), 3)), map.remove_entry("b"));
+        assert_eq!(None, map.remove("b"));
+        assert_eq!(1, map.len());
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(None, map.get("a"));
+    }
+
+    #[test]
+    fn test_collisions_and_backward_shift() {
+        let mut map: HashMap<u32, u32, BuildHasherDefault<Collide>> = HashMap::with_hasher(Default::default());
+        for i in 0..6 {
+            map.insert(i, i * 10);
+        }
+        check(&map);
+        assert_eq!(Some(20), map.remove(&2));
+        check(&map);
+        for i in [0, 1, 3, 4, 5] {
+            assert_eq!(Some(&(i * 10)), map.get(&i));
+        }
+        assert_eq!(None, map.get(&2));
+    }
+
+    #[test]
+    fn test_robin_hood_displacement() {
+        let mut map: HashMap<u64, char, BuildHasherDefault<Identity>> =
+            HashMap::with_capacity_and_hasher(4, Default::default());
+        assert_eq!(8, map.slots.len());
+        // 0和8都想要槽位0，1想要槽位1
+        map.insert(0, 'a');
+        map.insert(8, 'b');
+        map.insert(1, 'c');
+        check(&map);
+        // 8的探测距离为1，1被挤到槽位2
+        assert_eq!(8, map.slots[1].as_ref().unwrap().key);
+        assert_eq!(1, map.slots[2].as_ref().unwrap().key);
+        map.remove(&0);
+        check(&map);
+        assert_eq!(8, map.slots[0].as_ref().unwrap().key);
+        assert_eq!(1, map.slots[1].as_ref().unwrap().key);
+    }
+
+    #[test]
+    fn test_retain_wrapped_cluster() {
+        let mut map: HashMap<u64, (), BuildHasherDefault<Identity>> =
+            HashMap::with_capacity_and_hasher(4, Default::default());
+        assert_eq!(8, map.slots.len());
+        // 7在最后一个槽位，15和23绕回到槽位0和1
+        for key in [7, 15, 23] {
+            map.insert(key, ());
+        }
+        assert_eq!(15, map.slots[0].as_ref().unwrap().key);
+        let mut calls = std::collections::HashMap::new();
+        map.retain(|&key, _| {
+            *calls.entry(key).or_insert(0) += 1;
+            key != 7
+        });
+        assert_eq!(std::collections::HashMap::from([(7, 1), (15, 1), (23, 1)]), calls);
+        check(&map);
+        assert_eq!(2, map.len());
+        assert!(map.contains_key(&15) && map.contains_key(&23));
+    }
+
+    #[test]
+    fn test_load_factor_resize() {
+        let mut map = HashMap::new().with_load_factor(0.5);
+        assert_eq!(0, map.capacity());
+        for i in 0..4 {
+            map.insert(i, ());
+        }
+        assert_eq!(8, map.slots.len());
+        map.insert(4, ());
+        assert_eq!(16, map.slots.len());
+        assert_eq!(8, map.capacity());
+        check(&map);
+        let map: HashMap<u32, ()> = HashMap::with_capacity(100);
+        assert!(map.capacity() >= 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "load factor")]
+    fn test_invalid_load_factor() {
+        let _ = HashMap::<u8, u8>::new().with_load_factor(1.0);
+    }
+
+    #[test]
+    fn test_entry() {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in "hello world".chars() {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+        assert_eq!(3, counts[&'l']);
+        assert_eq!(2, counts[&'o']);
+        counts.entry('l').and_modify(|n| *n *= 10).or_default();
+        assert_eq!(30, counts[&'l']);
+        assert_eq!(&'z', counts.entry('z').key());
+        *counts.entry('z').or_insert_with(|| 7) += 1;
+        assert_eq!(8, counts[&'z']);
+        if let Entry::Occupied(entry) = counts.entry('h') {
+            assert_eq!(1, entry.remove());
+        }
+        assert!(!counts.contains_key(&'h'));
+        if let Entry::Occupied(mut entry) = counts.entry('w') {
+            assert_eq!(1, entry.insert(5));
+            assert_eq!(&5, entry.get());
+        }
+        check(&counts);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut map: HashMap<u32, String> = (0..20).map(|i| (i, i.to_string())).collect();
+        assert_eq!(20, map.iter().len());
+        for (_, v) in map.iter_mut() {
+            v.push('!');
+        }
+        for v in map.values_mut() {
+            v.push('?');
+        }
+        let mut keys: Vec<u32> = map.keys().copied().collect();
+        keys.sort();
+        assert_eq!((0..20).collect::<Vec<_>>(), keys);
+        assert!(map.values().all(|v| v.ends_with("!?")));
+        map.retain(|k, _| k % 2 == 0);
+        check(&map);
+        assert_eq!(10, map.len());
+        let other = map.clone();
+        assert_eq!(other, map);
+        let mut pairs: Vec<(u32, String)> = map.into_iter().collect();
+        pairs.sort();
+        assert_eq!((0, "0!?".to_string()), pairs[0]);
+        assert_eq!("{1: 2}", format!("{:?}", [(1, 2)].into_iter().collect::<HashMap<_, _>>()));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_std_hash_map(ops in prop::collection::vec((any::<bool>(), 0u16..300, any::<u32>()), 0..500)) {
+            let mut map = HashMap::new();
+            let mut model = std::collections::HashMap::new();
+            for (insert, k, v) in ops {
+                if insert {
+                    prop_assert_eq!(model.insert(k, v), map.insert(k, v));
+                } else {
+                    prop_assert_eq!(model.remove(&k), map.remove(&k));
+                }
+            }
+            check(&map);
+            prop_assert_eq!(model.len(), map.len());
+            for (k, v) in &model {
+                prop_assert_eq!(Some(v), map.get(k));
+            }
+        }
+    }
+}
@@ -0,0 +1,543 @@
+//! # 堆（Heap）
+//! 可以快速找到最大或最小元素的完全二叉树，使用数组存储
+//! ## 结构
+//! ```code
+//!              *9                 index:   0    1    2    3    4    5
+//!            ↙    ↘              data:  | *9 | *7 | *8 | *1 | *3 | *5 |
+//!          *7      *8
+//!        ↙   ↘    ↙              parent(i) = (i - 1) / 2
+//!      *1    *3  *5              children(i) = 2i + 1, 2i + 2
+//! ```
+//! ## 常用方法
+//! 1. push:入堆，放到末尾后上浮（sift up）O(logn)
+//! 2. pop:取出堆顶，末尾元素放到堆顶后下沉（sift down）O(logn)
+//! 3. peek / peek_mut:查看堆顶，通过peek_mut修改后自动下沉
+//! 4. from_vec:自底向上堆化 O(n)
+//! 5. into_sorted_vec:堆排序
+//! ## 大顶堆与小顶堆
+//! 堆顶始终是比较器认为"最大"的元素：
+//! 1. `BinaryHeap::new()`:大顶堆
+//! 2. `BinaryHeap::new_min()`:小顶堆，等价于在大顶堆中存放`std::cmp::Reverse<T>`
+//! 3. `BinaryHeap::with_comparator(|a, b| ...)`:自定义比较器
+//! ## 索引优先队列
+//! `IndexedPriorityQueue<K, P>`为每个元素绑定一个键，通过键记录元素在堆中的位置，
+//! 可以在O(logn)内降低某个键的优先级（decrease_key），Dijkstra和任务调度都依赖这个操作
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/9/9
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+/// # 比较器
+/// 堆顶是`compare`认为最大的元素
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// 大顶堆比较器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+impl<T: Ord> Compare<T> for Max {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// 小顶堆比较器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+impl<T: Ord> Compare<T> for Min {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// # 二叉堆
+#[derive(Clone)]
+pub struct BinaryHeap<T, C = Max> {
+    data: Vec<T>,
+    cmp: C,
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    /// 创建空的大顶堆
+    pub fn new() -> Self {
+        BinaryHeap::with_comparator(Max)
+    }
+}
+
+impl<T: Ord> BinaryHeap<T, Min> {
+    /// 创建空的小顶堆
+    pub fn new_min() -> Self {
+        BinaryHeap::with_comparator(Min)
+    }
+}
+
+impl<T: Ord> Default for BinaryHeap<T> {
+    fn default() -> Self {
+        BinaryHeap::new()
+    }
+}
+
+/// 原地堆化，O(n)
+impl<T: Ord> From<Vec<T>> for BinaryHeap<T> {
+    fn from(data: Vec<T>) -> Self {
+        BinaryHeap::from_vec(data, Max)
+    }
+}
+
+impl<T, C: Compare<T>> BinaryHeap<T, C> {
+    /// 使用自定义比较器创建空堆
+    pub fn with_comparator(cmp: C) -> Self {
+        BinaryHeap { data: Vec::new(), cmp }
+    }
+    /// # 自底向上堆化
+    /// 从最后一个非叶子节点开始依次下沉，总代价O(n)
+    pub fn from_vec(data: Vec<T>, cmp: C) -> Self {
+        let mut heap = BinaryHeap { data, cmp };
+        for i in (0..heap.data.len() / 2).rev() {
+            heap.sift_down(i, heap.data.len());
+        }
+        heap
+    }
+    /// 堆中元素数量
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    /// 是否为空堆
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// a是否应该位于b的上方
+    fn above(&self, a: usize, b: usize) -> bool {
+        self.cmp.compare(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+    /// # 上浮
+    /// 比父节点"大"时与父节点交换，直到堆顶
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.above(i, parent) {
+                break;
+            }
+            self.data.swap(i, parent);
+            i = parent;
+        }
+    }
+    /// # 下沉
+    /// 与较"大"的子节点交换，直到没有子节点比它"大"，只处理[0, end)
+    fn sift_down(&mut self, mut i: usize, end: usize) {
+        loop {
+            let left = 2 * i + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && self.above(right, left) { right } else { left };
+            if !self.above(child, i) {
+                break;
+            }
+            self.data.swap(i, child);
+            i = child;
+        }
+    }
+    /// # 入堆
+    /// 1. 放到数组末尾
+    /// 2. 上浮
+    pub fn push(&mut self, el: T) {
+        self.data.push(el);
+        self.sift_up(self.data.len() - 1);
+    }
+    /// # 取出堆顶
+    /// 1. 堆顶与末尾交换后弹出
+    /// 2. 新堆顶下沉
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.len().checked_sub(1)?;
+        self.data.swap(0, last);
+        let top = self.data.pop();
+        self.sift_down(0, self.data.len());
+        top
+    }
+    /// 查看堆顶
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+    /// # 修改堆顶
+    /// 返回的`PeekMut`被丢弃时堆顶自动下沉，保持堆的性质
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+    /// 清空
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    /// 按数组中的顺序（非有序）遍历
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+    /// 取出底层数组，顺序不保证
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+    /// # 堆排序
+    /// 不断把堆顶交换到末尾并缩小堆的范围，结果按比较器升序排列
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+}
+
+impl<T: Debug, C> Debug for BinaryHeap<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for BinaryHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        BinaryHeap::from_vec(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for BinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for el in iter {
+            self.push(el);
+        }
+    }
+}
+
+/// # 堆顶的可变引用
+/// drop时对堆顶执行下沉
+pub struct PeekMut<'a, T: 'a, C: Compare<T>> {
+    heap: &'a mut BinaryHeap<T, C>,
+}
+
+impl<T, C: Compare<T>> PeekMut<'_, T, C> {
+    /// 直接取出堆顶
+    pub fn pop(this: Self) -> T {
+        let top = this.heap.pop();
+        // heap.pop已经维护了堆的性质，不需要再下沉
+        mem::forget(this);
+        top.expect("PeekMut is only created for non-empty heaps")
+    }
+}
+
+impl<T, C: Compare<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> Drop for PeekMut<'_, T, C> {
+    fn drop(&mut self) {
+        let len = self.heap.data.len();
+        self.heap.sift_down(0, len);
+    }
+}
+
+/// # 索引优先队列（小顶）
+/// - heap:按优先级排列的(键, 优先级)
+/// - positions:键在heap中的下标，每次交换都同步更新
+///
+/// 需要大顶时可以把优先级包装为`std::cmp::Reverse`
+pub struct IndexedPriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedPriorityQueue<K, P> {
+    pub fn new() -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    /// 是否包含某个键
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+    /// 查询某个键的优先级
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&i| &self.heap[i].1)
+    }
+    /// 交换两个位置并同步下标
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.heap[b].0).unwrap() = b;
+    }
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+    fn sift_down(&mut self, mut i: usize) {
+        let end = self.heap.len();
+        loop {
+            let left = 2 * i + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && self.heap[right].1 < self.heap[left].1 { right } else { left };
+            if self.heap[child].1 >= self.heap[i].1 {
+                break;
+            }
+            self.swap(i, child);
+            i = child;
+        }
+    }
+    /// # 入队
+    /// 键已经存在时改为更新优先级，并返回原来的优先级
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&i) = self.positions.get(&key) {
+            return Some(self.set_priority(i, priority));
+        }
+        self.positions.insert(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+    /// 替换下标i处的优先级并调整位置，返回原来的优先级
+    fn set_priority(&mut self, i: usize, priority: P) -> P {
+        let old = mem::replace(&mut self.heap[i].1, priority);
+        match self.heap[i].1.cmp(&old) {
+            Ordering::Less => self.sift_up(i),
+            Ordering::Greater => self.sift_down(i),
+            Ordering::Equal => {}
+        }
+        old
+    }
+    /// # 降低优先级
+    /// 只有新的优先级更小时才会生效，返回是否生效（键不存在返回false）
+    pub fn decrease_key(&mut self, key: &K, priority: P) -> bool {
+        match self.positions.get(key) {
+            Some(&i) if priority < self.heap[i].1 => {
+                self.set_priority(i, priority);
+                true
+            }
+            _ => false,
+        }
+    }
+    /// # 修改优先级
+    /// 升高或降低均可，键不存在返回None
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let &i = self.positions.get(key)?;
+        Some(self.set_priority(i, priority))
+    }
+    /// 查看优先级最小的元素
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(k, p)| (k, p))
+    }
+    /// # 取出优先级最小的元素
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        self.remove_at(0)
+    }
+    /// # 移除某个键
+    /// 返回它的优先级
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let &i = self.positions.get(key)?;
+        self.remove_at(i).map(|(_, p)| p)
+    }
+    /// 与末尾交换后弹出，被换过来的元素可能需要上浮或下沉
+    fn remove_at(&mut self, i: usize) -> Option<(K, P)> {
+        let last = self.heap.len().checked_sub(1)?;
+        self.swap(i, last);
+        let (key, priority) = self.heap.pop()?;
+        self.positions.remove(&key);
+        if i < self.heap.len() {
+            self.sift_down(i);
+            self.sift_up(i);
+        }
+        Some((key, priority))
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        IndexedPriorityQueue::new()
+    }
+}
+
+impl<K: Debug, P: Debug> Debug for IndexedPriorityQueue<K, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.heap.iter().map(|(k, p)| (k, p))).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = BinaryHeap::new();
+        assert_eq!(None, heap.pop());
+        for x in [4, 1, 9, 7, 3, 8] {
+            heap.push(x);
+        }
+        assert_eq!(Some(&9), heap.peek());
+        assert_eq!(6, heap.len());
+        assert_eq!(Some(9), heap.pop());
+        assert_eq!(Some(8), heap.pop());
+        assert_eq!(vec![1, 3, 4, 7], heap.into_sorted_vec());
+    }
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap: BinaryHeap<u32, Min> = [5, 2, 8, 1].into_iter().collect();
+        assert_eq!(Some(1), heap.pop());
+        let mut reversed: BinaryHeap<Reverse<u32>> = [5, 2, 8, 1].into_iter().map(Reverse).collect();
+        assert_eq!(Some(Reverse(1)), reversed.pop());
+        assert_eq!(heap.pop(), reversed.pop().map(|r| r.0));
+        // 小顶堆的"升序"是从大到小
+        assert_eq!(vec![8, 5], heap.into_sorted_vec());
+        let mut by_len = BinaryHeap::with_comparator(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+        by_len.extend(["ab", "abcd", "a"]);
+        assert_eq!(Some("abcd"), by_len.pop());
+        assert!(BinaryHeap::<u8, Min>::new_min().is_empty());
+    }
+
+    #[test]
+    fn test_peek_mut() {
+        let mut heap = BinaryHeap::from(vec![1, 5, 3]);
+        {
+            let mut top = heap.peek_mut().unwrap();
+            *top = 0;
+        }
+        assert_eq!(Some(&3), heap.peek());
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(3, PeekMut::pop(top));
+        assert_eq!(vec![0, 1], heap.into_sorted_vec());
+    }
+
+    #[test]
+    fn test_indexed_priority_queue() {
+        let mut pq = IndexedPriorityQueue::new();
+        assert_eq!(None, pq.push("a", 5));
+        assert_eq!(None, pq.push("b", 3));
+        assert_eq!(None, pq.push("c", 8));
+        assert_eq!(Some((&"b", &3)), pq.peek());
+        assert!(pq.decrease_key(&"c", 1));
+        assert!(!pq.decrease_key(&"c", 2));
+        assert!(!pq.decrease_key(&"x", 0));
+        assert_eq!(Some(&1), pq.priority(&"c"));
+        assert_eq!(Some(5), pq.push("a", 10));
+        assert_eq!(Some(3), pq.remove(&"b"));
+        assert_eq!(Some(1), pq.change_priority(&"c", 20));
+        assert_eq!(Some(("a", 10)), pq.pop());
+        assert_eq!(Some(("c", 20)), pq.pop());
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // 0 →1→ 1 →2→ 3, 0 →4→ 2 →1→ 3, 1 →1→ 2
+        let edges: Vec<Vec<(usize, u32)>> = vec![vec![(1, 1), (2, 4)], vec![(3, 2), (2, 1)], vec![(3, 1)], vec![]];
+        let mut dist = vec![u32::MAX; edges.len()];
+        let mut pq = IndexedPriorityQueue::new();
+        dist[0] = 0;
+        pq.push(0, 0);
+        while let Some((u, d)) = pq.pop() {
+            for &(v, w) in &edges[u] {
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    if !pq.decrease_key(&v, dist[v]) {
+                        pq.push(v, dist[v]);
+                    }
+                }
+            }
+        }
+        assert_eq!(vec![0, 1, 2, 3], dist);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_std_heap(data in prop::collection::vec(any::<i32>(), 0..200), pops in 0usize..50) {
+            let mut heap = BinaryHeap::from(data.clone());
+            let mut model = std::collections::BinaryHeap::from(data.clone());
+            for _ in 0..pops {
+                prop_assert_eq!(model.pop(), heap.pop());
+                prop_assert_eq!(model.peek(), heap.peek());
+            }
+            let mut sorted = data;
+            sorted.sort();
+            prop_assert_eq!(model.into_sorted_vec(), heap.into_sorted_vec());
+            let mut min: BinaryHeap<i32, Min> = sorted.iter().copied().collect();
+            for x in &sorted {
+                prop_assert_eq!(Some(*x), min.pop());
+            }
+        }
+
+        #[test]
+        fn prop_indexed_queue_pops_in_order(
+            items in prop::collection::vec((0u8..50, any::<u16>()), 0..100),
+            updates in prop::collection::vec((0u8..50, any::<u16>()), 0..50),
+        ) {
+            let mut pq = IndexedPriorityQueue::new();
+            let mut model = HashMap::new();
+            for (k, p) in items.into_iter().chain(updates) {
+                prop_assert_eq!(model.insert(k, p), pq.push(k, p));
+            }
+            let mut expected: Vec<(u8, u16)> = model.into_iter().collect();
+            expected.sort_by_key(|&(k, p)| (p, k));
+            let mut popped = vec![];
+            while let Some(item) = pq.pop() {
+                popped.push(item);
+            }
+            let mut popped_sorted = popped.clone();
+            popped_sorted.sort_by_key(|&(k, p)| (p, k));
+            prop_assert!(popped.windows(2).all(|w| w[0].1 <= w[1].1));
+            prop_assert_eq!(expected, popped_sorted);
+        }
+    }
+}
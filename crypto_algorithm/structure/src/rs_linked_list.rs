@@ -12,6 +12,13 @@
 //!   |  data  |  next pointer | ——→ next el
 //!   |————————————————————————|
 //! ```
+//! ## 常用方法
+//! 1. push / pop:头部入链、出链
+//! 2. push_back:通过尾指针在尾部入链，O(1)
+//! 3. peek / peek_back:查看头、尾元素
+//! 4. insert_at / remove_at:在指定位置插入、移除
+//! 5. reverse / append / split_off:反转、拼接、拆分
+//! 6. contains / retain:查找、过滤
 //! ## 使用场景
 //!1. 实现容器：链表可以用于实现各种容器，如列表、队列和栈。链表具有动态扩展和收缩的特性，可以高效地添加、删除和访问元素。
 //!2. 内存管理：链表在内存分配中有广泛的应用。例如，操作系统中的内存管理器可以使用链表来跟踪未使用的内存块。
@@ -27,15 +34,30 @@
 //! @description:
 //! ```
 
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr::{self, NonNull};
+
+/// 指向堆上节点的指针，节点由`Box::into_raw`创建
+type Link<T> = Option<NonNull<Node<T>>>;
+
 /// # 单个节点
 /// 1. 数据域
 /// 2. 指针域：指向下一个节点
+///
+/// 节点拥有它之后的整条链，drop时一并释放
 #[derive(Debug)]
 pub struct Node<T> {
     data: T,
-    next: Option<Box<Node<T>>>,
+    next: Link<T>,
 }
 
+// 与Box<Node<T>>一样，节点独占它之后的整条链
+unsafe impl<T: Send> Send for Node<T> {}
+
+unsafe impl<T: Sync> Sync for Node<T> {}
+
 impl<T> Node<T> {
     /// # 初始化空节点
     /// 传入数据域
@@ -46,31 +68,72 @@ impl<T> Node<T> {
             next: None,
         }
     }
+    /// 在堆上创建节点并交出所有权，由链表负责释放
+    fn alloc(data: T, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Box::new(Node { data, next });
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+    /// # 释放节点
+    /// 取回数据域和后继，后继不会被释放
+    /// ## Safety
+    /// node必须来自`alloc`，且之后不再被使用
+    unsafe fn free(node: NonNull<Node<T>>) -> (T, Link<T>) {
+        let node = ManuallyDrop::new(*Box::from_raw(node.as_ptr()));
+        (ptr::read(&node.data), node.next)
+    }
     /// 设置数据域
-    pub fn set(&mut self, data: T) -> () {
+    pub fn set(&mut self, data: T) {
         self.data = data;
     }
     /// 返回数据域
     pub fn get(&self) -> Option<&T> {
         Some(&self.data)
     }
-    /// 设置后续节点
-    pub fn set_next(&mut self, el: Node<T>) -> () {
-        self.next = Some(Box::new(el));
+    /// 设置后续节点，原来的后续节点被释放
+    pub fn set_next(&mut self, el: Node<T>) {
+        let node = NonNull::from(Box::leak(Box::new(el)));
+        free_chain(self.next.replace(node));
     }
 }
-#[derive(Debug)]
+
+/// 循环逐个释放从link开始的节点，避免递归drop导致长链表栈溢出
+fn free_chain<T>(mut link: Link<T>) {
+    while let Some(node) = link {
+        link = unsafe { Node::free(node) }.1;
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        free_chain(self.next.take());
+    }
+}
+
+/// # 单链表
+/// - node:头节点
+/// - tail:尾节点，只用于O(1)的尾部入链
+///
+/// 所有节点都通过`Box::into_raw`交给链表管理，头指针、尾指针和节点间的指针都是同一来源的裸指针，
+/// 不会因为移动Box而失效
 pub struct LinkedList<T> {
     len: usize,
-    node: Option<Box<Node<T>>>,
+    node: Link<T>,
+    tail: Link<T>,
+    _marker: PhantomData<Box<Node<T>>>,
 }
 
+unsafe impl<T: Send> Send for LinkedList<T> {}
+
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
 impl<T> LinkedList<T> {
     /// 初始化空链表
     pub fn new() -> Self {
         LinkedList {
             len: 0,
             node: None,
+            tail: None,
+            _marker: PhantomData,
         }
     }
     /// 获取链表长度
@@ -78,53 +141,230 @@ impl<T> LinkedList<T> {
     /// 判断是否为空链表
     pub fn is_empty(&self) -> bool { 0_usize.eq(&self.len()) }
     /// 添加元素
-    pub fn push(&mut self, el: T) -> () {
-        let node = Box::new(Node{
-            data: el,
-            next: self.node.take()
-        });
+    pub fn push(&mut self, el: T) {
+        let node = Node::alloc(el, self.node);
+        if self.tail.is_none() {
+            self.tail = Some(node);
+        }
         self.node = Some(node);
         self.len += 1;
     }
+    /// # 尾部添加元素
+    /// 1. 新节点挂到尾节点之后（空链表时作为头节点）
+    /// 2. 尾指针指向新节点
+    pub fn push_back(&mut self, el: T) {
+        let node = Node::alloc(el, None);
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.node = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
     /// # 取出头节点
     /// 1. 获取头节点
     /// 2. 后续节点前移
     pub fn pop(&mut self)->Option<T>{
-        self.node.take().map(|node|{
-            self.node = node.next;
+        self.node.map(|node|{
+            let (data, next) = unsafe { Node::free(node) };
+            self.node = next;
             self.len-=1;
-            node.data
+            if self.node.is_none() {
+                self.tail = None;
+            }
+            data
         })
     }
-    /// 获取头节点的引用
-    pub fn peek(&self)->Option<&Box<Node<T>>>{
-        self.node.as_ref()
+    /// 获取头节点数据域的引用
+    pub fn peek(&self)->Option<&T>{
+        self.node.map(|node| unsafe { &(*node.as_ptr()).data })
     }
-    /// 获取头节点可变引用
-    pub fn peek_as_mut(&mut self)->Option<&mut Box<Node<T>>>{
-        self.node.as_mut()
+    /// 获取头节点数据域的可变引用
+    pub fn peek_as_mut(&mut self)->Option<&mut T>{
+        self.node.map(|node| unsafe { &mut (*node.as_ptr()).data })
     }
-    pub fn into_iter(self)->IntoIter<T>{
-        IntoIter(self)
+    /// 获取尾节点数据域的引用
+    pub fn peek_back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+    /// 找到第index个节点的指针域
+    fn link_at(&mut self, index: usize) -> &mut Link<T> {
+        let mut link = &mut self.node;
+        for _ in 0..index {
+            let node = link.expect("index within length");
+            link = unsafe { &mut (*node.as_ptr()).next };
+        }
+        link
+    }
+    /// 从头遍历重新定位尾节点
+    fn reset_tail(&mut self) {
+        let mut tail = None;
+        let mut cur = self.node;
+        while let Some(node) = cur {
+            tail = Some(node);
+            cur = unsafe { (*node.as_ptr()).next };
+        }
+        self.tail = tail;
+    }
+    /// # 在指定位置插入
+    /// index为0等同于push，等于长度时等同于push_back
+    /// ## Panics
+    /// index大于长度时panic
+    pub fn insert_at(&mut self, index: usize, el: T) {
+        assert!(index <= self.len, "index {} out of bounds for length {}", index, self.len);
+        if index == self.len {
+            return self.push_back(el);
+        }
+        let link = self.link_at(index);
+        *link = Some(Node::alloc(el, *link));
+        self.len += 1;
+    }
+    /// # 移除指定位置的元素
+    /// 越界返回None，移除尾节点时尾指针前移
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        let link = self.link_at(index);
+        let (data, next) = unsafe { Node::free((*link)?) };
+        *link = next;
+        self.len -= 1;
+        if index == self.len {
+            self.reset_tail();
+        }
+        Some(data)
+    }
+    /// # 反转
+    /// 逐个把节点的指针域指向前一个节点，原头节点成为尾节点
+    pub fn reverse(&mut self) {
+        let mut reversed: Link<T> = None;
+        let mut cur = self.node;
+        self.tail = self.node;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).next;
+                (*node.as_ptr()).next = reversed;
+            }
+            reversed = Some(node);
+        }
+        self.node = reversed;
+    }
+    /// # 拼接
+    /// 把other的全部节点挂到尾部，other变为空链表
+    pub fn append(&mut self, other: &mut Self) {
+        let Some(head) = other.node.take() else {
+            return;
+        };
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(head) },
+            None => self.node = Some(head),
+        }
+        self.tail = other.tail.take();
+        self.len += other.len;
+        other.len = 0;
+    }
+    /// # 拆分
+    /// 保留前at个元素，返回从at开始的剩余部分
+    /// ## Panics
+    /// at大于长度时panic
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index {} out of bounds for length {}", at, self.len);
+        let len = self.len;
+        let rest = self.link_at(at).take();
+        let mut other = LinkedList::new();
+        if rest.is_some() {
+            other.node = rest;
+            other.tail = self.tail;
+            other.len = len - at;
+        }
+        self.len = at;
+        self.reset_tail();
+        other
     }
-    pub fn iter(&self)->Iter<T>{
+    /// 是否包含某个元素
+    pub fn contains(&self, el: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|data| data == el)
+    }
+    /// # 只保留满足条件的元素
+    /// 按顺序检查每个节点，不满足条件的直接摘除
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut link = &mut self.node;
+        while let Some(node) = *link {
+            if f(unsafe { &(*node.as_ptr()).data }) {
+                link = unsafe { &mut (*node.as_ptr()).next };
+            } else {
+                *link = unsafe { Node::free(node) }.1;
+                self.len -= 1;
+            }
+        }
+        self.reset_tail();
+    }
+    pub fn iter(&self)->Iter<'_, T>{
         Iter{
-            next: self.node.as_deref()
+            next: self.node.map(|node| unsafe { &*node.as_ptr() })
         }
     }
-    pub fn iter_mut(&mut self)->IterMut<T>{
+    pub fn iter_mut(&mut self)->IterMut<'_, T>{
         IterMut{
-            next: self.node.as_deref_mut()
+            next: self.node.map(|node| unsafe { &mut *node.as_ptr() })
         }
     }
 }
 
-/// 清空
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+/// # 清空
+/// 循环逐个释放节点，避免递归drop导致长链表栈溢出
 impl<T> Drop for LinkedList<T>{
     fn drop(&mut self) {
-        self.node.take().map(|mut node|{
-           node.next.take()
-        });
+        free_chain(self.node.take());
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// 依次添加到尾部
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for el in iter {
+            self.push_back(el);
+        }
     }
 }
 
@@ -138,6 +378,34 @@ impl<T> Iterator for IntoIter<T>{
         self.0.pop()
     }
 }
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 /// 转为不可变引用迭代器(Node)
 /// 通过迭代器获取Node的数据域
 /// 获取到之后将下一个前移（None也一样）
@@ -150,7 +418,7 @@ impl <'a,T> Iterator for Iter<'a ,T>{
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node|{
-            self.next = node.next.as_deref();
+            self.next = node.next.map(|next| unsafe { &*next.as_ptr() });
             &node.data
         })
     }
@@ -166,8 +434,118 @@ impl<'a ,T> Iterator for IterMut<'a ,T>{
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.take().map(|node|{
-            self.next = node.next.as_deref_mut();
+            self.next = node.next.map(|next| unsafe { &mut *next.as_ptr() });
             &mut node.data
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_vec<T: Clone>(list: &LinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut list = LinkedList::new();
+        assert_eq!(None, list.pop());
+        list.push(2);
+        list.push(1);
+        list.push_back(3);
+        assert_eq!(Some(&1), list.peek());
+        assert_eq!(Some(&3), list.peek_back());
+        *list.peek_as_mut().unwrap() = 0;
+        assert_eq!(vec![0, 2, 3], to_vec(&list));
+        assert_eq!(Some(0), list.pop());
+        assert_eq!(Some(2), list.pop());
+        assert_eq!(Some(3), list.pop());
+        assert_eq!(None, list.peek_back());
+        list.push_back(4);
+        assert_eq!(Some(&4), list.peek());
+        assert_eq!(1, list.len());
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut list: LinkedList<u32> = (1..4).collect();
+        list.insert_at(0, 0);
+        list.insert_at(4, 5);
+        list.insert_at(4, 4);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], to_vec(&list));
+        assert_eq!(Some(5), list.remove_at(5));
+        assert_eq!(Some(&4), list.peek_back());
+        assert_eq!(Some(2), list.remove_at(2));
+        assert_eq!(None, list.remove_at(4));
+        list.push_back(6);
+        assert_eq!(vec![0, 1, 3, 4, 6], to_vec(&list));
+        assert!(list.contains(&3));
+        assert!(!list.contains(&2));
+    }
+
+    #[test]
+    fn test_reverse_append_split() {
+        let mut list: LinkedList<u32> = (0..5).collect();
+        list.reverse();
+        list.push_back(10);
+        assert_eq!(vec![4, 3, 2, 1, 0, 10], to_vec(&list));
+
+        let mut rest = list.split_off(2);
+        assert_eq!(vec![4, 3], to_vec(&list));
+        assert_eq!(vec![2, 1, 0, 10], to_vec(&rest));
+        list.push_back(20);
+        rest.push_back(30);
+        assert_eq!(Some(&20), list.peek_back());
+
+        list.append(&mut rest);
+        assert!(rest.is_empty());
+        assert_eq!(None, rest.peek_back());
+        list.push_back(40);
+        assert_eq!(vec![4, 3, 20, 2, 1, 0, 10, 30, 40], to_vec(&list));
+        assert_eq!(9, list.len());
+
+        assert!(list.split_off(9).is_empty());
+        let all = list.split_off(0);
+        assert!(list.is_empty());
+        assert_eq!(9, all.len());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list: LinkedList<u32> = (0..10).collect();
+        list.retain(|x| x % 3 != 0);
+        assert_eq!(vec![1, 2, 4, 5, 7, 8], to_vec(&list));
+        assert_eq!(6, list.len());
+        assert_eq!(Some(&8), list.peek_back());
+        list.retain(|&x| x < 5);
+        list.push_back(9);
+        assert_eq!(vec![1, 2, 4, 9], to_vec(&list));
+    }
+
+    #[test]
+    fn test_traits() {
+        let list: LinkedList<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let mut copy = list.clone();
+        assert_eq!(list, copy);
+        copy.extend(["c".to_string()]);
+        assert_ne!(list, copy);
+        assert_eq!("[\"a\", \"b\", \"c\"]", format!("{:?}", copy));
+        for s in &mut copy {
+            s.push('!');
+        }
+        assert_eq!(vec!["a!", "b!", "c!"], copy.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        // Miri下逐条解释执行，只验证释放过程本身
+        let n = if cfg!(miri) { 1_000 } else { 10_000_000_u32 };
+        let mut list = LinkedList::new();
+        for i in 0..n {
+            list.push(i);
+        }
+        drop(list);
+    }
+}
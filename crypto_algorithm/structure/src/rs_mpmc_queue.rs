@@ -0,0 +1,352 @@
+//! # 有界多生产者多消费者队列（Vyukov MPMC Queue）
+//! 基于数组的无锁环形队列，每个槽位带有一个序号（sequence），生产者和消费者通过序号判断槽位状态
+//! ## 结构
+//! ```code
+//!   capacity = 3, one_lap = 4
+//!   enqueue_pos = lap 1 + 1         dequeue_pos = lap 0 + 2
+//!                                  ↓
+//!   slots: | seq:4+0 (empty) | seq:0+1+4 ... | seq:0+2+1 (data) |
+//! ```
+//! ## 位置与序号
+//! 位置（stamp）由圈数和下标组成：`lap + index`，`one_lap`是大于capacity的最小的2的幂，
+//! 低位是槽位下标，高位是圈数。下标到达capacity后进入下一圈、下标归零。
+//! 槽位的序号：
+//! 1. `seq == stamp`:槽位为空，可以写入位置stamp的数据
+//! 2. `seq == stamp + 1`:槽位中是位置stamp的数据，可以读取
+//! 3. 读取后`seq = stamp + one_lap`，留给下一圈的生产者
+//!
+//! 所有运算都是回绕的（wrapping），one_lap整除`usize::MAX + 1`，位置回绕到0时下标仍然连续，
+//! 因此任意容量在32位和64位平台上都可以无限期运行。
+//!
+//! 生产者和消费者分别通过CAS推进enqueue_pos和dequeue_pos来占有一个位置，
+//! 之后只有占有者会访问该槽位的数据
+//! ## 常用方法
+//! 1. try_push / try_pop:非阻塞，队列满或空时立即返回
+//! 2. push / pop:阻塞，队列满或空时等待
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/20
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use crate::sync::{AtomicUsize, Backoff, Ordering, UnsafeCell};
+use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
+
+struct Slot<T> {
+    seq: AtomicUsize,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// 对齐到缓存行，避免生产者和消费者的位置计数互相干扰（false sharing）
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+/// # 有界MPMC队列
+/// 可以通过`Arc`在多个线程间共享
+pub struct BoundedMpmcQueue<T> {
+    slots: Box<[Slot<T>]>,
+    /// 一圈的位置数量，大于capacity的2的幂
+    one_lap: usize,
+    enqueue_pos: CachePadded<AtomicUsize>,
+    dequeue_pos: CachePadded<AtomicUsize>,
+}
+
+unsafe impl<T: Send> Send for BoundedMpmcQueue<T> {}
+unsafe impl<T: Send> Sync for BoundedMpmcQueue<T> {}
+
+impl<T> BoundedMpmcQueue<T> {
+    /// # 创建
+    /// ## panic
+    /// 容量为0时panic
+    pub fn new(capacity: usize) -> Self {
+        Self::starting_at(capacity, 0)
+    }
+    /// 从圈数lap开始，测试中用来验证位置回绕
+    fn starting_at(capacity: usize, lap: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        let one_lap = (capacity + 1).next_power_of_two();
+        let start = lap.wrapping_mul(one_lap);
+        let slots = (0..capacity)
+            .map(|i| Slot {
+                seq: AtomicUsize::new(start.wrapping_add(i)),
+                data: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        BoundedMpmcQueue {
+            slots,
+            one_lap,
+            enqueue_pos: CachePadded(AtomicUsize::new(start)),
+            dequeue_pos: CachePadded(AtomicUsize::new(start)),
+        }
+    }
+    /// 容量
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+    /// 位置对应的槽位下标
+    fn index(&self, stamp: usize) -> usize {
+        stamp & (self.one_lap - 1)
+    }
+    /// 下一个位置：下标加一，到达capacity时进入下一圈
+    fn next(&self, stamp: usize) -> usize {
+        if self.index(stamp) + 1 < self.capacity() {
+            stamp.wrapping_add(1)
+        } else {
+            (stamp & !(self.one_lap - 1)).wrapping_add(self.one_lap)
+        }
+    }
+    /// 元素数量，并发修改时只是一个瞬时的结果
+    pub fn len(&self) -> usize {
+        loop {
+            let enqueue = self.enqueue_pos.0.load(Ordering::SeqCst);
+            let dequeue = self.dequeue_pos.0.load(Ordering::SeqCst);
+            // 两次读取之间入队位置没有变化，得到的是一致的快照
+            if self.enqueue_pos.0.load(Ordering::SeqCst) != enqueue {
+                continue;
+            }
+            let (tail, head) = (self.index(enqueue), self.index(dequeue));
+            return if head < tail {
+                tail - head
+            } else if head > tail {
+                self.capacity() - head + tail
+            } else if enqueue == dequeue {
+                0
+            } else {
+                self.capacity()
+            };
+        }
+    }
+    /// 是否为空，并发修改时只是一个瞬时的结果
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len())
+    }
+    /// 是否已满，并发修改时只是一个瞬时的结果
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+    /// # 入队（非阻塞）
+    /// 队列已满时返回Err，把数据还给调用者
+    pub fn try_push(&self, data: T) -> Result<(), T> {
+        let mut pos = self.enqueue_pos.0.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[self.index(pos)];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos) as isize;
+            if diff == 0 {
+                match self.enqueue_pos.0.compare_exchange_weak(pos, self.next(pos), Ordering::Relaxed, Ordering::Relaxed) {
+                    Ok(_) => {
+                        slot.data.with_mut(|cell| unsafe { (*cell).write(data) });
+                        slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // 槽位中还是上一圈的数据：队列已满
+                return Err(data);
+            } else {
+                pos = self.enqueue_pos.0.load(Ordering::Relaxed);
+            }
+        }
+    }
+    /// # 入队（阻塞）
+    /// 队列已满时等待消费者腾出位置
+    pub fn push(&self, mut data: T) {
+        let mut backoff = Backoff::new();
+        while let Err(rejected) = self.try_push(data) {
+            data = rejected;
+            backoff.snooze();
+        }
+    }
+    /// # 出队（非阻塞）
+    /// 队列为空时返回None
+    pub fn try_pop(&self) -> Option<T> {
+        let mut pos = self.dequeue_pos.0.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[self.index(pos)];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos.wrapping_add(1)) as isize;
+            if diff == 0 {
+                match self.dequeue_pos.0.compare_exchange_weak(pos, self.next(pos), Ordering::Relaxed, Ordering::Relaxed) {
+                    Ok(_) => {
+                        let data = slot.data.with_mut(|cell| unsafe { (*cell).assume_init_read() });
+                        slot.seq.store(pos.wrapping_add(self.one_lap), Ordering::Release);
+                        return Some(data);
+                    }
+                    Err(current) => pos = current,
+                }
+            } else if diff < 0 {
+                // 槽位还没有写入位置pos的数据：队列为空
+                return None;
+            } else {
+                pos = self.dequeue_pos.0.load(Ordering::Relaxed);
+            }
+        }
+    }
+    /// # 出队（阻塞）
+    /// 队列为空时等待生产者写入
+    pub fn pop(&self) -> T {
+        let mut backoff = Backoff::new();
+        loop {
+            if let Some(data) = self.try_pop() {
+                return data;
+            }
+            backoff.snooze();
+        }
+    }
+}
+
+impl<T> Drop for BoundedMpmcQueue<T> {
+    fn drop(&mut self) {
+        while self.try_pop().is_some() {}
+    }
+}
+
+impl<T> Debug for BoundedMpmcQueue<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedMpmcQueue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_fifo() {
+        let queue = BoundedMpmcQueue::new(3);
+        assert!(queue.is_empty());
+        assert_eq!(Ok(()), queue.try_push(1));
+        assert_eq!(Ok(()), queue.try_push(2));
+        assert_eq!(Ok(()), queue.try_push(3));
+        assert!(queue.is_full());
+        assert_eq!(Err(4), queue.try_push(4));
+        assert_eq!(Some(1), queue.try_pop());
+        queue.push(4);
+        assert_eq!(vec![2, 3, 4], (0..3).map(|_| queue.pop()).collect::<Vec<_>>());
+        assert_eq!(None, queue.try_pop());
+        assert_eq!("BoundedMpmcQueue { len: 0, capacity: 3 }", format!("{:?}", queue));
+    }
+
+    #[test]
+    fn test_capacity_one() {
+        let queue = BoundedMpmcQueue::new(1);
+        for i in 0..5 {
+            assert_eq!(Ok(()), queue.try_push(i));
+            assert_eq!(Err(9), queue.try_push(9));
+            assert_eq!(Some(i), queue.try_pop());
+            assert_eq!(None, queue.try_pop());
+        }
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        let item = Arc::new(());
+        let queue = BoundedMpmcQueue::new(4);
+        for _ in 0..3 {
+            queue.push(Arc::clone(&item));
+        }
+        drop(queue);
+        assert_eq!(1, Arc::strong_count(&item));
+    }
+
+    #[test]
+    fn test_blocking() {
+        let queue = Arc::new(BoundedMpmcQueue::new(2));
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || (0..1000).for_each(|i| queue.push(i)))
+        };
+        let received: Vec<u32> = (0..1000).map(|_| queue.pop()).collect();
+        producer.join().unwrap();
+        assert_eq!((0..1000).collect::<Vec<_>>(), received);
+    }
+
+    #[test]
+    fn test_position_wraps() {
+        // 从最后一圈开始，位置回绕到0之后仍然保持先进先出
+        for capacity in [1_usize, 3, 4, 5] {
+            let one_lap = (capacity + 1).next_power_of_two();
+            let queue = BoundedMpmcQueue::starting_at(capacity, usize::MAX / one_lap);
+            let mut expected = std::collections::VecDeque::new();
+            for i in 0..10 * capacity {
+                while queue.try_push(i).is_ok() {
+                    expected.push_back(i);
+                }
+                assert!(queue.is_full());
+                assert_eq!(expected.len(), queue.len());
+                assert_eq!(expected.pop_front(), queue.try_pop());
+            }
+            assert!(expected.into_iter().eq(std::iter::from_fn(|| queue.try_pop())));
+            assert!(queue.is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn test_zero_capacity() {
+        BoundedMpmcQueue::<u8>::new(0);
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn loom_two_producers() {
+        loom::model(|| {
+            let queue = Arc::new(BoundedMpmcQueue::new(1));
+            let producers: Vec<_> = (0..2)
+                .map(|i| {
+                    let queue = Arc::clone(&queue);
+                    thread::spawn(move || queue.try_push(i).is_ok())
+                })
+                .collect();
+            let pushed = producers.into_iter().map(|h| h.join().unwrap()).filter(|&ok| ok).count();
+            // 容量为1，两个生产者中恰好一个成功
+            assert_eq!(1, pushed);
+            assert!(queue.try_pop().is_some());
+            assert_eq!(None, queue.try_pop());
+        });
+    }
+
+    #[test]
+    fn loom_producer_consumer() {
+        loom::model(|| {
+            let queue = Arc::new(BoundedMpmcQueue::new(2));
+            let producer = {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    queue.try_push(1).unwrap();
+                    queue.try_push(2).unwrap();
+                })
+            };
+            let consumer = {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let first = queue.try_pop();
+                    let second = queue.try_pop();
+                    // 出队顺序与入队顺序一致
+                    if let (Some(a), Some(b)) = (first, second) {
+                        assert_eq!((1, 2), (a, b));
+                    }
+                    first.is_some() as usize + second.is_some() as usize
+                })
+            };
+            producer.join().unwrap();
+            let taken = consumer.join().unwrap();
+            let rest = std::iter::from_fn(|| queue.try_pop()).count();
+            assert_eq!(2, taken + rest);
+        });
+    }
+}
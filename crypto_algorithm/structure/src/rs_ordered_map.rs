@@ -0,0 +1,653 @@
+//! # 有序映射（AVL树）
+//! 平衡二叉搜索树：任意节点左右子树的高度差不超过1，查找、插入、删除都是O(logn)
+//! ## 结构
+//! ```code
+//!               (8, h3)
+//!             ↙         ↘
+//!        (4, h2)       (10, h1)
+//!       ↙      ↘
+//!   (2, h1)   (6, h1)
+//!
+//!  each node structure
+//!   |——————————————————————————————————————————————|
+//!   | key | value | height | left ptr | right ptr  |
+//!   |——————————————————————————————————————————————|
+//! ```
+//! ## 旋转
+//! 插入或删除后沿路径自底向上检查平衡因子（左高 - 右高）：
+//! 1. LL（平衡因子 > 1，左子树的平衡因子 >= 0）：右旋
+//! 2. LR（平衡因子 > 1，左子树的平衡因子 < 0）：左子树左旋后再右旋
+//! 3. RR（平衡因子 < -1，右子树的平衡因子 <= 0）：左旋
+//! 4. RL（平衡因子 < -1，右子树的平衡因子 > 0）：右子树右旋后再左旋
+//! ## 常用方法
+//! 1. insert / get / get_mut / remove:增查改删
+//! 2. first / last:最小、最大的键
+//! 3. floor / ceiling:不大于、不小于某个键的最近键
+//! 4. range:范围查询（中序）
+//! 5. iter / keys / values:中序遍历（按键升序）
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/8/10
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Bound, RangeBounds};
+
+type Tree<K, V> = Option<Box<Node<K, V>>>;
+
+/// # 树节点
+/// height为以该节点为根的子树高度，叶子节点为1
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    left: Tree<K, V>,
+    right: Tree<K, V>,
+}
+
+fn height<K, V>(tree: &Tree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.height)
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+    fn update_height(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+    }
+    /// 平衡因子：左子树高度 - 右子树高度
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+    /// # 右旋
+    /// ```code
+    ///       y            x
+    ///      / \\          / \\
+    ///     x   c  →     a   y
+    ///    / \\              / \\
+    ///   a   b            b   c
+    /// ```
+    fn rotate_right(mut self: Box<Self>) -> Box<Self> {
+        let mut x = self.left.take().expect("rotate right requires a left child");
+        self.left = x.right.take();
+        self.update_height();
+        x.right = Some(self);
+        x.update_height();
+        x
+    }
+    /// # 左旋
+    /// 右旋的镜像
+    fn rotate_left(mut self: Box<Self>) -> Box<Self> {
+        let mut y = self.right.take().expect("rotate left requires a right child");
+        self.right = y.left.take();
+        self.update_height();
+        y.left = Some(self);
+        y.update_height();
+        y
+    }
+    /// # 重新平衡
+    /// 更新高度后根据平衡因子选择LL、LR、RR、RL四种旋转
+    fn rebalance(mut self: Box<Self>) -> Box<Self> {
+        self.update_height();
+        let factor = self.balance_factor();
+        if factor > 1 {
+            if self.left.as_ref().unwrap().balance_factor() < 0 {
+                self.left = Some(self.left.take().unwrap().rotate_left());
+            }
+            self.rotate_right()
+        } else if factor < -1 {
+            if self.right.as_ref().unwrap().balance_factor() > 0 {
+                self.right = Some(self.right.take().unwrap().rotate_right());
+            }
+            self.rotate_left()
+        } else {
+            self
+        }
+    }
+}
+
+/// # 有序映射
+pub struct OrderedMap<K, V> {
+    root: Tree<K, V>,
+    len: usize,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        OrderedMap { root: None, len: 0 }
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 清空
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+    /// 最小的键值对：一直向左
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+    /// 最大的键值对：一直向右
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+    /// 按键升序遍历
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            stack: Vec::new(),
+            len: self.len,
+        };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+    /// 按升序遍历所有键
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+    /// 按键的升序遍历所有值
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> {
+    /// # 插入
+    /// 键已存在时替换值并返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (root, old) = Self::insert_node(self.root.take(), key, value);
+        self.root = Some(root);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    /// 递归插入，回溯时重新平衡
+    fn insert_node(tree: Tree<K, V>, key: K, value: V) -> (Box<Node<K, V>>, Option<V>) {
+        let Some(mut node) = tree else {
+            return (Node::new(key, value), None);
+        };
+        let old = match key.cmp(&node.key) {
+            Ordering::Less => {
+                let (left, old) = Self::insert_node(node.left.take(), key, value);
+                node.left = Some(left);
+                old
+            }
+            Ordering::Greater => {
+                let (right, old) = Self::insert_node(node.right.take(), key, value);
+                node.right = Some(right);
+                old
+            }
+            Ordering::Equal => {
+                let old = std::mem::replace(&mut node.value, value);
+                return (node, Some(old));
+            }
+        };
+        (node.rebalance(), old)
+    }
+    fn find<Q>(&self, key: &Q) -> Option<&Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tree = self.root.as_deref();
+        while let Some(node) = tree {
+            tree = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(node),
+            };
+        }
+        None
+    }
+    /// 查询
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|node| &node.value)
+    }
+    /// 查询（可变）
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tree = self.root.as_deref_mut();
+        while let Some(node) = tree {
+            tree = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+    /// 是否包含某个键
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+    /// # 删除
+    /// 返回被删除的值
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+    /// 删除，返回被删除的键值对
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (root, removed) = Self::remove_node(self.root.take(), key);
+        self.root = root;
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+    /// # 递归删除
+    /// 1. 没有子节点或只有一个子节点：用子节点替代
+    /// 2. 有两个子节点：用右子树的最小节点替代
+    /// 3. 回溯时重新平衡
+    fn remove_node<Q>(tree: Tree<K, V>, key: &Q) -> (Tree<K, V>, Option<(K, V)>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let Some(mut node) = tree else {
+            return (None, None);
+        };
+        let removed = match key.cmp(node.key.borrow()) {
+            Ordering::Less => {
+                let (left, removed) = Self::remove_node(node.left.take(), key);
+                node.left = left;
+                removed
+            }
+            Ordering::Greater => {
+                let (right, removed) = Self::remove_node(node.right.take(), key);
+                node.right = right;
+                removed
+            }
+            Ordering::Equal => {
+                let Node { key, value, left, right, .. } = *node;
+                let replacement = match (left, right) {
+                    (None, right) => right,
+                    (left, None) => left,
+                    (left, Some(right)) => {
+                        let (right, mut min) = Self::remove_min(right);
+                        min.left = left;
+                        min.right = right;
+                        Some(min.rebalance())
+                    }
+                };
+                return (replacement, Some((key, value)));
+            }
+        };
+        (Some(node.rebalance()), removed)
+    }
+    /// 摘下子树中的最小节点，返回剩余的子树和该节点
+    fn remove_min(mut node: Box<Node<K, V>>) -> (Tree<K, V>, Box<Node<K, V>>) {
+        match node.left.take() {
+            None => (node.right.take(), node),
+            Some(left) => {
+                let (left, min) = Self::remove_min(left);
+                node.left = left;
+                (Some(node.rebalance()), min)
+            }
+        }
+    }
+    /// # 不大于key的最大键
+    /// 向右走时记录候选
+    pub fn floor<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tree = self.root.as_deref();
+        let mut candidate = None;
+        while let Some(node) = tree {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => tree = node.left.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    candidate = Some((&node.key, &node.value));
+                    tree = node.right.as_deref();
+                }
+            }
+        }
+        candidate
+    }
+    /// # 不小于key的最小键
+    /// 向左走时记录候选
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tree = self.root.as_deref();
+        let mut candidate = None;
+        while let Some(node) = tree {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Greater => tree = node.right.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Less => {
+                    candidate = Some((&node.key, &node.value));
+                    tree = node.left.as_deref();
+                }
+            }
+        }
+        candidate
+    }
+    /// # 范围查询
+    /// 按键升序返回落在range内的键值对
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, Q, R>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut stack = Vec::new();
+        // 只压入不小于下界的节点，小于下界的整棵左子树都可以跳过
+        let mut tree = self.root.as_deref();
+        while let Some(node) = tree {
+            let above_start = match range.start_bound() {
+                Bound::Included(start) => node.key.borrow() >= start,
+                Bound::Excluded(start) => node.key.borrow() > start,
+                Bound::Unbounded => true,
+            };
+            if above_start {
+                stack.push(node);
+                tree = node.left.as_deref();
+            } else {
+                tree = node.right.as_deref();
+            }
+        }
+        Range {
+            iter: Iter { stack, len: self.len },
+            range,
+            _marker: std::marker::PhantomData,
+        }
+    }
+    /// # 检查AVL不变式
+    /// 1. 中序遍历严格升序
+    /// 2. 每个节点记录的高度正确，平衡因子在[-1, 1]之间
+    /// 3. 节点数量与len一致
+    ///
+    /// 只在debug构建和测试中存在，供测试在每次修改后调用
+    #[cfg(any(test, debug_assertions))]
+    pub fn check_invariants(&self) {
+        fn check<K: Ord, V>(tree: &Tree<K, V>, count: &mut usize) -> usize {
+            let Some(node) = tree else {
+                return 0;
+            };
+            *count += 1;
+            let left = check(&node.left, count);
+            let right = check(&node.right, count);
+            assert!(node.left.as_ref().is_none_or(|l| l.key < node.key), "left child out of order");
+            assert!(node.right.as_ref().is_none_or(|r| r.key > node.key), "right child out of order");
+            assert_eq!(1 + left.max(right), node.height, "stale height");
+            assert!(left.abs_diff(right) <= 1, "unbalanced node");
+            node.height
+        }
+        let mut count = 0;
+        check(&self.root, &mut count);
+        assert_eq!(self.len, count, "len does not match node count");
+        assert!(self.keys().zip(self.keys().skip(1)).all(|(a, b)| a < b), "keys out of order");
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        OrderedMap::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for OrderedMap<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Clone for OrderedMap<K, V> {
+    fn clone(&self) -> Self {
+        self.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for OrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderedMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for OrderedMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+/// # 中序遍历迭代器
+/// 栈中保存尚未访问的祖先节点：弹出一个节点后，把它右子树的左链压栈
+pub struct Iter<'a, K: 'a, V: 'a> {
+    stack: Vec<&'a Node<K, V>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut tree: Option<&'a Node<K, V>>) {
+        while let Some(node) = tree {
+            self.stack.push(node);
+            tree = node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        self.len = self.len.saturating_sub(1);
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+/// # 范围迭代器
+/// 在中序遍历的基础上，遇到超过上界的键时结束
+pub struct Range<'a, K: 'a, V: 'a, Q: ?Sized, R> {
+    iter: Iter<'a, K, V>,
+    range: R,
+    _marker: std::marker::PhantomData<fn(&Q)>,
+}
+
+impl<'a, K, V, Q, R> Iterator for Range<'a, K, V, Q, R>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, v) = self.iter.next()?;
+        let below_end = match self.range.end_bound() {
+            Bound::Included(end) => k.borrow() <= end,
+            Bound::Excluded(end) => k.borrow() < end,
+            Bound::Unbounded => true,
+        };
+        if below_end {
+            Some((k, v))
+        } else {
+            self.iter.stack.clear();
+            None
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = OrderedMap::new();
+        assert_eq!(None, map.first());
+        for (i, k) in [50, 30, 70, 20, 40, 60, 80, 10].into_iter().enumerate() {
+            assert_eq!(None, map.insert(k, i));
+            map.check_invariants();
+        }
+        assert_eq!(Some(0), map.insert(50, 100));
+        assert_eq!(8, map.len());
+        assert_eq!(Some(&100), map.get(&50));
+        *map.get_mut(&10).unwrap() += 1;
+        assert_eq!(Some((&10, &8)), map.first());
+        assert_eq!(Some((&80, &6)), map.last());
+        assert_eq!(Some(100), map.remove(&50));
+        map.check_invariants();
+        assert_eq!(None, map.remove(&50));
+        assert!(!map.contains_key(&50));
+        assert_eq!(vec![10, 20, 30, 40, 60, 70, 80], map.keys().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rotations() {
+        // 升序插入会持续触发RR旋转，树高保持在O(logn)
+        let mut map = OrderedMap::new();
+        for i in 0..1024 {
+            map.insert(i, ());
+        }
+        map.check_invariants();
+        assert_eq!(11, height(&map.root));
+        // LR和RL
+        let lr: OrderedMap<u8, ()> = [30, 10, 20].into_iter().map(|k| (k, ())).collect();
+        assert_eq!(20, lr.root.as_ref().unwrap().key);
+        let rl: OrderedMap<u8, ()> = [10, 30, 20].into_iter().map(|k| (k, ())).collect();
+        assert_eq!(20, rl.root.as_ref().unwrap().key);
+        for i in (0..1024).step_by(3) {
+            map.remove(&i);
+        }
+        map.check_invariants();
+    }
+
+    #[test]
+    fn test_floor_ceiling_range() {
+        let map: OrderedMap<u32, char> = [(10, 'a'), (20, 'b'), (30, 'c'), (40, 'd')].into_iter().collect();
+        assert_eq!(Some((&20, &'b')), map.floor(&25));
+        assert_eq!(Some((&20, &'b')), map.floor(&20));
+        assert_eq!(None, map.floor(&5));
+        assert_eq!(Some((&30, &'c')), map.ceiling(&25));
+        assert_eq!(None, map.ceiling(&41));
+        assert_eq!(vec![20, 30], map.range(15..35).map(|(k, _)| *k).collect::<Vec<_>>());
+        assert_eq!(vec![20, 30, 40], map.range(20..=40).map(|(k, _)| *k).collect::<Vec<_>>());
+        assert_eq!(vec![10], map.range(..20).map(|(k, _)| *k).collect::<Vec<_>>());
+        assert_eq!(4, map.range(..).count());
+        assert_eq!(0, map.range(41..).count());
+        assert_eq!("{10: 'a', 20: 'b', 30: 'c', 40: 'd'}", format!("{:?}", map));
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let mut map: OrderedMap<String, usize> = ["pear", "apple", "fig"].iter().map(|s| (s.to_string(), s.len())).collect();
+        assert_eq!(Some(&5), map.get("apple"));
+        assert_eq!(Some((&"fig".to_string(), &3)), map.ceiling("b"));
+        assert_eq!(vec!["fig", "pear"], map.range::<str, _>((Bound::Included("c"), Bound::Unbounded)).map(|(k, _)| k.as_str()).collect::<Vec<_>>());
+        assert_eq!(Some(4), map.remove("pear"));
+        assert_eq!(map.clone(), map);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(u8, u16),
+        Remove(u8),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (any::<u8>(), any::<u16>()).prop_map(|(k, v)| Op::Insert(k, v)),
+            any::<u8>().prop_map(Op::Remove),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_btree_map(ops in prop::collection::vec(op(), 0..300), probe in any::<u8>(), a in any::<u8>(), b in any::<u8>()) {
+            let mut map = OrderedMap::new();
+            let mut model = BTreeMap::new();
+            for op in ops {
+                match op {
+                    Op::Insert(k, v) => prop_assert_eq!(model.insert(k, v), map.insert(k, v)),
+                    Op::Remove(k) => prop_assert_eq!(model.remove(&k), map.remove(&k)),
+                }
+                map.check_invariants();
+            }
+            prop_assert!(model.iter().eq(map.iter()));
+            prop_assert_eq!(model.first_key_value(), map.first());
+            prop_assert_eq!(model.last_key_value(), map.last());
+            prop_assert_eq!(model.range(..=probe).next_back(), map.floor(&probe));
+            prop_assert_eq!(model.range(probe..).next(), map.ceiling(&probe));
+            let (lo, hi) = (a.min(b), a.max(b));
+            prop_assert!(model.range(lo..hi).eq(map.range(lo..hi)));
+            prop_assert!(model.range(lo..=hi).eq(map.range(lo..=hi)));
+            prop_assert!(model.range((Bound::Excluded(lo), Bound::Unbounded)).eq(map.range((Bound::Excluded(lo), Bound::Unbounded))));
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! # 持久化链表（Persistent List）
+//! 不可变的单向链表（cons list），修改操作返回新版本，旧版本保持不变并与新版本共享节点
+//! ## 结构
+//! ```code
+//!   v1 = [2, 1]            v1 ——> (2) ——> (1) ——> None
+//!   v2 = v1.push(3)                 ↑
+//!   v3 = v1.push(4)        v2 ——> (3)
+//!                          v3 ——> (4) ——┘ (与v2共享(2)、(1))
+//! ```
+//! 节点通过`Arc`共享，可以在线程间传递；没有版本引用的节点会被自动释放
+//! ## 常用方法
+//! 1. push:在头部添加元素，返回新版本 O(1)
+//! 2. pop:去掉头部元素，返回新版本 O(1)
+//! 3. set:修改第i个元素，复制前i个节点，返回新版本 O(i)
+//! 4. first / get / iter:查询
+//! 5. clone:只复制头指针 O(1)
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/24
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+type Link<T> = Option<Arc<Node<T>>>;
+
+struct Node<T> {
+    data: T,
+    next: Link<T>,
+}
+
+/// # 持久化链表
+pub struct PersistentList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> PersistentList<T> {
+    pub fn new() -> Self {
+        PersistentList { head: None, len: 0 }
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 头部元素
+    pub fn first(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.data)
+    }
+    /// 第index个元素 O(index)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+    /// # 头部添加
+    /// 新节点指向当前头节点，当前版本不受影响
+    pub fn push(&self, data: T) -> Self {
+        PersistentList {
+            head: Some(Arc::new(Node {
+                data,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+    /// # 去掉头部
+    /// 返回从第二个节点开始的版本，空链表返回None
+    pub fn pop(&self) -> Option<Self> {
+        self.head.as_ref().map(|node| PersistentList {
+            head: node.next.clone(),
+            len: self.len - 1,
+        })
+    }
+    /// 从头到尾遍历
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            len: self.len,
+        }
+    }
+    /// 两个版本是否共享同一个头节点
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Clone> PersistentList<T> {
+    /// # 修改
+    /// 复制前index个节点，第index个节点之后的部分与当前版本共享；越界时返回None
+    pub fn set(&self, index: usize, data: T) -> Option<Self> {
+        if index >= self.len {
+            return None;
+        }
+        let mut prefix: Vec<&T> = Vec::with_capacity(index);
+        let mut node = self.head.as_ref().unwrap();
+        for _ in 0..index {
+            prefix.push(&node.data);
+            node = node.next.as_ref().unwrap();
+        }
+        let mut list = PersistentList {
+            head: node.next.clone(),
+            len: self.len - index - 1,
+        }
+        .push(data);
+        for data in prefix.into_iter().rev() {
+            list = list.push(data.clone());
+        }
+        Some(list)
+    }
+    /// 反转，返回新版本
+    pub fn reverse(&self) -> Self {
+        self.iter().fold(PersistentList::new(), |list, data| list.push(data.clone()))
+    }
+}
+
+impl<T> Clone for PersistentList<T> {
+    /// 只复制头指针，不要求`T: Clone`
+    fn clone(&self) -> Self {
+        PersistentList {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Drop for PersistentList<T> {
+    /// 迭代释放不再被共享的节点，避免长链表递归drop导致栈溢出
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            match Arc::try_unwrap(node) {
+                Ok(mut node) => link = node.next.take(),
+                // 节点仍被其他版本引用，后面的部分也由它负责
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        PersistentList::new()
+    }
+}
+
+impl<T: Debug> Debug for PersistentList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for PersistentList<T> {}
+
+impl<T> FromIterator<T> for PersistentList<T> {
+    /// 保持迭代顺序：第一个元素在头部
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let items: Vec<T> = iter.into_iter().collect();
+        items.into_iter().rev().fold(PersistentList::new(), |list, data| list.push(data))
+    }
+}
+
+/// # 迭代器
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.len -= 1;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_versions() {
+        let v1 = PersistentList::new().push(1).push(2);
+        let v2 = v1.push(3);
+        let v3 = v1.push(4);
+        assert_eq!(vec![&2, &1], v1.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&3, &2, &1], v2.iter().collect::<Vec<_>>());
+        assert_eq!(vec![&4, &2, &1], v3.iter().collect::<Vec<_>>());
+        assert!(v2.pop().unwrap().ptr_eq(&v1));
+        assert_eq!(Some(&4), v3.first());
+        assert_eq!(Some(&1), v3.get(2));
+        assert_eq!(None, PersistentList::<u8>::new().pop());
+    }
+
+    #[test]
+    fn test_set_shares_suffix() {
+        let v1: PersistentList<u32> = (0..5).collect();
+        let v2 = v1.set(2, 20).unwrap();
+        assert_eq!("[0, 1, 2, 3, 4]", format!("{:?}", v1));
+        assert_eq!("[0, 1, 20, 3, 4]", format!("{:?}", v2));
+        let suffix = |list: &PersistentList<u32>| list.pop().unwrap().pop().unwrap().pop().unwrap();
+        assert!(suffix(&v1).ptr_eq(&suffix(&v2)));
+        assert_eq!(None, v1.set(5, 0));
+        assert_eq!((0..5).rev().collect::<PersistentList<_>>(), v1.reverse());
+    }
+
+    #[test]
+    fn test_drop() {
+        let item = Arc::new(());
+        let shared: PersistentList<_> = (0..3).map(|_| Arc::clone(&item)).collect();
+        let branch = shared.push(Arc::clone(&item));
+        drop(shared);
+        assert_eq!(5, Arc::strong_count(&item));
+        drop(branch);
+        assert_eq!(1, Arc::strong_count(&item));
+        // 长链表迭代释放，Miri下缩短
+        let n = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let long: PersistentList<u32> = (0..n).collect();
+        drop(long);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(usize, u8),
+        Pop(usize),
+        Set(usize, usize, u8),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (any::<usize>(), any::<u8>()).prop_map(|(v, x)| Op::Push(v, x)),
+            any::<usize>().prop_map(Op::Pop),
+            (any::<usize>(), 0_usize..8, any::<u8>()).prop_map(|(v, i, x)| Op::Set(v, i, x)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_old_versions_unchanged(ops in prop::collection::vec(op(), 0..100)) {
+            // 每个版本都与一个Vec模型对应（头部对应Vec的第0个元素）
+            let mut versions = vec![(PersistentList::new(), Vec::new())];
+            for op in ops {
+                let next = match op {
+                    Op::Push(v, x) => {
+                        let (list, model) = &versions[v % versions.len()];
+                        let mut model = model.clone();
+                        model.insert(0, x);
+                        Some((list.push(x), model))
+                    }
+                    Op::Pop(v) => {
+                        let (list, model) = &versions[v % versions.len()];
+                        prop_assert_eq!(model.is_empty(), list.pop().is_none());
+                        list.pop().map(|list| (list, model[1..].to_vec()))
+                    }
+                    Op::Set(v, i, x) => {
+                        let (list, model) = &versions[v % versions.len()];
+                        prop_assert_eq!(i >= model.len(), list.set(i, x).is_none());
+                        list.set(i, x).map(|list| {
+                            let mut model = model.clone();
+                            model[i] = x;
+                            (list, model)
+                        })
+                    }
+                };
+                versions.extend(next);
+                for (list, model) in &versions {
+                    prop_assert_eq!(model.len(), list.len());
+                    prop_assert!(model.iter().eq(list.iter()));
+                }
+            }
+        }
+    }
+}
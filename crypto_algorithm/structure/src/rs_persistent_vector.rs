@@ -0,0 +1,447 @@
+//! # 持久化向量（Persistent Vector）
+//! 32叉字典树（trie），修改时只复制从根到目标叶子的一条路径（路径复制），其余节点在新旧版本间共享
+//! ## 结构
+//! ```code
+//!                      root (shift = 5)
+//!              ↙          ↓          ↘
+//!        leaf[0..32]  leaf[32..64]  ...          tail: [1024..1030]
+//!
+//!  index的第(shift, shift-5, ..., 5)段5位依次选择每层的子节点，最低5位选择叶子中的元素
+//! ```
+//! ## 尾部优化
+//! 最后不满32个的元素单独存放在tail中，不进入树。push只有在tail满时才把整个tail作为叶子放入树，
+//! 因此大多数push和pop只需要复制tail，均摊接近O(1)
+//! ## 常用方法
+//! 1. push / pop:尾部添加、删除，返回新版本
+//! 2. set:修改第i个元素，返回新版本 O(log32 n)
+//! 3. get / iter:查询
+//! 4. clone:只复制根和tail的指针 O(1)
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/24
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::ops::Index;
+use std::sync::Arc;
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+enum Node<T> {
+    Branch(Vec<Arc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+impl<T> Node<T> {
+    fn children(&self) -> &Vec<Arc<Node<T>>> {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => unreachable!("expected a branch node"),
+        }
+    }
+    fn items(&self) -> &Vec<T> {
+        match self {
+            Node::Leaf(items) => items,
+            Node::Branch(_) => unreachable!("expected a leaf node"),
+        }
+    }
+}
+
+/// # 持久化向量
+/// shift为根节点所在层的位移，叶子层为0
+pub struct PersistentVector<T> {
+    len: usize,
+    shift: usize,
+    root: Arc<Node<T>>,
+    tail: Arc<Vec<T>>,
+}
+
+impl<T> PersistentVector<T> {
+    pub fn new() -> Self {
+        PersistentVector {
+            len: 0,
+            shift: BITS,
+            root: Arc::new(Node::Branch(Vec::new())),
+            tail: Arc::new(Vec::new()),
+        }
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 树中元素的数量，之后的元素都在tail中
+    fn tail_offset(&self) -> usize {
+        self.len - self.tail.len()
+    }
+    /// 包含index的叶子（或tail）
+    fn leaf_for(&self, index: usize) -> &Vec<T> {
+        if index >= self.tail_offset() {
+            return &self.tail;
+        }
+        let mut node = &self.root;
+        let mut level = self.shift;
+        while level > 0 {
+            node = &node.children()[(index >> level) & MASK];
+            level -= BITS;
+        }
+        node.items()
+    }
+    /// 查询
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        Some(&self.leaf_for(index)[index & MASK])
+    }
+    /// 最后一个元素
+    pub fn last(&self) -> Option<&T> {
+        self.tail.last()
+    }
+    /// 按下标顺序遍历，每次取出一整个叶子
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vector: self,
+            index: 0,
+            leaf: [].iter(),
+        }
+    }
+}
+
+impl<T: Clone> PersistentVector<T> {
+    /// # 尾部添加
+    /// 1. tail未满：复制tail并添加
+    /// 2. tail已满：把tail作为叶子放入树中，新元素成为新的tail；根节点已满时树长高一层
+    pub fn push(&self, data: T) -> Self {
+        if self.tail.len() < WIDTH {
+            let mut tail = Vec::with_capacity(self.tail.len() + 1);
+            tail.extend_from_slice(&self.tail);
+            tail.push(data);
+            return PersistentVector {
+                len: self.len + 1,
+                shift: self.shift,
+                root: Arc::clone(&self.root),
+                tail: Arc::new(tail),
+            };
+        }
+        let leaf = Arc::new(Node::Leaf((*self.tail).clone()));
+        let tree_len = self.tail_offset();
+        let (root, shift) = if (tree_len >> BITS) >= (1 << self.shift) {
+            // 根节点的子节点已满
+            let path = Self::new_path(self.shift, leaf);
+            (Arc::new(Node::Branch(vec![Arc::clone(&self.root), path])), self.shift + BITS)
+        } else {
+            (Self::push_leaf(self.shift, &self.root, tree_len, leaf), self.shift)
+        };
+        PersistentVector {
+            len: self.len + 1,
+            shift,
+            root,
+            tail: Arc::new(vec![data]),
+        }
+    }
+    /// 从level层向下创建一条只有一个分支的路径，末端是leaf
+    fn new_path(level: usize, leaf: Arc<Node<T>>) -> Arc<Node<T>> {
+        (0..level / BITS).fold(leaf, |node, _| Arc::new(Node::Branch(vec![node])))
+    }
+    /// 把leaf放到树中下标tree_len所在的位置，复制沿途的节点
+    fn push_leaf(level: usize, node: &Arc<Node<T>>, tree_len: usize, leaf: Arc<Node<T>>) -> Arc<Node<T>> {
+        let mut children = node.children().clone();
+        let sub = (tree_len >> level) & MASK;
+        let child = if level == BITS {
+            leaf
+        } else if let Some(child) = children.get(sub) {
+            Self::push_leaf(level - BITS, child, tree_len, leaf)
+        } else {
+            Self::new_path(level - BITS, leaf)
+        };
+        if sub < children.len() {
+            children[sub] = child;
+        } else {
+            children.push(child);
+        }
+        Arc::new(Node::Branch(children))
+    }
+    /// # 修改
+    /// 复制从根到目标叶子的路径；越界时返回None
+    pub fn set(&self, index: usize, data: T) -> Option<Self> {
+        if index >= self.len {
+            return None;
+        }
+        let mut vector = self.clone();
+        if index >= self.tail_offset() {
+            let mut tail = (*self.tail).clone();
+            tail[index & MASK] = data;
+            vector.tail = Arc::new(tail);
+        } else {
+            vector.root = Self::set_in(self.shift, &self.root, index, data);
+        }
+        Some(vector)
+    }
+    fn set_in(level: usize, node: &Arc<Node<T>>, index: usize, data: T) -> Arc<Node<T>> {
+        if level == 0 {
+            let mut items = node.items().clone();
+            items[index & MASK] = data;
+            return Arc::new(Node::Leaf(items));
+        }
+        let mut children = node.children().clone();
+        let sub = (index >> level) & MASK;
+        children[sub] = Self::set_in(level - BITS, &children[sub], index, data);
+        Arc::new(Node::Branch(children))
+    }
+    /// # 尾部删除
+    /// 1. tail中不止一个元素：复制tail并删除
+    /// 2. tail只剩一个元素：把树中最后一个叶子取出作为新的tail；根节点只剩一个子节点时树降低一层
+    ///
+    /// 空向量返回None
+    pub fn pop(&self) -> Option<Self> {
+        match self.len {
+            0 => return None,
+            1 => return Some(PersistentVector::new()),
+            _ => {}
+        }
+        if self.tail.len() > 1 {
+            let mut vector = self.clone();
+            vector.tail = Arc::new(self.tail[..self.tail.len() - 1].to_vec());
+            vector.len -= 1;
+            return Some(vector);
+        }
+        let tail = Arc::new(self.leaf_for(self.len - 2).clone());
+        let mut root = Self::pop_leaf(self.shift, &self.root, self.len - 2).unwrap_or_else(|| Arc::new(Node::Branch(Vec::new())));
+        let mut shift = self.shift;
+        if shift > BITS && root.children().len() == 1 {
+            root = Arc::clone(&root.children()[0]);
+            shift -= BITS;
+        }
+        Some(PersistentVector {
+            len: self.len - 1,
+            shift,
+            root,
+            tail,
+        })
+    }
+    /// 删除包含last的最后一个叶子，节点变空时返回None
+    fn pop_leaf(level: usize, node: &Arc<Node<T>>, last: usize) -> Option<Arc<Node<T>>> {
+        let sub = (last >> level) & MASK;
+        let mut children = node.children().clone();
+        if level > BITS {
+            match Self::pop_leaf(level - BITS, &children[sub], last) {
+                Some(child) => children[sub] = child,
+                None => {
+                    children.truncate(sub);
+                }
+            }
+        } else {
+            children.truncate(sub);
+        }
+        if children.is_empty() {
+            None
+        } else {
+            Some(Arc::new(Node::Branch(children)))
+        }
+    }
+}
+
+impl<T> Clone for PersistentVector<T> {
+    /// 只复制根和tail的指针，不要求`T: Clone`
+    fn clone(&self) -> Self {
+        PersistentVector {
+            len: self.len,
+            shift: self.shift,
+            root: Arc::clone(&self.root),
+            tail: Arc::clone(&self.tail),
+        }
+    }
+}
+
+impl<T> Default for PersistentVector<T> {
+    fn default() -> Self {
+        PersistentVector::new()
+    }
+}
+
+impl<T: Debug> Debug for PersistentVector<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentVector<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for PersistentVector<T> {}
+
+impl<T> Index<usize> for PersistentVector<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {index} out of bounds for length {}", self.len))
+    }
+}
+
+impl<T: Clone> FromIterator<T> for PersistentVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().fold(PersistentVector::new(), |vector, data| vector.push(data))
+    }
+}
+
+/// # 迭代器
+/// 每进入一个新的叶子只需要一次自顶向下的查找
+pub struct Iter<'a, T> {
+    vector: &'a PersistentVector<T>,
+    index: usize,
+    leaf: std::slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(data) = self.leaf.next() {
+            return Some(data);
+        }
+        if self.index >= self.vector.len {
+            return None;
+        }
+        self.leaf = self.vector.leaf_for(self.index).iter();
+        self.index += WIDTH;
+        self.leaf.next()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentVector<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_push_get_pop() {
+        let mut vector = PersistentVector::new();
+        // 超过32 * 32 + 32个元素，树至少有三层
+        for i in 0..2000 {
+            vector = vector.push(i);
+        }
+        assert_eq!(2000, vector.len());
+        assert_eq!(10, vector.shift);
+        assert!((0..2000).all(|i| vector[i] == i));
+        assert_eq!(None, vector.get(2000));
+        assert_eq!(Some(&1999), vector.last());
+        assert!((0..2000).eq(vector.iter().copied()));
+        for i in (0..2000).rev() {
+            assert_eq!(Some(&i), vector.last());
+            vector = vector.pop().unwrap();
+            assert_eq!(i, vector.len());
+        }
+        assert_eq!(BITS, vector.shift);
+        assert_eq!(None, vector.pop());
+    }
+
+    #[test]
+    fn test_versions() {
+        let v1: PersistentVector<u32> = (0..100).collect();
+        let v2 = v1.set(5, 500).unwrap();
+        let v3 = v1.push(100);
+        let v4 = v1.pop().unwrap();
+        assert_eq!(5, v1[5]);
+        assert_eq!(500, v2[5]);
+        assert_eq!(101, v3.len());
+        assert_eq!(99, v4.len());
+        assert!((0..100).eq(v1.iter().copied()));
+        // 修改树中元素不会复制tail，修改tail不会复制树
+        assert!(Arc::ptr_eq(&v1.tail, &v2.tail));
+        assert!(Arc::ptr_eq(&v1.root, &v1.set(99, 0).unwrap().root));
+        assert_eq!(None, v1.set(100, 0));
+        assert_eq!("[1, 2]", format!("{:?}", PersistentVector::new().push(1).push(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_out_of_bounds() {
+        let _ = PersistentVector::<u8>::new()[0];
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(usize, u16),
+        Extend(usize, usize),
+        Pop(usize),
+        Set(usize, usize, u16),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (any::<usize>(), any::<u16>()).prop_map(|(v, x)| Op::Push(v, x)),
+            (any::<usize>(), 0_usize..1200).prop_map(|(v, n)| Op::Extend(v, n)),
+            any::<usize>().prop_map(Op::Pop),
+            (any::<usize>(), any::<usize>(), any::<u16>()).prop_map(|(v, i, x)| Op::Set(v, i, x)),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        #[test]
+        fn prop_old_versions_unchanged(ops in prop::collection::vec(op(), 0..40)) {
+            let mut versions = vec![(PersistentVector::new(), Vec::new())];
+            for op in ops {
+                let next = match op {
+                    Op::Push(v, x) => {
+                        let (vector, model) = &versions[v % versions.len()];
+                        let mut model = model.clone();
+                        model.push(x);
+                        Some((vector.push(x), model))
+                    }
+                    Op::Extend(v, n) => {
+                        let (vector, model) = &versions[v % versions.len()];
+                        let (mut vector, mut model) = (vector.clone(), model.clone());
+                        for x in 0..n as u16 {
+                            vector = vector.push(x);
+                            model.push(x);
+                        }
+                        Some((vector, model))
+                    }
+                    Op::Pop(v) => {
+                        let (vector, model) = &versions[v % versions.len()];
+                        prop_assert_eq!(model.is_empty(), vector.pop().is_none());
+                        vector.pop().map(|vector| (vector, model[..model.len() - 1].to_vec()))
+                    }
+                    Op::Set(v, i, x) => {
+                        let (vector, model) = &versions[v % versions.len()];
+                        let i = if model.is_empty() { i } else { i % model.len() };
+                        prop_assert_eq!(model.is_empty(), vector.set(i, x).is_none());
+                        vector.set(i, x).map(|vector| {
+                            let mut model = model.clone();
+                            model[i] = x;
+                            (vector, model)
+                        })
+                    }
+                };
+                versions.extend(next);
+            }
+            for (vector, model) in &versions {
+                prop_assert_eq!(model.len(), vector.len());
+                prop_assert!(model.iter().eq(vector.iter()));
+                prop_assert!(model.iter().enumerate().all(|(i, x)| vector.get(i) == Some(x)));
+            }
+        }
+    }
+}
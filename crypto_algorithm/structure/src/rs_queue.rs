@@ -7,12 +7,26 @@
 //!       ——————————————————————————————————
 //!       ↳              容量               ↲
 //! ```
+//! ## 循环队列
+//! 底层是固定容量的环形缓冲区，head指向队首，队尾位置为`(head + len) % capacity`，
+//! 入队和出队都只移动下标，时间复杂度O(1)。
+//! 槽位按需分配：最多预先分配`PREALLOC_LIMIT`个，队满但未达到容量时把环展开到开头并加倍，
+//! 所以很大的容量可以放心用作保护性的上限
+//! ```code
+//!            tail       head
+//!              ↓         ↓
+//!       | *6 | ?? | ?? | *1 | *2 | *3 | *4 | *5 |
+//! ```
 //! ## 方法
 //! 1. new:初始化队列
-//! 2. push:入队
-//! 3. pop:出队
-//! 4. is_empty:是否空队
-//! 5. len:队列长度
+//! 2. new_overwrite:初始化覆盖模式的队列（队满时覆盖最旧的元素，适合环形日志）
+//! 3. push / try_push / force_push:入队
+//! 4. pop:出队
+//! 5. peek / peek_back:查看队首/队尾
+//! 6. is_empty / is_full:是否空队/队满
+//! 7. len / capacity:队列长度/容量
+//! 8. iter:从队首到队尾遍历
+//! 9. clear:清空
 //! ## 场景
 //!
 //!1. 任务调度：队列可以用于任务调度和处理。当有多个任务需要执行时，可以将这些任务排列在队列中，按照先进先出的顺序逐个执行。
@@ -29,46 +43,287 @@
 //! @version:0.0.1
 //! @description:
 //! ```
-#[derive(Debug)]
-pub struct Queue<T>{
-    data:Vec<T>,
-    len:usize
+use std::fmt::{Debug, Formatter};
+
+/// 创建队列时最多预先分配的槽位数量
+const PREALLOC_LIMIT: usize = 1024;
+
+pub struct Queue<T> {
+    /// 已分配的槽位，环形缓冲区的长度
+    data: Vec<Option<T>>,
+    capacity: usize,
+    head: usize,
+    len: usize,
+    overwrite: bool,
 }
 
 impl<T> Queue<T> {
     /// # 创建一个空队列
-    /// 队列容量由调用者传入
-    /// 限定了队列长度，所以使用with_capacity
-    pub fn new(len:usize)->Self{
-        Queue{
-            data:Vec::with_capacity(len),
-            len
+    /// 队列容量由调用者传入，最多预先分配`PREALLOC_LIMIT`个槽位
+    pub fn new(len: usize) -> Self {
+        Queue {
+            data: (0..len.min(PREALLOC_LIMIT)).map(|_| None).collect(),
+            capacity: len,
+            head: 0,
+            len: 0,
+            overwrite: false,
+        }
+    }
+    /// # 创建一个覆盖模式的空队列
+    /// 队满时入队会挤掉最旧的元素（队首），可以作为环形日志使用
+    pub fn new_overwrite(len: usize) -> Self {
+        Queue {
+            overwrite: true,
+            ..Queue::new(len)
         }
     }
     /// # 队列长度
-    pub fn len(&self)->usize{
-        self.data.len()
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// # 队列容量
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
     /// # 是否空队
-    pub fn is_empty(&self)->bool{
+    pub fn is_empty(&self) -> bool {
         0_usize.eq(&self.len())
     }
+    /// # 是否队满
+    pub fn is_full(&self) -> bool {
+        self.len().eq(&self.capacity())
+    }
+    /// 是否为覆盖模式
+    pub fn is_overwrite(&self) -> bool {
+        self.overwrite
+    }
+    /// 第offset个元素在环形缓冲区中的下标
+    fn slot(&self, offset: usize) -> usize {
+        (self.head + offset) % self.data.len()
+    }
+    /// 槽位用完但未达到容量时，把环展开为从0开始，槽位数量加倍（不超过容量）
+    fn grow(&mut self) {
+        self.data.rotate_left(self.head);
+        self.head = 0;
+        let slots = self.data.len().saturating_mul(2).clamp(1, self.capacity);
+        self.data.resize_with(slots, || None);
+    }
     /// # 入队
-    /// 1. 判断队列长度是否==容量(队满)
-    /// 2. 入队
-    pub fn push(&mut self,el:T)->(){
-        if self.len().lt(&self.len) {
-            self.data.push(el);
+    /// 1. 判断队列长度是否==容量(队满)，队满时把元素原样返回
+    /// 2. 写入队尾
+    ///
+    /// 覆盖模式下队满时同样返回Err，需要覆盖请使用`force_push`或`push`
+    pub fn try_push(&mut self, el: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(el);
+        }
+        if self.len == self.data.len() {
+            self.grow();
+        }
+        let tail = self.slot(self.len);
+        self.data[tail] = Some(el);
+        self.len += 1;
+        Ok(())
+    }
+    /// # 强制入队
+    /// 队满时挤掉队首元素并返回它；容量为0时直接返回传入的元素
+    pub fn force_push(&mut self, el: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(el);
+        }
+        if self.is_full() {
+            let oldest = self.data[self.head].replace(el);
+            self.head = self.slot(1);
+            oldest
+        } else {
+            let _ = self.try_push(el);
+            None
+        }
+    }
+    /// # 入队
+    /// 覆盖模式下队满时挤掉最旧的元素
+    /// ## Panics
+    /// 非覆盖模式下队满时panic，需要处理该情况请使用`try_push`
+    pub fn push(&mut self, el: T) {
+        if self.overwrite {
+            let _ = self.force_push(el);
+        } else if self.try_push(el).is_err() {
+            panic!("queue is full")
         }
     }
     /// # 出队
     /// 1. 判断队列是否为空
-    /// 2. 出队(需要注意的是，出队是从队首出的)
-    pub fn pop(&mut self)->T{
+    /// 2. 出队(需要注意的是，出队是从队首出的)，head后移一位
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let el = self.data[self.head].take();
+        self.head = self.slot(1);
+        self.len -= 1;
+        el
+    }
+    /// # 查看队首元素
+    pub fn peek(&self) -> Option<&T> {
+        self.iter().next()
+    }
+    /// # 查看队尾元素
+    pub fn peek_back(&self) -> Option<&T> {
         if self.is_empty() {
-            panic!("empty")
-        }else {
-            self.data.remove(0)
+            None
+        } else {
+            self.data[self.slot(self.len - 1)].as_ref()
         }
     }
-}
\ No newline at end of file
+    /// # 清空队列
+    /// 保留容量
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+        self.head = 0;
+    }
+    /// # 从队首到队尾遍历
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            queue: self,
+            offset: 0,
+        }
+    }
+}
+
+impl<T: Debug> Debug for Queue<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Queue")
+            .field("data", &self.iter().collect::<Vec<_>>())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// 转为不可变引用迭代器（队首到队尾）
+pub struct Iter<'a, T: 'a> {
+    queue: &'a Queue<T>,
+    offset: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset < self.queue.len {
+            let el = self.queue.data[self.queue.slot(self.offset)].as_ref();
+            self.offset += 1;
+            el
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.queue.len - self.offset;
+        (rest, Some(rest))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_fifo() {
+        let mut queue = Queue::new(3);
+        assert!(queue.is_empty());
+        assert_eq!(Ok(()), queue.try_push(1));
+        assert_eq!(Ok(()), queue.try_push(2));
+        assert_eq!(Ok(()), queue.try_push(3));
+        assert!(queue.is_full());
+        assert_eq!(Err(4), queue.try_push(4));
+        assert_eq!(Some(&1), queue.peek());
+        assert_eq!(Some(&3), queue.peek_back());
+        assert_eq!(Some(1), queue.pop());
+        queue.push(4);
+        assert_eq!(vec![2, 3, 4], queue.iter().copied().collect::<Vec<_>>());
+        assert_eq!(Some(2), queue.pop());
+        assert_eq!(Some(3), queue.pop());
+        assert_eq!(Some(4), queue.pop());
+        assert_eq!(None, queue.pop());
+        assert_eq!(None, queue.peek_back());
+    }
+
+    #[test]
+    fn test_queue_wrap_around() {
+        let mut queue = Queue::new(1000);
+        let mut expected = 0;
+        for i in 0..50_000 {
+            queue.push(i);
+            if queue.is_full() {
+                for _ in 0..700 {
+                    assert_eq!(Some(expected), queue.pop());
+                    expected += 1;
+                }
+            }
+        }
+        assert_eq!(50_000 - expected, queue.len());
+        assert_eq!(Some(&expected), queue.peek());
+        assert_eq!(Some(&49_999), queue.peek_back());
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(1000, queue.capacity());
+    }
+
+    #[test]
+    #[should_panic(expected = "queue is full")]
+    fn test_queue_push_full() {
+        let mut queue = Queue::new(1);
+        queue.push("a");
+        queue.push("b");
+    }
+
+    #[test]
+    fn test_queue_huge_capacity() {
+        // 容量不会预先分配，槽位用完时在环绕的状态下扩容
+        let mut queue = Queue::new(usize::MAX);
+        assert_eq!(usize::MAX, queue.capacity());
+        for i in 0..1000 {
+            queue.push(i);
+        }
+        for i in 0..600 {
+            assert_eq!(Some(i), queue.pop());
+        }
+        for i in 1000..3000 {
+            queue.push(i);
+        }
+        assert_eq!(2400, queue.len());
+        assert!(queue.iter().copied().eq(600..3000));
+        assert_eq!(Some(&2999), queue.peek_back());
+        let mut queue = Queue::new(5);
+        for i in 0..5 {
+            assert_eq!(Ok(()), queue.try_push(i));
+        }
+        assert_eq!(Err(5), queue.try_push(5));
+    }
+
+    #[test]
+    fn test_queue_overwrite() {
+        let mut log = Queue::new_overwrite(3);
+        for line in ["a", "b", "c", "d", "e"] {
+            log.push(line.to_string());
+        }
+        assert_eq!(vec!["c", "d", "e"], log.iter().map(String::as_str).collect::<Vec<_>>());
+        assert_eq!(Some("c".to_string()), log.force_push("f".to_string()));
+        assert_eq!("Queue { data: [\"d\", \"e\", \"f\"], capacity: 3 }", format!("{:?}", log));
+
+        let mut empty = Queue::new_overwrite(0);
+        assert_eq!(Some(1), empty.force_push(1));
+        empty.push(2);
+        assert!(empty.is_empty());
+    }
+}
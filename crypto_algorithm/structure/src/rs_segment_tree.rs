@@ -0,0 +1,421 @@
+//! # 线段树（Segment Tree）
+//! 每个节点保存一个区间的聚合值，根节点对应整个数组，子节点把区间一分为二
+//! ## 结构
+//! ```code
+//!   data:  | 5 | 3 | 7 | 1 |        (sum)
+//!                                  [0,4):16
+//!                                ↙         ↘
+//!                          [0,2):8        [2,4):8
+//!                          ↙    ↘         ↙     ↘
+//!                      [0]:5  [1]:3    [2]:7   [3]:1
+//! ```
+//! ## 幺半群（Monoid）
+//! 聚合操作需要满足结合律并且有单位元，例如求和（单位元0）、最小值（单位元MAX）。
+//! 不要求交换律，合并时总是左区间在前
+//! ## 懒标记（Lazy Propagation）
+//! 区间修改时，如果节点区间被完全覆盖，只修改该节点的聚合值并记录一个懒标记，
+//! 等到需要访问子节点时再把标记下推，区间修改和区间查询都是O(logn)
+//! ## 常用方法
+//! 1. query:区间查询
+//! 2. set:单点修改
+//! 3. update:区间修改
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/18
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{Bound, RangeBounds};
+
+/// # 幺半群
+/// combine需要满足结合律，identity是combine的单位元。
+/// Update为区间修改的类型：apply把修改作用到一个长度为len的区间的聚合值上，
+/// compose把新的修改合并到尚未下推的旧修改上。
+/// 不需要区间修改时可以使用`std::convert::Infallible`作为Update
+pub trait Monoid<T> {
+    type Update: Clone;
+    fn identity(&self) -> T;
+    fn combine(&self, left: &T, right: &T) -> T;
+    fn apply(&self, update: &Self::Update, value: &T, len: usize) -> T;
+    fn compose(&self, newer: &Self::Update, older: &Self::Update) -> Self::Update;
+}
+
+/// 区间和，区间修改为整体加上一个数
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+/// 区间最小值，区间修改为整体加上一个数
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+/// 区间最大值，区间修改为整体加上一个数
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+macro_rules! impl_monoid {
+    ($($t:ty),*) => {$(
+        impl Monoid<$t> for Sum {
+            type Update = $t;
+            fn identity(&self) -> $t {
+                0 as $t
+            }
+            fn combine(&self, left: &$t, right: &$t) -> $t {
+                left + right
+            }
+            fn apply(&self, update: &$t, value: &$t, len: usize) -> $t {
+                value + update * (len as $t)
+            }
+            fn compose(&self, newer: &$t, older: &$t) -> $t {
+                newer + older
+            }
+        }
+
+        impl Monoid<$t> for Min {
+            type Update = $t;
+            fn identity(&self) -> $t {
+                <$t>::MAX
+            }
+            fn combine(&self, left: &$t, right: &$t) -> $t {
+                if right < left { *right } else { *left }
+            }
+            fn apply(&self, update: &$t, value: &$t, _len: usize) -> $t {
+                value + update
+            }
+            fn compose(&self, newer: &$t, older: &$t) -> $t {
+                newer + older
+            }
+        }
+
+        impl Monoid<$t> for Max {
+            type Update = $t;
+            fn identity(&self) -> $t {
+                <$t>::MIN
+            }
+            fn combine(&self, left: &$t, right: &$t) -> $t {
+                if right > left { *right } else { *left }
+            }
+            fn apply(&self, update: &$t, value: &$t, _len: usize) -> $t {
+                value + update
+            }
+            fn compose(&self, newer: &$t, older: &$t) -> $t {
+                newer + older
+            }
+        }
+    )*};
+}
+
+impl_monoid!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// # 线段树
+/// 节点按堆的方式编号：根为1，节点i的子节点为2i和2i+1。
+/// 每个节点都对应一个非空的真实区间，懒标记不会作用到单位元上
+pub struct SegmentTree<T, Op: Monoid<T>> {
+    len: usize,
+    tree: Vec<T>,
+    lazy: Vec<Option<Op::Update>>,
+    op: Op,
+}
+
+impl<T: Clone, Op: Monoid<T>> SegmentTree<T, Op> {
+    /// 长度为len、所有元素都是单位元的线段树
+    pub fn new(len: usize, op: Op) -> Self {
+        let data = vec![op.identity(); len];
+        SegmentTree::from_vec(data, op)
+    }
+    /// 自底向上建树 O(n)
+    pub fn from_vec(data: Vec<T>, op: Op) -> Self {
+        let len = data.len();
+        let size = 4 * len.max(1);
+        let mut tree = SegmentTree {
+            len,
+            tree: vec![op.identity(); size],
+            lazy: vec![None; size],
+            op,
+        };
+        if len > 0 {
+            tree.build(1, 0, len, &data);
+        }
+        tree
+    }
+    fn build(&mut self, node: usize, lo: usize, hi: usize, data: &[T]) {
+        if hi - lo == 1 {
+            self.tree[node] = data[lo].clone();
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.build(2 * node, lo, mid, data);
+        self.build(2 * node + 1, mid, hi, data);
+        self.pull(node);
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 用子节点更新父节点
+    fn pull(&mut self, node: usize) {
+        self.tree[node] = self.op.combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+    /// 把修改作用到节点上，非叶子节点记录懒标记
+    fn apply_node(&mut self, node: usize, update: &Op::Update, len: usize) {
+        self.tree[node] = self.op.apply(update, &self.tree[node], len);
+        if len > 1 {
+            self.lazy[node] = Some(match self.lazy[node].take() {
+                Some(older) => self.op.compose(update, &older),
+                None => update.clone(),
+            });
+        }
+    }
+    /// 下推懒标记
+    fn push_down(&mut self, node: usize, lo: usize, mid: usize, hi: usize) {
+        if let Some(update) = self.lazy[node].take() {
+            self.apply_node(2 * node, &update, mid - lo);
+            self.apply_node(2 * node + 1, &update, hi - mid);
+        }
+    }
+    /// 把RangeBounds转换为[start, end)，越界时panic
+    fn bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end && end <= self.len, "range {start}..{end} out of bounds for length {}", self.len);
+        (start, end)
+    }
+    /// # 区间查询
+    /// 空区间返回单位元
+    /// ## panic
+    /// 区间越界时panic
+    pub fn query<R: RangeBounds<usize>>(&mut self, range: R) -> T {
+        let (start, end) = self.bounds(range);
+        if start == end {
+            return self.op.identity();
+        }
+        self.query_node(1, 0, self.len, start, end)
+    }
+    fn query_node(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize) -> T {
+        if start <= lo && hi <= end {
+            return self.tree[node].clone();
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push_down(node, lo, mid, hi);
+        if end <= mid {
+            self.query_node(2 * node, lo, mid, start, end)
+        } else if start >= mid {
+            self.query_node(2 * node + 1, mid, hi, start, end)
+        } else {
+            let left = self.query_node(2 * node, lo, mid, start, end);
+            let right = self.query_node(2 * node + 1, mid, hi, start, end);
+            self.op.combine(&left, &right)
+        }
+    }
+    /// 单个元素
+    pub fn get(&mut self, index: usize) -> T {
+        self.query(index..=index)
+    }
+    /// 整个数组的聚合值 O(1)
+    pub fn total(&self) -> T {
+        if self.is_empty() {
+            self.op.identity()
+        } else {
+            self.tree[1].clone()
+        }
+    }
+    /// # 单点修改
+    /// ## panic
+    /// index越界时panic
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "index {index} out of bounds for length {}", self.len);
+        self.set_node(1, 0, self.len, index, value);
+    }
+    fn set_node(&mut self, node: usize, lo: usize, hi: usize, index: usize, value: T) {
+        if hi - lo == 1 {
+            self.tree[node] = value;
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push_down(node, lo, mid, hi);
+        if index < mid {
+            self.set_node(2 * node, lo, mid, index, value);
+        } else {
+            self.set_node(2 * node + 1, mid, hi, index, value);
+        }
+        self.pull(node);
+    }
+    /// # 区间修改
+    /// ## panic
+    /// 区间越界时panic
+    pub fn update<R: RangeBounds<usize>>(&mut self, range: R, update: Op::Update) {
+        let (start, end) = self.bounds(range);
+        if start < end {
+            self.update_node(1, 0, self.len, start, end, &update);
+        }
+    }
+    fn update_node(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize, update: &Op::Update) {
+        if start <= lo && hi <= end {
+            self.apply_node(node, update, hi - lo);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push_down(node, lo, mid, hi);
+        if start < mid {
+            self.update_node(2 * node, lo, mid, start, end, update);
+        }
+        if end > mid {
+            self.update_node(2 * node + 1, mid, hi, start, end, update);
+        }
+        self.pull(node);
+    }
+    /// 下推所有懒标记后按顺序取出所有元素
+    pub fn to_vec(&mut self) -> Vec<T> {
+        (0..self.len).map(|i| self.get(i)).collect()
+    }
+}
+
+impl<T: Clone + Debug, Op: Monoid<T>> Debug for SegmentTree<T, Op> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SegmentTree")
+            .field("len", &self.len)
+            .field("total", &self.total())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_sum() {
+        let mut tree = SegmentTree::from_vec(vec![5_i64, 3, 7, 1], Sum);
+        assert_eq!(16, tree.total());
+        assert_eq!(10, tree.query(1..3));
+        assert_eq!(0, tree.query(2..2));
+        tree.update(1..=2, 10);
+        assert_eq!(vec![5, 13, 17, 1], tree.to_vec());
+        tree.set(2, 0);
+        assert_eq!(19, tree.query(..));
+        assert_eq!(18, tree.query(..3));
+        assert_eq!("SegmentTree { len: 4, total: 19 }", format!("{:?}", tree));
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut min = SegmentTree::from_vec(vec![4_i32, -2, 9, 0, 3], Min);
+        let mut max = SegmentTree::from_vec(vec![4_i32, -2, 9, 0, 3], Max);
+        assert_eq!(-2, min.query(..));
+        assert_eq!(9, max.query(..));
+        min.update(..2, 5);
+        max.update(3.., 10);
+        assert_eq!(0, min.query(..));
+        assert_eq!(13, max.query(..));
+        assert_eq!(i32::MAX, min.query(0..0));
+        let mut empty: SegmentTree<f64, Min> = SegmentTree::new(0, Min);
+        assert_eq!(f64::MAX, empty.query(..));
+    }
+
+    /// 字符串拼接：满足结合律但不满足交换律，不支持区间修改
+    struct Concat;
+
+    impl Monoid<String> for Concat {
+        type Update = Infallible;
+        fn identity(&self) -> String {
+            String::new()
+        }
+        fn combine(&self, left: &String, right: &String) -> String {
+            format!("{left}{right}")
+        }
+        fn apply(&self, update: &Infallible, _: &String, _: usize) -> String {
+            match *update {}
+        }
+        fn compose(&self, newer: &Infallible, _: &Infallible) -> Infallible {
+            *newer
+        }
+    }
+
+    #[test]
+    fn test_custom_monoid() {
+        let mut tree = SegmentTree::from_vec("segment".chars().map(String::from).collect(), Concat);
+        assert_eq!("segment", tree.total());
+        assert_eq!("gme", tree.query(2..5));
+        tree.set(0, "S".to_string());
+        assert_eq!("Seg", tree.query(..=2));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_out_of_bounds() {
+        let mut tree: SegmentTree<i32, Sum> = SegmentTree::new(3, Sum);
+        tree.query(1..4);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Set(usize, i64),
+        Update(usize, usize, i64),
+        Query(usize, usize),
+    }
+
+    fn ops(len: usize) -> impl Strategy<Value = Vec<Op>> {
+        let op = prop_oneof![
+            (0..len, -100_i64..100).prop_map(|(i, v)| Op::Set(i, v)),
+            (0..=len, 0..=len, -100_i64..100).prop_map(|(a, b, v)| Op::Update(a.min(b), a.max(b), v)),
+            (0..=len, 0..=len).prop_map(|(a, b)| Op::Query(a.min(b), a.max(b))),
+        ];
+        prop::collection::vec(op, 0..100)
+    }
+
+    fn data_and_ops() -> impl Strategy<Value = (Vec<i64>, Vec<Op>)> {
+        prop::collection::vec(-100_i64..100, 1..40).prop_flat_map(|data| {
+            let len = data.len();
+            (Just(data), ops(len))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_naive((data, ops) in data_and_ops()) {
+            let mut sum = SegmentTree::from_vec(data.clone(), Sum);
+            let mut min = SegmentTree::from_vec(data.clone(), Min);
+            let mut max = SegmentTree::from_vec(data.clone(), Max);
+            let mut naive = data;
+            for op in ops {
+                match op {
+                    Op::Set(i, v) => {
+                        naive[i] = v;
+                        sum.set(i, v);
+                        min.set(i, v);
+                        max.set(i, v);
+                    }
+                    Op::Update(a, b, v) => {
+                        naive[a..b].iter_mut().for_each(|x| *x += v);
+                        sum.update(a..b, v);
+                        min.update(a..b, v);
+                        max.update(a..b, v);
+                    }
+                    Op::Query(a, b) => {
+                        prop_assert_eq!(naive[a..b].iter().sum::<i64>(), sum.query(a..b));
+                        prop_assert_eq!(naive[a..b].iter().copied().min().unwrap_or(i64::MAX), min.query(a..b));
+                        prop_assert_eq!(naive[a..b].iter().copied().max().unwrap_or(i64::MIN), max.query(a..b));
+                    }
+                }
+            }
+            prop_assert_eq!(&naive, &sum.to_vec());
+            prop_assert_eq!(&naive, &min.to_vec());
+            prop_assert_eq!(naive.iter().sum::<i64>(), sum.total());
+        }
+    }
+}
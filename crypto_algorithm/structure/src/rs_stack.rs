@@ -13,7 +13,10 @@
 //! 2. is_empty:是否为空栈
 //! 3. push:入栈
 //! 4. pop:出栈
-//! 5. peek:获取栈顶元素
+//! 5. peek / peek_mut:获取栈顶元素
+//! 6. len:栈中元素数量
+//! 7. clear:清空
+//! 8. iter:从栈顶到栈底遍历
 //!
 //! ## 场景
 //! 1. 函数调用：栈用于存储函数调用的上下文和局部变量。当一个函数被调用时，其上下文（如返回地址、参数、局部变量等）被压入栈中，当函数执行完毕后，这些上下文被弹出，程序继续执行调用该函数的位置。
@@ -25,61 +28,298 @@
 //!
 //! ## 限定栈深度（引出：扩容）
 //! 可以通过设置一个容量来限定栈的深度，此时可以动态传入栈的容量，当容量低于三分之一时考虑扩容
+//!
+//! 使用`Stack::with_capacity(max_depth)`创建限定深度的栈，入栈前由扩容策略（`GrowthPolicy`）决定是否调整深度：
+//! 1. Fixed:固定深度，栈满时入栈返回`StackOverflow`
+//! 2. Doubling:栈满时深度翻倍
+//! 3. OneThird:剩余容量低于三分之一时深度扩大为原来的1.5倍
+//!
+//! 也可以实现`GrowthPolicy`自定义扩容策略，通过`Stack::with_policy`传入
 //! ```txt
 //! @author:syf20020816@Outlook.com
 //! @date:2023/8/10
 //! @version:0.0.1
 //! @description:
 //! ```
-#[derive(Debug)]
-pub struct Stack<T>{
-    pointer:isize,
-    data:Vec<T>
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// # 扩容策略
+/// 入栈前调用，返回新的栈深度，不扩容返回None
+pub trait GrowthPolicy {
+    /// - depth:入栈后栈中元素的数量
+    /// - max_depth:当前限定的深度
+    fn grow(&self, depth: usize, max_depth: usize) -> Option<usize>;
+}
+
+/// 固定深度，不扩容
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fixed;
+
+impl GrowthPolicy for Fixed {
+    fn grow(&self, _depth: usize, _max_depth: usize) -> Option<usize> {
+        None
+    }
+}
+
+/// 栈满时深度翻倍
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Doubling;
+
+impl GrowthPolicy for Doubling {
+    fn grow(&self, depth: usize, max_depth: usize) -> Option<usize> {
+        if depth > max_depth {
+            Some(max_depth.saturating_mul(2).max(depth))
+        } else {
+            None
+        }
+    }
+}
+
+/// 剩余容量低于三分之一时深度扩大为原来的1.5倍
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OneThird;
+
+impl GrowthPolicy for OneThird {
+    fn grow(&self, depth: usize, max_depth: usize) -> Option<usize> {
+        if max_depth.saturating_sub(depth).saturating_mul(3) < max_depth || depth > max_depth {
+            Some(max_depth.saturating_add(max_depth.div_ceil(2)).max(depth))
+        } else {
+            None
+        }
+    }
+}
+
+/// # 栈溢出
+/// 限定深度的栈已满，携带未能入栈的元素
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackOverflow<T>(pub T);
+
+impl<T> Display for StackOverflow<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("stack overflow")
+    }
 }
 
-impl<T> Stack<T>{
+impl<T: Debug> Error for StackOverflow<T> {}
+
+/// 创建限定深度的栈时最多预先分配的元素数量
+const PREALLOC_LIMIT: usize = 1024;
+
+/// # 栈
+/// 栈顶即`data`的末尾，`max_depth`为None时不限定深度。
+/// `max_depth`只是上限，不会按它预先分配内存，`data`随入栈按需增长
+#[derive(Debug, Clone)]
+pub struct Stack<T, P = Fixed> {
+    data: Vec<T>,
+    max_depth: Option<usize>,
+    policy: P,
+}
+
+impl<T> Stack<T> {
     /// # 初始化空栈
-    pub fn new()->Self{
-        Stack{
-            pointer: -1,
-            data: vec![]
+    /// 不限定深度
+    pub fn new() -> Self {
+        Stack {
+            data: vec![],
+            max_depth: None,
+            policy: Fixed,
+        }
+    }
+    /// # 初始化限定深度的空栈
+    /// 深度固定，栈满时入栈返回`StackOverflow`
+    pub fn with_capacity(max_depth: usize) -> Self {
+        Stack::with_policy(max_depth, Fixed)
+    }
+}
+
+impl<T, P: GrowthPolicy> Stack<T, P> {
+    /// # 初始化限定深度的空栈
+    /// 入栈时由policy决定是否扩容；最多预先分配`PREALLOC_LIMIT`个元素，
+    /// 很大的max_depth可以放心用作保护性的上限
+    pub fn with_policy(max_depth: usize, policy: P) -> Self {
+        Stack {
+            data: Vec::with_capacity(max_depth.min(PREALLOC_LIMIT)),
+            max_depth: Some(max_depth),
+            policy,
         }
     }
     /// # 判断栈是否为空
-    /// 一行代码足够了
-    /// 1. 判断栈顶指针是否为-1
-    /// 2. 判断栈中数据是否为0
-    pub fn is_empty(&self)->bool{
-        isize::from(-1 as isize).eq(&self.pointer) && 0.eq(&self.data.len())
+    /// 一行代码足够了：判断栈中数据是否为0
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// # 栈中元素数量
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    /// # 当前限定的深度
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
     }
     /// # 入栈
-    /// 1. 栈顶指针上移1位
-    /// 2. 元素入vec
-    pub fn push(&mut self,el:T)->(){
-        self.pointer+=1;
+    /// 1. 询问扩容策略是否需要调整深度
+    /// 2. 超出深度时返回`StackOverflow`，带回元素
+    /// 3. 元素入vec
+    pub fn try_push(&mut self, el: T) -> Result<(), StackOverflow<T>> {
+        if let Some(max_depth) = self.max_depth {
+            let depth = self.len() + 1;
+            if let Some(grown) = self.policy.grow(depth, max_depth) {
+                self.max_depth = Some(grown);
+            }
+            if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+                return Err(StackOverflow(el));
+            }
+        }
         self.data.push(el);
+        Ok(())
     }
-    /// # 出栈
-    /// 1. 判断是否为空栈
-    /// 2. 栈顶指针下移一位
-    /// 3. data去除元素
-    pub fn pop(&mut self)->T{
-        if self.is_empty() {
-            panic!("empty")
-        }else {
-            self.pointer-=1;
-            match self.data.pop() {
-                None =>  panic!("empty"),
-                Some(res) => res
-            }
+    /// # 入栈
+    /// ## Panics
+    /// 栈溢出时panic，需要处理该情况请使用`try_push`
+    pub fn push(&mut self, el: T) {
+        if let Err(e) = self.try_push(el) {
+            panic!("{}", e)
         }
     }
+    /// # 出栈
+    /// 空栈返回None
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop()
+    }
     /// # 获取栈顶元素
-    pub fn peek(&self)->&T{
-        if self.is_empty() {
-            panic!("empty")
-        }else {
-            &self.data[self.pointer as usize]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.last()
+    }
+    /// # 获取栈顶元素的可变引用
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.data.last_mut()
+    }
+    /// # 清空
+    /// 保留当前深度
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+    /// # 从栈顶到栈底遍历
+    pub fn iter(&self) -> std::iter::Rev<std::slice::Iter<'_, T>> {
+        self.data.iter().rev()
+    }
+}
+
+/// 不限定深度的空栈，不会调用policy扩容
+impl<T, P: Default> Default for Stack<T, P> {
+    fn default() -> Self {
+        Stack {
+            data: vec![],
+            max_depth: None,
+            policy: P::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+        assert_eq!(None, stack.pop());
+        assert_eq!(None, stack.peek());
+        for i in 0..5 {
+            stack.push(i);
+        }
+        assert_eq!(5, stack.len());
+        assert_eq!(Some(&4), stack.peek());
+        *stack.peek_mut().unwrap() = 40;
+        assert_eq!(vec![40, 3, 2, 1, 0], stack.iter().copied().collect::<Vec<_>>());
+        assert_eq!(Some(40), stack.pop());
+        stack.clear();
+        assert!(stack.is_empty());
+        let mut stack: Stack<u32, Doubling> = Stack::default();
+        assert_eq!(None, stack.max_depth());
+        for i in 0..2000 {
+            stack.push(i);
+        }
+        assert_eq!(2000, stack.len());
+    }
+
+    #[test]
+    fn test_stack_fixed() {
+        let mut stack = Stack::with_capacity(2);
+        assert_eq!(Ok(()), stack.try_push('a'));
+        assert_eq!(Ok(()), stack.try_push('b'));
+        assert_eq!(Err(StackOverflow('c')), stack.try_push('c'));
+        assert_eq!(Some(2), stack.max_depth());
+        assert_eq!(Some('b'), stack.pop());
+        assert_eq!(Ok(()), stack.try_push('c'));
+    }
+
+    #[test]
+    #[should_panic(expected = "stack overflow")]
+    fn test_stack_push_overflow() {
+        let mut stack = Stack::with_capacity(0);
+        stack.push(1);
+    }
+
+    #[test]
+    fn test_stack_doubling() {
+        let mut stack = Stack::with_policy(2, Doubling);
+        for i in 0..5 {
+            assert_eq!(Ok(()), stack.try_push(i));
+        }
+        assert_eq!(Some(8), stack.max_depth());
+        let mut empty = Stack::with_policy(0, Doubling);
+        empty.push(1);
+        assert_eq!(Some(1), empty.max_depth());
+    }
+
+    #[test]
+    fn test_stack_one_third() {
+        let mut stack = Stack::with_policy(6, OneThird);
+        for i in 0..4 {
+            stack.push(i);
+        }
+        assert_eq!(Some(6), stack.max_depth());
+        // 第5个元素入栈后只剩1个空位，低于三分之一
+        stack.push(4);
+        assert_eq!(Some(9), stack.max_depth());
+        for i in 5..20 {
+            stack.push(i);
+        }
+        assert!(stack.max_depth().unwrap() >= 20 * 3 / 2);
+    }
+
+    #[test]
+    fn test_stack_huge_limit() {
+        // 深度上限不会预先分配内存
+        let mut stack = Stack::with_capacity(usize::MAX);
+        stack.push(1);
+        assert_eq!(Some(usize::MAX), stack.max_depth());
+        let mut stack = Stack::with_policy(usize::MAX / 2 + 1, Doubling);
+        stack.push(1);
+        assert_eq!(Some(usize::MAX / 2 + 1), stack.max_depth());
+        let mut stack = Stack::with_policy(usize::MAX, OneThird);
+        for i in 0..3 {
+            stack.push(i);
+        }
+        assert_eq!(Some(usize::MAX), stack.max_depth());
+    }
+
+    #[test]
+    fn test_stack_custom_policy() {
+        /// 最多扩容到10
+        struct Capped;
+        impl GrowthPolicy for Capped {
+            fn grow(&self, depth: usize, max_depth: usize) -> Option<usize> {
+                (depth > max_depth && depth <= 10).then_some(depth)
+            }
+        }
+        let mut stack = Stack::with_policy(1, Capped);
+        for i in 0..10 {
+            stack.push(i);
         }
+        assert_eq!(Err(StackOverflow(10)), stack.try_push(10));
     }
 }
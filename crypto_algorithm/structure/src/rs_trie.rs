@@ -0,0 +1,313 @@
+//! # 字典树（Trie / 前缀树）
+//! 每条边代表一个字符，从根到某个节点的路径就是一个前缀，节点上保存以该路径为键的值
+//! ## 结构
+//! ```code
+//!              (root)
+//!             ↙     ↘
+//!          'a'       'b'
+//!          ↙           ↘
+//!       'p'(1)         'e'
+//!        ↓               ↘
+//!       'i'(2)           'e'(3)
+//!
+//!   keys: "ap" = 1, "api" = 2, "bee" = 3
+//! ```
+//! ## 常用方法
+//! 1. insert / get / get_mut / remove:增查改删，复杂度与键长成正比
+//! 2. iter_prefix:按字典序遍历以某个前缀开头的所有键
+//! 3. longest_prefix_match:查找是查询串前缀的最长键，常用于路由匹配
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/18
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+
+/// 子节点按字符排序，遍历时自然得到字典序
+#[derive(Clone)]
+struct TrieNode<V> {
+    value: Option<V>,
+    children: BTreeMap<char, TrieNode<V>>,
+}
+
+impl<V> TrieNode<V> {
+    fn new() -> Self {
+        TrieNode {
+            value: None,
+            children: BTreeMap::new(),
+        }
+    }
+    /// 没有值也没有子节点的节点可以被剪掉
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.is_empty()
+    }
+    /// 递归删除，回溯时剪掉空节点
+    fn remove(&mut self, mut chars: std::str::Chars<'_>) -> Option<V> {
+        match chars.next() {
+            None => self.value.take(),
+            Some(c) => {
+                let child = self.children.get_mut(&c)?;
+                let removed = child.remove(chars);
+                if child.is_empty() {
+                    self.children.remove(&c);
+                }
+                removed
+            }
+        }
+    }
+}
+
+/// # 字典树
+/// 键为字符串，值为V
+#[derive(Clone)]
+pub struct Trie<V> {
+    root: TrieNode<V>,
+    len: usize,
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Trie {
+            root: TrieNode::new(),
+            len: 0,
+        }
+    }
+    /// 键的数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 清空
+    pub fn clear(&mut self) {
+        self.root = TrieNode::new();
+        self.len = 0;
+    }
+    fn node(&self, key: &str) -> Option<&TrieNode<V>> {
+        key.chars().try_fold(&self.root, |node, c| node.children.get(&c))
+    }
+    /// # 插入
+    /// 键已存在时替换值并返回旧值
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let node = key
+            .chars()
+            .fold(&mut self.root, |node, c| node.children.entry(c).or_insert_with(TrieNode::new));
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    /// 查询
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.node(key)?.value.as_ref()
+    }
+    /// 查询（可变）
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        key.chars()
+            .try_fold(&mut self.root, |node, c| node.children.get_mut(&c))?
+            .value
+            .as_mut()
+    }
+    /// 是否包含某个键
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    /// # 删除
+    /// 删除后不再被任何键使用的节点会被剪掉
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let removed = self.root.remove(key.chars());
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+    /// 是否有以prefix开头的键
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        self.node(prefix).is_some_and(|node| !node.is_empty())
+    }
+    /// 按字典序遍历所有键值对
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.iter_prefix("")
+    }
+    /// # 前缀遍历
+    /// 按字典序遍历以prefix开头的键值对（包含prefix本身）
+    pub fn iter_prefix(&self, prefix: &str) -> Iter<'_, V> {
+        Iter {
+            stack: self.node(prefix).map(|node| (prefix.to_string(), node)).into_iter().collect(),
+        }
+    }
+    /// # 最长前缀匹配
+    /// 在所有是query前缀的键中返回最长的一个
+    /// ```code
+    /// keys: "/api", "/api/v1"
+    /// "/api/v1/users" → "/api/v1"
+    /// "/api/v2"       → "/api"
+    /// ```
+    pub fn longest_prefix_match<'q>(&self, query: &'q str) -> Option<(&'q str, &V)> {
+        let mut node = &self.root;
+        let mut best = node.value.as_ref().map(|v| (0, v));
+        for (i, c) in query.char_indices() {
+            match node.children.get(&c) {
+                Some(child) => node = child,
+                None => break,
+            }
+            if let Some(v) = node.value.as_ref() {
+                best = Some((i + c.len_utf8(), v));
+            }
+        }
+        best.map(|(end, v)| (&query[..end], v))
+    }
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Trie::new()
+    }
+}
+
+impl<V: Debug> Debug for Trie<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V: PartialEq> PartialEq for Trie<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: AsRef<str>, V> FromIterator<(K, V)> for Trie<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut trie = Trie::new();
+        trie.extend(iter);
+        trie
+    }
+}
+
+impl<K: AsRef<str>, V> Extend<(K, V)> for Trie<V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k.as_ref(), v);
+        }
+    }
+}
+
+/// # 字典序迭代器
+/// 先序遍历：先输出节点自身，再按字符顺序访问子节点（逆序压栈）
+pub struct Iter<'a, V> {
+    stack: Vec<(String, &'a TrieNode<V>)>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            for (c, child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(*c);
+                self.stack.push((child_key, child));
+            }
+            if let Some(value) = node.value.as_ref() {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut trie = Trie::new();
+        assert_eq!(None, trie.insert("ap", 1));
+        assert_eq!(None, trie.insert("api", 2));
+        assert_eq!(None, trie.insert("bee", 3));
+        assert_eq!(Some(1), trie.insert("ap", 10));
+        assert_eq!(3, trie.len());
+        assert_eq!(Some(&10), trie.get("ap"));
+        assert_eq!(None, trie.get("a"));
+        assert!(trie.has_prefix("a"));
+        *trie.get_mut("bee").unwrap() += 1;
+        assert_eq!(Some(4), trie.remove("bee"));
+        assert!(!trie.has_prefix("b"));
+        assert_eq!(None, trie.remove("bee"));
+        assert_eq!(Some(10), trie.remove("ap"));
+        assert!(trie.contains_key("api"));
+        assert_eq!(1, trie.len());
+        assert_eq!("{\"api\": 2}", format!("{:?}", trie));
+    }
+
+    #[test]
+    fn test_prefix_iteration() {
+        let trie: Trie<usize> = ["car", "cart", "care", "cat", "dog", "c"].into_iter().map(|k| (k, k.len())).collect();
+        let keys: Vec<String> = trie.iter_prefix("car").map(|(k, _)| k).collect();
+        assert_eq!(vec!["car", "care", "cart"], keys);
+        assert_eq!(5, trie.iter_prefix("c").count());
+        assert_eq!(0, trie.iter_prefix("x").count());
+        assert_eq!(vec!["c", "car", "care", "cart", "cat", "dog"], trie.iter().map(|(k, _)| k).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let routes: Trie<&str> = [("/api", "api"), ("/api/v1", "v1"), ("/", "root"), ("/静态", "static")].into_iter().collect();
+        assert_eq!(Some(("/api/v1", &"v1")), routes.longest_prefix_match("/api/v1/users"));
+        assert_eq!(Some(("/api", &"api")), routes.longest_prefix_match("/api/v2"));
+        assert_eq!(Some(("/", &"root")), routes.longest_prefix_match("/home"));
+        assert_eq!(Some(("/静态", &"static")), routes.longest_prefix_match("/静态/图片"));
+        assert_eq!(None, routes.longest_prefix_match("api"));
+        let mut empty_key = Trie::new();
+        empty_key.insert("", 0);
+        assert_eq!(Some(("", &0)), empty_key.longest_prefix_match("abc"));
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(String, u8),
+        Remove(String),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        let key = "[ab]{0,4}";
+        prop_oneof![
+            (key, any::<u8>()).prop_map(|(k, v)| Op::Insert(k, v)),
+            key.prop_map(Op::Remove),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_btree_map(ops in prop::collection::vec(op(), 0..100), query in "[ab]{0,6}") {
+            let mut trie = Trie::new();
+            let mut model = BTreeMap::new();
+            for op in ops {
+                match op {
+                    Op::Insert(k, v) => prop_assert_eq!(model.insert(k.clone(), v), trie.insert(&k, v)),
+                    Op::Remove(k) => prop_assert_eq!(model.remove(&k), trie.remove(&k)),
+                }
+                prop_assert_eq!(model.len(), trie.len());
+            }
+            prop_assert!(model.iter().map(|(k, v)| (k.clone(), v)).eq(trie.iter()));
+            let prefix = &query[..query.len().min(2)];
+            prop_assert!(model.iter().filter(|(k, _)| k.starts_with(prefix)).map(|(k, v)| (k.clone(), v)).eq(trie.iter_prefix(prefix)));
+            let expected = model.iter().filter(|(k, _)| query.starts_with(k.as_str())).max_by_key(|(k, _)| k.len()).map(|(k, v)| (k.as_str(), v));
+            prop_assert_eq!(expected, trie.longest_prefix_match(&query));
+            // 删除后剪枝：没有键时根节点也没有子节点
+            if model.is_empty() {
+                prop_assert!(trie.root.is_empty());
+            }
+        }
+    }
+}
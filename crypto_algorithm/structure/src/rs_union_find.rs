@@ -0,0 +1,177 @@
+//! # 并查集（Union-Find / Disjoint Set）
+//! 维护若干个互不相交的集合，每个集合用一棵树表示，树根作为集合的代表元素
+//! ## 结构
+//! ```code
+//!   parent: | 0 | 0 | 1 | 3 | 3 |          (0)       (3)
+//!   index:    0   1   2   3   4            ↑         ↑
+//!                                         (1)       (4)
+//!                                          ↑
+//!                                         (2)
+//! ```
+//! ## 优化
+//! 1. 路径压缩：find时把路径上的所有节点直接挂到根上
+//! 2. 按秩合并：合并时把秩（树高的上界）较小的树挂到较大的树下
+//!
+//! 两者结合后单次操作的均摊复杂度为O(α(n))，α为反阿克曼函数，可视为常数
+//! ## 常用方法
+//! 1. find:查找代表元素
+//! 2. union:合并两个元素所在的集合
+//! 3. connected:是否在同一集合
+//! 4. make_set:添加一个新的单元素集合
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/18
+//! @version:0.0.1
+//! @description:
+//! ```
+
+/// # 并查集
+/// 元素为`0..len`的编号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// n个单元素集合
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            count: n,
+        }
+    }
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    /// 集合数量
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// 添加一个单元素集合，返回新元素的编号
+    pub fn make_set(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.rank.push(0);
+        self.size.push(1);
+        self.count += 1;
+        x
+    }
+    /// # 查找代表元素
+    /// 先找到根，再把路径上的节点都指向根（两趟，避免递归）
+    /// ## panic
+    /// x越界时panic
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+    /// # 合并
+    /// 两个元素原本不在同一集合时返回true
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.rank[a] < self.rank[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        // b挂到a下
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+        self.count -= 1;
+        true
+    }
+    /// 是否在同一集合
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+    /// x所在集合的元素数量
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(5);
+        assert_eq!(5, sets.count());
+        assert!(sets.union(0, 1));
+        assert!(sets.union(1, 2));
+        assert!(!sets.union(2, 0));
+        assert!(sets.union(3, 4));
+        assert!(sets.connected(0, 2));
+        assert!(!sets.connected(0, 3));
+        assert_eq!(2, sets.count());
+        assert_eq!(3, sets.set_size(1));
+        let x = sets.make_set();
+        assert_eq!(5, x);
+        assert_eq!((6, 3), (sets.len(), sets.count()));
+        assert!(sets.union(x, 4));
+        assert_eq!(3, sets.set_size(3));
+    }
+
+    #[test]
+    fn test_long_chain() {
+        // 按秩合并保证秩不超过log2(n)
+        let bits = if cfg!(miri) { 10 } else { 16 };
+        let mut sets = UnionFind::new(1 << bits);
+        for i in 1..(1 << bits) {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(1, sets.count());
+        assert!(sets.rank.iter().all(|&r| r <= bits));
+        assert_eq!(1 << bits, sets.set_size(1000));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_naive(n in 1_usize..30, ops in prop::collection::vec((0_usize..30, 0_usize..30), 0..60)) {
+            let mut sets = UnionFind::new(n);
+            // 朴素实现：直接记录每个元素所属的集合编号，合并时整体改写
+            let mut label: Vec<usize> = (0..n).collect();
+            for (a, b) in ops.into_iter().filter(|&(a, b)| a < n && b < n) {
+                let merged = label[a] != label[b];
+                prop_assert_eq!(merged, sets.union(a, b));
+                let (from, to) = (label[b], label[a]);
+                label.iter_mut().filter(|l| **l == from).for_each(|l| *l = to);
+            }
+            let mut distinct = label.clone();
+            distinct.sort();
+            distinct.dedup();
+            prop_assert_eq!(distinct.len(), sets.count());
+            for a in 0..n {
+                prop_assert_eq!(label.iter().filter(|&&l| l == label[a]).count(), sets.set_size(a));
+                for b in 0..n {
+                    prop_assert_eq!(label[a] == label[b], sets.connected(a, b));
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,15 @@
 //! # Vec
 //! Vec 是一个强大但简单的数据容器，提供了数据收集机制和各种各样的操作
+//! ## 结构
+//! ```code
+//!   ptr ——→ | *1 | *2 | *3 | *4 | ?? | ?? | ?? | ?? |
+//!           |->        len      <-|
+//!           |->                cap                <-|
+//! ```
+//! 1. ptr:通过`std::alloc`手动申请的连续内存
+//! 2. len:已初始化的元素数量
+//! 3. cap:已申请的内存可容纳的元素数量，空间不足时翻倍扩容（均摊O(1)）
+//! 4. 零大小类型（ZST）不需要申请内存，cap视为`usize::MAX`
 //! ## 常用方法
 //! 1. new:创建一个空Vec
 //! 2. push:元素入Vec
@@ -8,6 +18,11 @@
 //! 5. is_empty:是否为空
 //! 6. len:vec的长度
 //! 7. remove:将元素从Vec某个位置移除
+//! 8. reserve:预留空间
+//! 9. shrink_to_fit:释放多余空间
+//! 10. drain:移出一段范围内的元素
+//!
+//! `Vector<T>`通过`Deref<Target=[T]>`获得切片的所有方法（下标、迭代、排序等）
 //! ```txt
 //! @author:syf20020816@Outlook.com
 //! @date:2023/8/11
@@ -15,6 +30,644 @@
 //! @description:
 //! ```
 
-pub struct Vector{
+use std::alloc::{self, Layout};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Bound, Deref, DerefMut, RangeBounds};
+use std::ptr::{self, NonNull};
+
+/// # 底层内存
+/// 只负责申请、扩容和释放内存，不关心其中的元素是否初始化
+struct RawVector<T> {
+    ptr: NonNull<T>,
+    cap: usize,
+}
+
+unsafe impl<T: Send> Send for RawVector<T> {}
+
+unsafe impl<T: Sync> Sync for RawVector<T> {}
+
+impl<T> RawVector<T> {
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    fn new() -> Self {
+        RawVector {
+            ptr: NonNull::dangling(),
+            cap: if Self::IS_ZST { usize::MAX } else { 0 },
+        }
+    }
+    /// # 重新分配内存
+    /// 1. new_cap为0时释放内存
+    /// 2. 原先没有内存时申请，否则realloc（会拷贝原有内容）
+    fn realloc(&mut self, new_cap: usize) {
+        debug_assert!(!Self::IS_ZST);
+        if new_cap == 0 {
+            self.dealloc();
+            return;
+        }
+        let new_layout = Layout::array::<T>(new_cap).expect("capacity overflow");
+        let new_ptr = if self.cap == 0 {
+            unsafe { alloc::alloc(new_layout) }
+        } else {
+            let old_layout = Layout::array::<T>(self.cap).unwrap();
+            unsafe { alloc::realloc(self.ptr.as_ptr() as *mut u8, old_layout, new_layout.size()) }
+        };
+        self.ptr = match NonNull::new(new_ptr as *mut T) {
+            Some(ptr) => ptr,
+            None => alloc::handle_alloc_error(new_layout),
+        };
+        self.cap = new_cap;
+    }
+    /// # 扩容
+    /// 保证至少能容纳`needed`个元素，容量不足时至少翻倍
+    fn grow_to(&mut self, needed: usize) {
+        // ZST的cap为usize::MAX，需要扩容说明长度溢出
+        assert!(!Self::IS_ZST, "capacity overflow");
+        if needed <= self.cap {
+            return;
+        }
+        let doubled = if self.cap == 0 { 4 } else { self.cap.saturating_mul(2) };
+        self.realloc(needed.max(doubled));
+    }
+    fn dealloc(&mut self) {
+        if !Self::IS_ZST && self.cap != 0 {
+            unsafe {
+                alloc::dealloc(
+                    self.ptr.as_ptr() as *mut u8,
+                    Layout::array::<T>(self.cap).unwrap(),
+                );
+            }
+        }
+        self.ptr = NonNull::dangling();
+        self.cap = if Self::IS_ZST { usize::MAX } else { 0 };
+    }
+}
+
+impl<T> Drop for RawVector<T> {
+    fn drop(&mut self) {
+        self.dealloc();
+    }
+}
+
+/// # 手动管理内存的可增长数组
+pub struct Vector<T> {
+    buf: RawVector<T>,
+    len: usize,
+}
+
+impl<T> Vector<T> {
+    /// # 创建一个空Vec
+    /// 不申请内存
+    pub fn new() -> Self {
+        Vector {
+            buf: RawVector::new(),
+            len: 0,
+        }
+    }
+    /// 创建一个至少能容纳`cap`个元素的空Vec
+    pub fn with_capacity(cap: usize) -> Self {
+        let mut vector = Vector::new();
+        vector.reserve(cap);
+        vector
+    }
+    fn ptr(&self) -> *mut T {
+        self.buf.ptr.as_ptr()
+    }
+    /// vec的长度
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        0_usize.eq(&self.len)
+    }
+    /// 已申请的容量
+    pub fn capacity(&self) -> usize {
+        self.buf.cap
+    }
+    /// # 预留空间
+    /// 保证还能再放入`additional`个元素而不需要重新分配
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed > self.capacity() {
+            self.buf.grow_to(needed);
+        }
+    }
+    /// # 释放多余空间
+    /// 容量缩减到与长度相同，ZST不需要处理
+    pub fn shrink_to_fit(&mut self) {
+        if !RawVector::<T>::IS_ZST && self.capacity() > self.len {
+            self.buf.realloc(self.len);
+        }
+    }
+    /// # 元素入Vec
+    /// 1. 容量已满时扩容
+    /// 2. 写入末尾
+    pub fn push(&mut self, el: T) {
+        if self.len == self.capacity() {
+            self.buf.grow_to(self.len + 1);
+        }
+        unsafe {
+            ptr::write(self.ptr().add(self.len), el);
+        }
+        self.len += 1;
+    }
+    /// 取出最后一个元素，空Vec返回None
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.len -= 1;
+            unsafe { Some(ptr::read(self.ptr().add(self.len))) }
+        }
+    }
+    /// # 将元素插入指定位置
+    /// 1. index之后的元素整体后移一位
+    /// 2. 写入index
+    /// ## Panics
+    /// index大于长度时panic
+    pub fn insert(&mut self, index: usize, el: T) {
+        assert!(index <= self.len, "index {} out of bounds for length {}", index, self.len);
+        if self.len == self.capacity() {
+            self.buf.grow_to(self.len + 1);
+        }
+        unsafe {
+            let p = self.ptr().add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            ptr::write(p, el);
+        }
+        self.len += 1;
+    }
+    /// # 移除指定位置的元素
+    /// 1. 读出index处的元素
+    /// 2. index之后的元素整体前移一位
+    /// ## Panics
+    /// index越界时panic
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        unsafe {
+            self.len -= 1;
+            let p = self.ptr().add(index);
+            let el = ptr::read(p);
+            ptr::copy(p.add(1), p, self.len - index);
+            el
+        }
+    }
+    /// 清空所有元素，保留容量
+    pub fn clear(&mut self) {
+        let elements: *mut [T] = self.as_mut_slice();
+        // 先置零长度，元素的drop发生panic时也不会重复释放
+        self.len = 0;
+        unsafe {
+            ptr::drop_in_place(elements);
+        }
+    }
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr(), self.len) }
+    }
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr(), self.len) }
+    }
+    /// # 移出一段范围内的元素
+    /// 返回的迭代器被丢弃时，未取出的元素会被释放，范围之后的元素前移补齐
+    /// ## Panics
+    /// 范围越界时panic
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "drain start {} is greater than end {}", start, end);
+        assert!(end <= self.len, "drain end {} out of bounds for length {}", end, self.len);
+        let tail_len = self.len - end;
+        // Drain被mem::forget时只会泄漏，不会访问已移出的元素
+        self.len = start;
+        Drain {
+            vec: NonNull::from(&mut *self),
+            start,
+            idx: start,
+            end,
+            tail_start: end,
+            tail_len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for Vector<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+impl<T> Default for Vector<T> {
+    fn default() -> Self {
+        Vector::new()
+    }
+}
+
+impl<T> Deref for Vector<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for Vector<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Debug> Debug for Vector<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for Vector<T> {
+    fn clone(&self) -> Self {
+        let mut vector = Vector::with_capacity(self.len);
+        vector.extend(self.iter().cloned());
+        vector
+    }
+}
+
+impl<T: PartialEq> PartialEq for Vector<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Vector::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+impl<T> Extend<T> for Vector<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for el in iter {
+            self.push(el);
+        }
+    }
+}
+
+/// # 转为迭代器
+/// 接管底层内存，从两端读出元素
+pub struct IntoIter<T> {
+    buf: RawVector<T>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> IntoIterator for Vector<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let vector = mem::ManuallyDrop::new(self);
+        // 取走内存的所有权，Vector本身不再drop
+        let buf = unsafe { ptr::read(&vector.buf) };
+        IntoIter {
+            buf,
+            start: 0,
+            end: vector.len,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Vector<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Vector<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            let el = unsafe { ptr::read(self.buf.ptr.as_ptr().add(self.start)) };
+            self.start += 1;
+            Some(el)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            None
+        } else {
+            self.end -= 1;
+            unsafe { Some(ptr::read(self.buf.ptr.as_ptr().add(self.end))) }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// 释放尚未读出的元素，内存由RawVector释放
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let rest = ptr::slice_from_raw_parts_mut(
+                self.buf.ptr.as_ptr().add(self.start),
+                self.end - self.start,
+            );
+            ptr::drop_in_place(rest);
+        }
+    }
+}
+
+/// # 移出范围内元素的迭代器
+/// 1. [start, idx)：已被读出
+/// 2. [idx, end)：尚未读出
+/// 3. [tail_start, tail_start + tail_len)：范围之后的元素，drop时前移到start
+pub struct Drain<'a, T: 'a> {
+    vec: NonNull<Vector<T>>,
+    start: usize,
+    idx: usize,
+    end: usize,
+    tail_start: usize,
+    tail_len: usize,
+    _marker: PhantomData<&'a mut Vector<T>>,
+}
+
+impl<T> Drain<'_, T> {
+    fn ptr(&self) -> *mut T {
+        unsafe { self.vec.as_ref().ptr() }
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            let el = unsafe { ptr::read(self.ptr().add(self.idx)) };
+            self.idx += 1;
+            Some(el)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.end -= 1;
+            unsafe { Some(ptr::read(self.ptr().add(self.end))) }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        /// 未取出的元素在drop时panic，展开过程中仍然把尾部前移并恢复长度
+        struct TailGuard<'r, 'a, T>(&'r mut Drain<'a, T>);
+
+        impl<T> Drop for TailGuard<'_, '_, T> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                unsafe {
+                    let ptr = drain.ptr();
+                    ptr::copy(ptr.add(drain.tail_start), ptr.add(drain.start), drain.tail_len);
+                    drain.vec.as_mut().len = drain.start + drain.tail_len;
+                }
+            }
+        }
+
+        let guard = TailGuard(self);
+        unsafe {
+            let rest = ptr::slice_from_raw_parts_mut(guard.0.ptr().add(guard.0.idx), guard.0.end - guard.0.idx);
+            ptr::drop_in_place(rest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop() {
+        let mut v = Vector::new();
+        assert!(v.is_empty());
+        assert_eq!(0, v.capacity());
+        for i in 0..10 {
+            v.push(i);
+        }
+        assert_eq!(10, v.len());
+        assert_eq!(16, v.capacity());
+        assert_eq!(Some(9), v.pop());
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7, 8], &v[..]);
+        v.clear();
+        assert_eq!(None, v.pop());
+        assert_eq!(16, v.capacity());
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut v: Vector<String> = ["a", "c"].iter().map(|s| s.to_string()).collect();
+        v.insert(1, "b".to_string());
+        v.insert(3, "d".to_string());
+        v.insert(0, "_".to_string());
+        assert_eq!(vec!["_", "a", "b", "c", "d"], v.iter().map(String::as_str).collect::<Vec<_>>());
+        assert_eq!("_", v.remove(0));
+        assert_eq!("d", v.remove(3));
+        assert_eq!("b", v.remove(1));
+        assert_eq!(vec!["a", "c"], v.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_remove_out_of_bounds() {
+        let mut v = Vector::<u8>::new();
+        v.remove(0);
+    }
+
+    #[test]
+    fn test_reserve_shrink() {
+        let mut v = Vector::<u64>::with_capacity(3);
+        assert!(v.capacity() >= 3);
+        v.extend([1, 2, 3]);
+        v.reserve(10);
+        assert!(v.capacity() >= 13);
+        v.shrink_to_fit();
+        assert_eq!(3, v.capacity());
+        v.clear();
+        v.shrink_to_fit();
+        assert_eq!(0, v.capacity());
+        v.push(4);
+        assert_eq!(&[4], &v[..]);
+    }
+
+    #[test]
+    fn test_deref_slice() {
+        let mut v: Vector<i32> = vec![3, 1, 2].into_iter().collect();
+        v.sort();
+        v[0] = 10;
+        assert_eq!(&[10, 2, 3], v.as_slice());
+        assert!(v.contains(&2));
+        assert_eq!("[10, 2, 3]", format!("{:?}", v));
+        assert_eq!(v.clone(), v);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut v = Vector::new();
+        assert_eq!(usize::MAX, v.capacity());
+        for _ in 0..1000 {
+            v.push(());
+        }
+        v.insert(10, ());
+        assert_eq!((), v.remove(0));
+        v.shrink_to_fit();
+        assert_eq!(1000, v.len());
+        assert_eq!(1000, v.drain(..).count());
+        assert!(v.is_empty());
+        v.extend([(), ()]);
+        assert_eq!(2, v.into_iter().rev().count());
+    }
+
+    #[test]
+    fn test_drop_elements() {
+        let counter = Rc::new(());
+        let mut v = Vector::new();
+        for _ in 0..5 {
+            v.push(Rc::clone(&counter));
+        }
+        let _ = v.pop();
+        let _ = v.remove(0);
+        assert_eq!(4, Rc::strong_count(&counter));
+        drop(v);
+        assert_eq!(1, Rc::strong_count(&counter));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let counter = Rc::new(());
+        let v: Vector<Rc<()>> = (0..6).map(|_| Rc::clone(&counter)).collect();
+        let mut iter = v.into_iter();
+        assert_eq!(6, iter.len());
+        let first = iter.next();
+        let last = iter.next_back();
+        assert_eq!(4, iter.len());
+        drop(iter);
+        assert_eq!(3, Rc::strong_count(&counter));
+        drop((first, last));
+        assert_eq!(1, Rc::strong_count(&counter));
+
+        let v: Vector<u8> = (0..4).collect();
+        assert_eq!(vec![3, 2, 1, 0], v.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut v: Vector<String> = (0..8).map(|i| i.to_string()).collect();
+        let drained: Vec<String> = v.drain(2..5).collect();
+        assert_eq!(vec!["2", "3", "4"], drained);
+        assert_eq!(vec!["0", "1", "5", "6", "7"], v.iter().map(String::as_str).collect::<Vec<_>>());
+
+        let mut drain = v.drain(1..=3);
+        assert_eq!(Some("1".to_string()), drain.next());
+        assert_eq!(Some("6".to_string()), drain.next_back());
+        drop(drain);
+        assert_eq!(vec!["0", "7"], v.iter().map(String::as_str).collect::<Vec<_>>());
+
+        v.drain(..);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn test_drain_forget_leaks_safely() {
+        // 取出范围内的元素泄漏而不是被drop两次；元素不占堆内存，Miri的泄漏检查只会看到Vector自身
+        struct Counted<'a>(u8, &'a Cell<usize>);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+        let drops = Cell::new(0);
+        let mut v: Vector<Counted> = (0..4).map(|i| Counted(i, &drops)).collect();
+        mem::forget(v.drain(1..));
+        assert_eq!(1, v.len());
+        assert_eq!(0, v[0].0);
+        drop(v);
+        assert_eq!(1, drops.get());
+    }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_drain_drop_panic() {
+        // 值为2的元素drop时panic，其余未取出的元素仍然被drop，尾部仍然前移
+        struct Bomb<'a>(u8, &'a Cell<usize>);
+        impl Drop for Bomb<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+                if self.0 == 2 {
+                    panic!("bomb");
+                }
+            }
+        }
+        let drops = Cell::new(0);
+        let mut v: Vector<Bomb> = (0..6).map(|i| Bomb(i, &drops)).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut drain = v.drain(1..4);
+            drop(drain.next());
+            drop(drain);
+        }));
+        assert!(result.is_err());
+        assert_eq!(3, drops.get());
+        assert_eq!(vec![0, 4, 5], v.iter().map(|b| b.0).collect::<Vec<_>>());
+        drop(v);
+        assert_eq!(6, drops.get());
+    }
+}
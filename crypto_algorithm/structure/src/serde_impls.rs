@@ -0,0 +1,307 @@
+//! # 序列化支持（serde）
+//! 开启`serde` feature后，容器实现`Serialize`/`Deserialize`
+//! ## 格式
+//! 1. 线性容器序列化为元素序列：Array、Vector、Queue（队首到队尾）、Deque、Stack（栈底到栈顶）、
+//!    LinkedList、DoublyLinkedList、BinaryHeap（堆内部顺序）、PersistentList、PersistentVector
+//! 2. 映射序列化为`[key, value]`对的序列：HashMap、OrderedMap、Trie
+//! 3. Graph序列化为`{ directed, nodes, edges: [[from, to, weight], ...] }`
+//!
+//! 容量、覆盖模式、增长策略、哈希种子等配置不会被序列化；有界Array只序列化已写入的元素，不包括占位元素
+//! ## 容量
+//! **直接反序列化（`Deserialize`）不限制元素数量**，任意长度的输入都会被完整读入：
+//! Array、Deque、Stack为无界容器，Queue的容量等于元素数量（至少为1）。
+//! 输入不可信、需要拒绝超长输入时必须使用`WithCapacity`，元素超过容量时返回错误：
+//! ```code
+//! let queue: Queue<u32> = WithCapacity::new(5).deserialize(&mut deserializer)?;
+//! ```
+//! 并发容器以及UnionFind、SegmentTree、IndexedPriorityQueue等算法辅助结构不提供序列化
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/26
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use crate::rs_array::Array;
+use crate::{
+    BinaryHeap, Compare, Deque, DoublyLinkedList, Graph, GrowthPolicy, HashMap, LinkedList, OrderedMap, PersistentList,
+    PersistentVector, Queue, Stack, Trie, Vector,
+};
+use serde::de::{DeserializeSeed, Error, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+/// # 序列访问者
+/// 把元素收集到Vec中，max为Some时超过数量立即报错
+struct SeqVisitor<T> {
+    max: Option<usize>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SeqVisitor<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) => write!(f, "a sequence of at most {} elements", max),
+            None => f.write_str("a sequence"),
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let max = self.max.unwrap_or(usize::MAX);
+        // 带长度前缀的格式（如bincode）可以在读取元素之前拒绝
+        if let Some(hint) = seq.size_hint().filter(|&hint| hint > max) {
+            return Err(A::Error::invalid_length(hint, &self));
+        }
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(item) = seq.next_element()? {
+            if items.len() == max {
+                return Err(A::Error::invalid_length(max + 1, &self));
+            }
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+fn deserialize_items<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D, max: Option<usize>) -> Result<Vec<T>, D::Error> {
+    deserializer.deserialize_seq(SeqVisitor {
+        max,
+        _marker: PhantomData,
+    })
+}
+
+/// 写出长度确定的序列，部分迭代器的size_hint不精确，bincode等格式需要预先知道长度
+fn serialize_items<S: Serializer, I: IntoIterator>(serializer: S, len: usize, items: I) -> Result<S::Ok, S::Error>
+where
+    I::Item: Serialize,
+{
+    let mut seq = serializer.serialize_seq(Some(len))?;
+    for item in items {
+        seq.serialize_element(&item)?;
+    }
+    seq.end()
+}
+
+/// # 限定容量的反序列化
+/// 作为`DeserializeSeed`使用，输入的元素数量超过capacity时返回错误
+pub struct WithCapacity<C> {
+    capacity: usize,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C> WithCapacity<C> {
+    pub fn new(capacity: usize) -> Self {
+        WithCapacity {
+            capacity,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// 为线性容器实现序列化，以及基于FromIterator的反序列化
+macro_rules! impl_seq {
+    ($container:ident<$t:ident $(, $extra:ident)*> where $($bound:tt)*) => {
+        impl<$t: Serialize $(, $extra)*> Serialize for $container<$t $(, $extra)*> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_items(serializer, self.len(), self.iter())
+            }
+        }
+
+        impl<'de, $t: Deserialize<'de> $(, $extra)*> Deserialize<'de> for $container<$t $(, $extra)*>
+        where
+            $($bound)*
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(deserialize_items(deserializer, None)?.into_iter().collect())
+            }
+        }
+    };
+}
+
+impl_seq!(Array<T> where);
+impl_seq!(Vector<T> where);
+impl_seq!(Deque<T> where);
+impl_seq!(LinkedList<T> where);
+impl_seq!(DoublyLinkedList<T> where);
+impl_seq!(PersistentList<T> where);
+impl_seq!(PersistentVector<T> where T: Clone);
+
+impl<T: Serialize, C: Compare<T>> Serialize for BinaryHeap<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_items(serializer, self.len(), self.iter())
+    }
+}
+
+/// 反序列化后重新堆化，不依赖输入的顺序
+impl<'de, T: Deserialize<'de>, C: Compare<T> + Default> Deserialize<'de> for BinaryHeap<T, C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(BinaryHeap::from_vec(deserialize_items(deserializer, None)?, C::default()))
+    }
+}
+
+impl<T: Serialize> Serialize for Queue<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_items(serializer, self.len(), self.iter())
+    }
+}
+
+/// 容量等于元素数量，空输入得到容量为1的队列，保证之后仍然可以入队。
+/// 不限制输入的长度，需要限定容量时使用`WithCapacity<Queue<T>>`
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Queue<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items: Vec<T> = deserialize_items(deserializer, None)?;
+        let mut queue = Queue::new(items.len().max(1));
+        items.into_iter().for_each(|item| queue.push(item));
+        Ok(queue)
+    }
+}
+
+impl<T: Serialize, P: GrowthPolicy> Serialize for Stack<T, P> {
+    /// 栈底在前，反序列化时按顺序入栈即可还原
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_items(serializer, self.len(), self.iter().rev())
+    }
+}
+
+/// 得到不限定深度的栈，策略取`P::default()`
+impl<'de, T: Deserialize<'de>, P: GrowthPolicy + Default> Deserialize<'de> for Stack<T, P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut stack = Stack::default();
+        deserialize_items(deserializer, None)?.into_iter().for_each(|item| stack.push(item));
+        Ok(stack)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for WithCapacity<Queue<T>> {
+    type Value = Queue<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut queue = Queue::new(self.capacity);
+        deserialize_items(deserializer, Some(self.capacity))?.into_iter().for_each(|item| queue.push(item));
+        Ok(queue)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for WithCapacity<Deque<T>> {
+    type Value = Deque<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut deque = Deque::new_bind(self.capacity);
+        deserialize_items(deserializer, Some(self.capacity))?.into_iter().for_each(|item| deque.push_back(item));
+        Ok(deque)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, P: GrowthPolicy + Default> DeserializeSeed<'de> for WithCapacity<Stack<T, P>> {
+    type Value = Stack<T, P>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut stack = Stack::with_policy(self.capacity, P::default());
+        deserialize_items(deserializer, Some(self.capacity))?.into_iter().for_each(|item| stack.push(item));
+        Ok(stack)
+    }
+}
+
+/// 有界数组需要`T: Default`填充未写入的位置，创建时按容量分配全部占位元素
+impl<'de, T: Deserialize<'de> + Default> DeserializeSeed<'de> for WithCapacity<Array<T>> {
+    type Value = Array<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let capacity = u32::try_from(self.capacity).map_err(|_| D::Error::custom("array capacity exceeds u32::MAX"))?;
+        let mut array = Array::new_bind(capacity);
+        deserialize_items(deserializer, Some(self.capacity))?.into_iter().for_each(|item| array.push(item));
+        Ok(array)
+    }
+}
+
+impl<K: Serialize, V: Serialize, S> Serialize for HashMap<K, V, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serialize_items(serializer, self.len(), self.iter())
+    }
+}
+
+impl<'de, K, V, S> Deserialize<'de> for HashMap<K, V, S>
+where
+    K: Deserialize<'de> + Hash + Eq,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(deserialize_items::<_, (K, V)>(deserializer, None)?.into_iter().collect())
+    }
+}
+
+impl<K: Serialize, V: Serialize> Serialize for OrderedMap<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_items(serializer, self.len(), self.iter())
+    }
+}
+
+impl<'de, K: Deserialize<'de> + Ord, V: Deserialize<'de>> Deserialize<'de> for OrderedMap<K, V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(deserialize_items::<_, (K, V)>(deserializer, None)?.into_iter().collect())
+    }
+}
+
+impl<V: Serialize> Serialize for Trie<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_items(serializer, self.len(), self.iter())
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Trie<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(deserialize_items::<_, (String, V)>(deserializer, None)?.into_iter().collect())
+    }
+}
+
+/// Graph的序列化格式
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Graph")]
+struct GraphRepr<N, E> {
+    directed: bool,
+    nodes: Vec<N>,
+    edges: Vec<(usize, usize, E)>,
+}
+
+impl<N: Serialize, E: Serialize> Serialize for Graph<N, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphRepr {
+            directed: self.is_directed(),
+            nodes: self.nodes().collect(),
+            edges: self.edges().map(|e| (e.from(), e.to(), e.weight())).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// 边的端点必须是已存在的顶点
+impl<'de, N: Deserialize<'de>, E: Deserialize<'de>> Deserialize<'de> for Graph<N, E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = GraphRepr::<N, E>::deserialize(deserializer)?;
+        let mut graph = if repr.directed {
+            Graph::new_directed()
+        } else {
+            Graph::new_undirected()
+        };
+        let count = repr.nodes.len();
+        repr.nodes.into_iter().for_each(|node| {
+            graph.add_node(node);
+        });
+        for (from, to, weight) in repr.edges {
+            if from >= count || to >= count {
+                return Err(D::Error::custom(format!(
+                    "edge {} -> {} references a missing node (node count is {})",
+                    from, to, count
+                )));
+            }
+            graph.add_edge(from, to, weight);
+        }
+        Ok(graph)
+    }
+}
@@ -0,0 +1,66 @@
+//! # 同步原语
+//! 并发容器统一从这里引入原子类型，使用`--cfg loom`编译时替换为loom的模型检查版本
+//! ```code
+//! RUSTFLAGS="--cfg loom" cargo test -p structure --release --lib loom_
+//! ```
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/20
+//! @version:0.0.1
+//! @description:
+//! ```
+
+#[cfg(loom)]
+pub(crate) use loom::{
+    cell::UnsafeCell,
+    hint::spin_loop,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+    thread::yield_now,
+};
+
+#[cfg(not(loom))]
+pub(crate) use std::{
+    hint::spin_loop,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+    thread::yield_now,
+};
+
+/// 与`loom::cell::UnsafeCell`相同的接口，通过闭包访问内部的裸指针
+#[cfg(not(loom))]
+#[derive(Debug)]
+pub(crate) struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    pub(crate) fn new(data: T) -> Self {
+        UnsafeCell(std::cell::UnsafeCell::new(data))
+    }
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
+
+/// # 退避
+/// 竞争失败或等待时先自旋，次数增加后让出CPU
+pub(crate) struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    const SPIN_LIMIT: u32 = 6;
+
+    pub(crate) fn new() -> Self {
+        Backoff { step: 0 }
+    }
+    pub(crate) fn snooze(&mut self) {
+        // loom需要显式让出才能切换到其他线程
+        if cfg!(loom) || self.step > Self::SPIN_LIMIT {
+            yield_now();
+        } else {
+            for _ in 0..(1 << self.step) {
+                spin_loop();
+            }
+            self.step += 1;
+        }
+    }
+}
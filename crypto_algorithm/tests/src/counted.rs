@@ -0,0 +1,71 @@
+//! # 计数包装
+//! 包装元素，每次通过`Ord`比较都计数一次。不需要改动排序算法，标准库的排序也能统计
+//! ```code
+//! let comparisons = Cell::new(0);
+//! let mut data: Vec<_> = input.into_iter().map(|x| Counted::new(x, &comparisons)).collect();
+//! data.sort();
+//! println!("{} comparisons", comparisons.get());
+//! ```
+//! 元素的移动是按位复制，包装类型看不到交换，交换次数由`SortObserver`统计
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/30
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+/// # 计数包装
+#[derive(Debug, Clone, Copy)]
+pub struct Counted<'a, T> {
+    pub value: T,
+    comparisons: &'a Cell<u64>,
+}
+
+impl<'a, T> Counted<'a, T> {
+    pub fn new(value: T, comparisons: &'a Cell<u64>) -> Self {
+        Counted { value, comparisons }
+    }
+}
+
+impl<T: Ord> Ord for Counted<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.comparisons.set(self.comparisons.get() + 1);
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Ord> PartialOrd for Counted<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for Counted<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Counted<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let comparisons = Cell::new(0);
+        let (a, b) = (Counted::new(1, &comparisons), Counted::new(2, &comparisons));
+        assert!(a < b);
+        assert_ne!(a, b);
+        assert_eq!(Ordering::Greater, b.cmp(&a));
+        assert_eq!(3, comparisons.get());
+        let mut data: Vec<_> = [3, 1, 2].into_iter().map(|x| Counted::new(x, &comparisons)).collect();
+        data.sort();
+        assert_eq!(vec![1, 2, 3], data.iter().map(|c| c.value).collect::<Vec<_>>());
+        assert!(comparisons.get() > 3);
+    }
+}
@@ -0,0 +1,133 @@
+//! # 输入分布
+//! 生成基准测试使用的输入，所有值都在`[0, n)`中，计数排序也能直接使用
+//! ```code
+//! random:     | 3 | 0 | 6 | 2 | 7 | 1 | 4 | 5 |
+//! sorted:     | 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 |
+//! reversed:   | 7 | 6 | 5 | 4 | 3 | 2 | 1 | 0 |
+//! few-unique: | 4 | 0 | 6 | 4 | 0 | 2 | 6 | 2 |     只有8种不同的值
+//! organ-pipe: | 0 | 1 | 2 | 3 | 3 | 2 | 1 | 0 |     先升后降
+//! ```
+//! 同样的n和seed总是生成同样的输入
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/30
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// few-unique中不同值的个数
+const FEW_UNIQUE_VALUES: u64 = 8;
+
+/// # 输入分布
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Distribution {
+    Random,
+    Sorted,
+    Reversed,
+    FewUnique,
+    OrganPipe,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 5] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::FewUnique,
+        Distribution::OrganPipe,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::FewUnique => "few-unique",
+            Distribution::OrganPipe => "organ-pipe",
+        }
+    }
+
+    /// 生成长度为n的输入
+    pub fn generate(self, n: usize, seed: u64) -> Vec<u32> {
+        let mut rng = Lcg(seed);
+        (0..n)
+            .map(|i| match self {
+                Distribution::Random => rng.below(n as u64),
+                Distribution::Sorted => i as u64,
+                Distribution::Reversed => (n - 1 - i) as u64,
+                Distribution::FewUnique => rng.below(FEW_UNIQUE_VALUES) * n as u64 / FEW_UNIQUE_VALUES,
+                Distribution::OrganPipe => i.min(n - 1 - i) as u64,
+            } as u32)
+            .collect()
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Distribution::ALL
+            .into_iter()
+            .find(|d| d.name() == s)
+            .ok_or_else(|| format!("unknown distribution `{}`, expected one of: {}", s, names()))
+    }
+}
+
+/// 所有分布的名称，逗号分隔
+fn names() -> String {
+    Distribution::ALL.map(Distribution::name).join(", ")
+}
+
+/// 线性同余生成器，参数与`benches`中使用的相同
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes() {
+        assert_eq!(vec![0, 1, 2, 3, 4], Distribution::Sorted.generate(5, 0));
+        assert_eq!(vec![4, 3, 2, 1, 0], Distribution::Reversed.generate(5, 0));
+        assert_eq!(vec![0, 1, 2, 2, 1, 0], Distribution::OrganPipe.generate(6, 0));
+        assert_eq!(vec![0, 1, 2, 1, 0], Distribution::OrganPipe.generate(5, 0));
+        for d in Distribution::ALL {
+            assert!(d.generate(0, 7).is_empty());
+            let data = d.generate(1000, 7);
+            assert_eq!(data, d.generate(1000, 7));
+            assert!(data.iter().all(|&x| x < 1000), "{}", d);
+        }
+        let mut few = Distribution::FewUnique.generate(1000, 7);
+        few.sort();
+        few.dedup();
+        assert!(few.len() <= FEW_UNIQUE_VALUES as usize);
+        assert_ne!(Distribution::Random.generate(100, 1), Distribution::Random.generate(100, 2));
+    }
+
+    #[test]
+    fn test_parse() {
+        for d in Distribution::ALL {
+            assert_eq!(Ok(d), d.name().parse());
+        }
+        let err = "zigzag".parse::<Distribution>().unwrap_err();
+        assert!(err.contains("few-unique"), "{}", err);
+    }
+}
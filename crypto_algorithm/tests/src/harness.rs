@@ -0,0 +1,205 @@
+//! # 基准测试
+//! 对每个(规模, 分布, 算法)组合：
+//! 1. 计时：在u32上排序repeat次，取中位数，并检查结果与标准库一致
+//! 2. 计数：在`Counted<u32>`上排序一次，由包装类型统计比较次数，由`SortStats`统计交换与写入次数
+//! ```code
+//! size ─┬─ distribution ─┬─ algorithm ─┬─ time  (u32, repeat次)
+//!       │                │             └─ count (Counted<u32>, SortStats)
+//! ```
+//! 标准库排序只统计比较次数；非比较类排序不比较元素，并行排序在多个线程中比较，都不统计次数
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/30
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use crate::counted::Counted;
+use crate::distribution::Distribution;
+use algorithm::sort::{self, find_sorter, SortStats};
+use serde::Serialize;
+use std::cell::Cell;
+use std::thread;
+use std::time::Instant;
+
+/// 标准库稳定排序
+pub const STD_STABLE: &str = "std_stable";
+/// 标准库不稳定排序
+pub const STD_UNSTABLE: &str = "std_unstable";
+/// 基数排序
+pub const RADIX: &str = "radix";
+/// 计数排序
+pub const COUNTING: &str = "counting";
+/// 并行归并排序
+pub const PAR_MERGE: &str = "par_merge";
+/// 并行快速排序
+pub const PAR_QUICK: &str = "par_quick";
+
+type NamedSort = (&'static str, fn(&mut [u32]));
+
+/// 计时使用的排序函数，直接调用泛型版本，与标准库一样没有动态分发的开销
+const TIMED: [NamedSort; 14] = [
+    ("bubble", sort::bubble_sort),
+    ("insert", sort::insert_sort),
+    ("select", sort::select_sort),
+    ("shell", sort::shell_sort),
+    ("merge", sort::merge_sort),
+    ("merge_bottom_up", sort::merge_sort_bottom_up),
+    ("quick", sort::quick_sort),
+    ("heap", sort::heap_sort),
+    (STD_STABLE, <[u32]>::sort),
+    (STD_UNSTABLE, <[u32]>::sort_unstable),
+    (RADIX, sort::radix_sort),
+    (COUNTING, |list| sort::counting_sort_by_key(list, |&x| x as usize)),
+    (PAR_MERGE, |list| sort::par_merge_sort(list, threads())),
+    (PAR_QUICK, |list| sort::par_quick_sort(list, threads())),
+];
+
+/// 并行排序使用全部CPU
+fn threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// 所有可以测试的算法
+pub fn algorithm_names() -> Vec<&'static str> {
+    TIMED.iter().map(|(name, _)| *name).collect()
+}
+
+/// # 测试配置
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub sizes: Vec<usize>,
+    pub distributions: Vec<Distribution>,
+    pub algorithms: Vec<&'static str>,
+    /// 计时重复的次数
+    pub repeat: usize,
+    pub seed: u64,
+}
+
+/// # 一次测量的结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Measurement {
+    pub algorithm: &'static str,
+    pub distribution: Distribution,
+    pub size: usize,
+    /// 耗时的中位数，纳秒
+    pub nanos: u64,
+    pub comparisons: Option<u64>,
+    pub swaps: Option<u64>,
+    pub writes: Option<u64>,
+}
+
+/// 按规模、分布、算法的顺序依次测量
+/// ## Panics
+/// 算法名称未知，或者排序结果与标准库不一致时panic
+pub fn run(config: &Config) -> Vec<Measurement> {
+    let mut measurements = Vec::new();
+    for &size in &config.sizes {
+        for &distribution in &config.distributions {
+            let input = distribution.generate(size, config.seed);
+            let mut expected = input.clone();
+            expected.sort_unstable();
+            for &algorithm in &config.algorithms {
+                let nanos = time(algorithm, &input, &expected, config.repeat);
+                let (comparisons, swaps, writes) = count(algorithm, &input);
+                measurements.push(Measurement {
+                    algorithm,
+                    distribution,
+                    size,
+                    nanos,
+                    comparisons,
+                    swaps,
+                    writes,
+                });
+            }
+        }
+    }
+    measurements
+}
+
+/// 排序repeat次，返回耗时的中位数
+fn time(algorithm: &str, input: &[u32], expected: &[u32], repeat: usize) -> u64 {
+    let (_, sort) = TIMED
+        .iter()
+        .find(|(name, _)| *name == algorithm)
+        .unwrap_or_else(|| panic!("unknown algorithm `{}`", algorithm));
+    let mut times: Vec<u64> = (0..repeat.max(1))
+        .map(|_| {
+            let mut data = input.to_vec();
+            let start = Instant::now();
+            sort(&mut data);
+            let nanos = start.elapsed().as_nanos() as u64;
+            assert!(data == expected, "{} produced unsorted output", algorithm);
+            nanos
+        })
+        .collect();
+    times.sort_unstable();
+    times[times.len() / 2]
+}
+
+/// 统计比较、交换、写入的次数
+fn count(algorithm: &str, input: &[u32]) -> (Option<u64>, Option<u64>, Option<u64>) {
+    let comparisons = Cell::new(0);
+    let mut data: Vec<Counted<u32>> = input.iter().map(|&x| Counted::new(x, &comparisons)).collect();
+    match algorithm {
+        STD_STABLE => data.sort(),
+        STD_UNSTABLE => data.sort_unstable(),
+        // 包装类型的计数器不能跨线程共享
+        RADIX | COUNTING | PAR_MERGE | PAR_QUICK => return (None, None, None),
+        _ => {
+            let sorter = find_sorter::<Counted<u32>>(algorithm).unwrap_or_else(|| panic!("unknown algorithm `{}`", algorithm));
+            let mut stats = SortStats::default();
+            sorter.sort_observed(&mut data, &mut Counted::cmp, &mut stats);
+            debug_assert_eq!(stats.comparisons, comparisons.get());
+            return (Some(comparisons.get()), Some(stats.swaps), Some(stats.writes));
+        }
+    }
+    (Some(comparisons.get()), None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algorithm::sort::sorters;
+
+    #[test]
+    fn test_every_sorter_is_timed() {
+        let names = algorithm_names();
+        for sorter in sorters::<u32>() {
+            assert!(names.contains(&sorter.name()), "{} is missing", sorter.name());
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let config = Config {
+            sizes: vec![0, 64],
+            distributions: Distribution::ALL.to_vec(),
+            algorithms: algorithm_names(),
+            repeat: 3,
+            seed: 1,
+        };
+        let measurements = run(&config);
+        assert_eq!(2 * 5 * algorithm_names().len(), measurements.len());
+        let find = |algorithm: &str, distribution: Distribution| {
+            measurements
+                .iter()
+                .find(|m| m.algorithm == algorithm && m.distribution == distribution && m.size == 64)
+                .unwrap()
+        };
+        // 有序输入：冒泡排序一趟比较n-1次，不交换
+        let bubble = find("bubble", Distribution::Sorted);
+        assert_eq!((Some(63), Some(0), Some(0)), (bubble.comparisons, bubble.swaps, bubble.writes));
+        // 逆序输入：冒泡排序交换n(n-1)/2次
+        assert_eq!(Some(64 * 63 / 2), find("bubble", Distribution::Reversed).swaps);
+        // 归并排序在小段上插入排序（交换），归并时移动元素（写入）
+        let merge = find("merge", Distribution::Random);
+        assert!(merge.swaps.unwrap() > 0 && merge.writes.unwrap() > 0);
+        assert_eq!(Some(0), find("select", Distribution::Random).writes);
+        let std = find(STD_STABLE, Distribution::Random);
+        assert!(std.comparisons.unwrap() > 0);
+        assert_eq!(None, std.swaps);
+        let radix = find(RADIX, Distribution::FewUnique);
+        assert_eq!((None, None, None), (radix.comparisons, radix.swaps, radix.writes));
+    }
+}
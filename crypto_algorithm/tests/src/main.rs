@@ -1,13 +1,151 @@
+//! # 排序基准测试
+//! 在不同规模、不同分布的输入上测试`algorithm::sort`中的排序算法，统计耗时、比较次数和交换次数，
+//! 用来为不同形态的数据选择算法
+//! ```code
+//! cargo run --release -p tests -- --sizes 1000,100000 --distributions random,few-unique
+//! cargo run --release -p tests -- --algorithms quick,merge,std_unstable --format csv --output sort.csv
+//! cargo run --release -p tests -- --list
+//! ```
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/30
+//! @version:0.0.1
+//! @description:
+//! ```
 
+mod counted;
+mod distribution;
+mod harness;
+mod report;
 
-mod apis;
+use clap::{value_parser, Arg, ArgAction, Command};
+use distribution::Distribution;
+use harness::Config;
+use report::Format;
+use std::process::ExitCode;
 
-// use crate::apis::{test_stack,test_queue};
-// use structure::{LinkedList};
+fn command() -> Command {
+    Command::new("tests")
+        .about("Benchmark the algorithm::sort algorithms on different input distributions")
+        .arg(
+            Arg::new("sizes")
+                .short('n')
+                .long("sizes")
+                .value_delimiter(',')
+                .value_parser(value_parser!(usize))
+                .default_value("1000,10000")
+                .help("Input sizes, comma separated"),
+        )
+        .arg(
+            Arg::new("distributions")
+                .short('d')
+                .long("distributions")
+                .value_delimiter(',')
+                .value_parser(|s: &str| s.parse::<Distribution>())
+                .help("Input distributions, comma separated [default: all]"),
+        )
+        .arg(
+            Arg::new("algorithms")
+                .short('a')
+                .long("algorithms")
+                .value_delimiter(',')
+                .value_parser(parse_algorithm)
+                .help("Algorithms to run, comma separated [default: all]"),
+        )
+        .arg(
+            Arg::new("repeat")
+                .short('r')
+                .long("repeat")
+                .value_parser(value_parser!(usize))
+                .default_value("5")
+                .help("Timed runs per measurement, the median is reported"),
+        )
+        .arg(
+            Arg::new("seed")
+                .short('s')
+                .long("seed")
+                .value_parser(value_parser!(u64))
+                .default_value("42")
+                .help("Seed for the random distributions"),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(|s: &str| s.parse::<Format>())
+                .default_value("table")
+                .help("Report format: table, csv or json"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .help("Write the report to a file instead of stdout"),
+        )
+        .arg(
+            Arg::new("list")
+                .long("list")
+                .action(ArgAction::SetTrue)
+                .help("List the algorithms and distributions, then exit"),
+        )
+}
+
+fn parse_algorithm(s: &str) -> Result<&'static str, String> {
+    let names = harness::algorithm_names();
+    names
+        .iter()
+        .find(|name| **name == s)
+        .copied()
+        .ok_or_else(|| format!("unknown algorithm `{}`, expected one of: {}", s, names.join(", ")))
+}
 
-use std::collections::HashMap;
-use std::ops::Add;
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+    if matches.get_flag("list") {
+        println!("algorithms: {}", harness::algorithm_names().join(", "));
+        println!("distributions: {}", Distribution::ALL.map(Distribution::name).join(", "));
+        return ExitCode::SUCCESS;
+    }
+    let config = Config {
+        sizes: matches.get_many("sizes").unwrap().copied().collect(),
+        distributions: match matches.get_many("distributions") {
+            Some(distributions) => distributions.copied().collect(),
+            None => Distribution::ALL.to_vec(),
+        },
+        algorithms: match matches.get_many("algorithms") {
+            Some(algorithms) => algorithms.copied().collect(),
+            None => harness::algorithm_names(),
+        },
+        repeat: *matches.get_one("repeat").unwrap(),
+        seed: *matches.get_one("seed").unwrap(),
+    };
+    let format: Format = *matches.get_one("format").unwrap();
+    let report = report::render(format, &harness::run(&config));
+    match matches.get_one::<String>("output") {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, report) {
+                eprintln!("failed to write {}: {}", path, e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", report),
+    }
+    ExitCode::SUCCESS
+}
 
-fn main(){
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn test_command() {
+        command().debug_assert();
+        let matches = command().try_get_matches_from(["tests", "-n", "10,20", "-d", "sorted,organ-pipe", "-a", "quick"]).unwrap();
+        let sizes: Vec<usize> = matches.get_many("sizes").unwrap().copied().collect();
+        assert_eq!(vec![10, 20], sizes);
+        let distributions: Vec<Distribution> = matches.get_many("distributions").unwrap().copied().collect();
+        assert_eq!(vec![Distribution::Sorted, Distribution::OrganPipe], distributions);
+        assert!(command().try_get_matches_from(["tests", "-a", "bogo"]).is_err());
+        assert!(command().try_get_matches_from(["tests", "-f", "xml"]).is_err());
+    }
 }
@@ -0,0 +1,163 @@
+//! # 报告
+//! 把测量结果输出为表格、CSV或JSON
+//! ```code
+//! algorithm,distribution,size,nanos,comparisons,swaps,writes
+//! quick,random,1000,31250,10984,2519,0
+//! std_stable,random,1000,24000,8707,,
+//! ```
+//! 没有统计的次数在CSV中为空，在JSON中为null
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/30
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use crate::harness::Measurement;
+use std::fmt::Write;
+use std::str::FromStr;
+
+const HEADER: [&str; 7] = ["algorithm", "distribution", "size", "nanos", "comparisons", "swaps", "writes"];
+
+/// # 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{}`, expected one of: table, csv, json", s)),
+        }
+    }
+}
+
+pub fn render(format: Format, measurements: &[Measurement]) -> String {
+    match format {
+        Format::Table => table(measurements),
+        Format::Csv => csv(measurements),
+        Format::Json => serde_json::to_string_pretty(measurements).expect("measurements are always serializable") + "\n",
+    }
+}
+
+/// 每个测量结果的各列
+fn cells(m: &Measurement) -> [String; 7] {
+    let count = |c: Option<u64>| c.map(|c| c.to_string()).unwrap_or_default();
+    [
+        m.algorithm.to_string(),
+        m.distribution.to_string(),
+        m.size.to_string(),
+        m.nanos.to_string(),
+        count(m.comparisons),
+        count(m.swaps),
+        count(m.writes),
+    ]
+}
+
+fn csv(measurements: &[Measurement]) -> String {
+    let mut out = HEADER.join(",") + "\n";
+    for m in measurements {
+        out += &cells(m).join(",");
+        out.push('\n');
+    }
+    out
+}
+
+/// 对齐的表格，名称左对齐，数字右对齐，没有统计的次数显示为`-`
+fn table(measurements: &[Measurement]) -> String {
+    let rows: Vec<[String; 7]> = measurements
+        .iter()
+        .map(|m| cells(m).map(|c| if c.is_empty() { "-".to_string() } else { c }))
+        .collect();
+    let mut widths = HEADER.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut out = String::new();
+    let mut line = |row: &[String]| {
+        for (i, cell) in row.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "  " };
+            if i < 2 {
+                let _ = write!(out, "{}{:<w$}", sep, cell, w = widths[i]);
+            } else {
+                let _ = write!(out, "{}{:>w$}", sep, cell, w = widths[i]);
+            }
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+    };
+    line(&HEADER.map(String::from));
+    for row in &rows {
+        line(row);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Distribution;
+
+    fn measurements() -> Vec<Measurement> {
+        vec![
+            Measurement {
+                algorithm: "quick",
+                distribution: Distribution::FewUnique,
+                size: 1000,
+                nanos: 31250,
+                comparisons: Some(10984),
+                swaps: Some(2519),
+                writes: Some(0),
+            },
+            Measurement {
+                algorithm: "std_stable",
+                distribution: Distribution::Random,
+                size: 1000,
+                nanos: 24000,
+                comparisons: Some(8707),
+                swaps: None,
+                writes: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "algorithm,distribution,size,nanos,comparisons,swaps,writes\n\
+             quick,few-unique,1000,31250,10984,2519,0\n\
+             std_stable,random,1000,24000,8707,,\n",
+            render(Format::Csv, &measurements())
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            "algorithm   distribution  size  nanos  comparisons  swaps  writes\n\
+             quick       few-unique    1000  31250        10984   2519       0\n\
+             std_stable  random        1000  24000         8707      -       -\n",
+            render(Format::Table, &measurements())
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value = serde_json::from_str(&render(Format::Json, &measurements())).unwrap();
+        assert_eq!("few-unique", json[0]["distribution"]);
+        assert_eq!(2519, json[0]["swaps"]);
+        assert!(json[1]["swaps"].is_null());
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
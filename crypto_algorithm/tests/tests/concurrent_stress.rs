@@ -0,0 +1,110 @@
+//! # 并发容器压力测试
+//! 多个生产者和消费者同时读写，检查每个元素恰好被取出一次
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/20
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use structure::{BoundedMpmcQueue, ConcurrentStack};
+
+const PRODUCERS: usize = 4;
+const CONSUMERS: usize = 4;
+const PER_PRODUCER: usize = 100_000;
+
+/// 生产者i写入`i * PER_PRODUCER..(i + 1) * PER_PRODUCER`，消费者记录取到的所有元素
+fn check_all_received(mut received: Vec<usize>) {
+    received.sort_unstable();
+    assert_eq!((0..PRODUCERS * PER_PRODUCER).collect::<Vec<_>>(), received);
+}
+
+#[test]
+fn stress_mpmc_queue() {
+    let queue = Arc::new(BoundedMpmcQueue::new(64));
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|p| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                for i in p * PER_PRODUCER..(p + 1) * PER_PRODUCER {
+                    // 交替使用阻塞和非阻塞入队
+                    if i % 2 == 0 {
+                        queue.push(i);
+                    } else {
+                        let mut item = i;
+                        while let Err(rejected) = queue.try_push(item) {
+                            item = rejected;
+                            thread::yield_now();
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..CONSUMERS)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                let mut received = Vec::new();
+                let mut last_per_producer = [None; PRODUCERS];
+                for _ in 0..PRODUCERS * PER_PRODUCER / CONSUMERS {
+                    let item = queue.pop();
+                    // 同一个生产者的元素按入队顺序出队
+                    let producer = item / PER_PRODUCER;
+                    assert!(last_per_producer[producer] < Some(item));
+                    last_per_producer[producer] = Some(item);
+                    received.push(item);
+                }
+                received
+            })
+        })
+        .collect();
+    producers.into_iter().for_each(|h| h.join().unwrap());
+    let received = consumers.into_iter().flat_map(|h| h.join().unwrap()).collect();
+    check_all_received(received);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn stress_concurrent_stack() {
+    let stack = Arc::new(ConcurrentStack::new());
+    let popped = Arc::new(AtomicUsize::new(0));
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|p| {
+            let stack = Arc::clone(&stack);
+            thread::spawn(move || (p * PER_PRODUCER..(p + 1) * PER_PRODUCER).for_each(|i| stack.push(i)))
+        })
+        .collect();
+    let consumers: Vec<_> = (0..CONSUMERS)
+        .map(|c| {
+            let stack = Arc::clone(&stack);
+            let popped = Arc::clone(&popped);
+            thread::spawn(move || {
+                let mut received = Vec::new();
+                // 先领取一个名额再出队，名额总数等于元素总数，阻塞出队不会永远等待
+                while popped.fetch_add(1, Ordering::Relaxed) < PRODUCERS * PER_PRODUCER {
+                    // 交替使用阻塞和非阻塞出队
+                    let item = if c % 2 == 0 {
+                        stack.pop()
+                    } else {
+                        loop {
+                            if let Some(item) = stack.try_pop() {
+                                break item;
+                            }
+                            thread::yield_now();
+                        }
+                    };
+                    received.push(item);
+                }
+                received
+            })
+        })
+        .collect();
+    producers.into_iter().for_each(|h| h.join().unwrap());
+    let received = consumers.into_iter().flat_map(|h| h.join().unwrap()).collect();
+    check_all_received(received);
+    assert!(stack.is_empty());
+}
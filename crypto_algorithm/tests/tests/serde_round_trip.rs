@@ -0,0 +1,192 @@
+//! # 序列化测试
+//! 每个容器分别经过JSON（自描述格式）和bincode（带长度前缀的二进制格式）往返
+//! ```txt
+//! @author:syf20020816@Outlook.com
+//! @date:2023/10/26
+//! @version:0.0.1
+//! @description:
+//! ```
+
+use bincode::Options;
+use serde::de::{DeserializeOwned, DeserializeSeed};
+use serde::Serialize;
+use std::fmt::Debug;
+use structure::rs_array::Array;
+use structure::{
+    BinaryHeap, Deque, Doubling, DoublyLinkedList, Graph, HashMap, LinkedList, Min, OrderedMap, PersistentList,
+    PersistentVector, Queue, Stack, Trie, Vector, WithCapacity,
+};
+
+/// 分别通过JSON和bincode往返一次
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> (T, T) {
+    let json = serde_json::to_string(value).unwrap();
+    let bytes = bincode::serialize(value).unwrap();
+    (serde_json::from_str(&json).unwrap(), bincode::deserialize(&bytes).unwrap())
+}
+
+fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let (from_json, from_bincode) = round_trip(&value);
+    assert_eq!(value, from_json);
+    assert_eq!(value, from_bincode);
+}
+
+#[test]
+fn test_linear_containers() {
+    assert_round_trip((0..10).collect::<Vector<u32>>());
+    assert_round_trip((0..10).collect::<LinkedList<u32>>());
+    assert_round_trip((0..10).collect::<DoublyLinkedList<u32>>());
+    assert_round_trip((0..10).collect::<PersistentList<u32>>());
+    assert_round_trip((0..100).collect::<PersistentVector<u32>>());
+    assert_round_trip(PersistentVector::<u32>::new());
+    let mut deque: Deque<String> = Deque::new();
+    deque.push_back("b".to_string());
+    deque.push_front("a".to_string());
+    deque.push_back("c".to_string());
+    assert_round_trip(deque);
+    let (from_json, from_bincode) = round_trip(&(0..5).collect::<Array<u32>>());
+    assert!(from_json.iter().eq(&[0, 1, 2, 3, 4]));
+    assert!(from_bincode.iter().eq(&[0, 1, 2, 3, 4]));
+}
+
+#[test]
+fn test_queue_keeps_order() {
+    let mut queue = Queue::new(4);
+    (0..4).for_each(|i| queue.push(i));
+    queue.pop();
+    queue.push(4);
+    assert_eq!("[1,2,3,4]", serde_json::to_string(&queue).unwrap());
+    let (mut from_json, from_bincode) = round_trip(&queue);
+    assert!(from_bincode.iter().eq(queue.iter()));
+    // 直接反序列化时容量等于元素数量
+    assert!(from_json.is_full());
+    assert_eq!(Some(1), from_json.pop());
+}
+
+#[test]
+fn test_empty_queue_stays_usable() {
+    let (mut from_json, mut from_bincode) = round_trip(&Queue::<u32>::new(5));
+    for queue in [&mut from_json, &mut from_bincode] {
+        assert!(queue.is_empty());
+        assert_eq!(1, queue.capacity());
+        assert_eq!(Ok(()), queue.try_push(1));
+    }
+}
+
+#[test]
+fn test_bounded_array_skips_placeholders() {
+    let mut array = Array::<u32>::new_bind(3);
+    array.push(7);
+    assert_eq!("[7]", serde_json::to_string(&array).unwrap());
+    let (from_json, from_bincode) = round_trip(&array);
+    assert!(from_json.iter().eq(&[7]));
+    assert_eq!(1, *from_bincode.get_current());
+}
+
+#[test]
+fn test_stack_keeps_order() {
+    let mut stack = Stack::new();
+    (0..5).for_each(|i| stack.push(i));
+    // 栈底在前
+    assert_eq!("[0,1,2,3,4]", serde_json::to_string(&stack).unwrap());
+    let (mut from_json, mut from_bincode) = round_trip(&stack);
+    assert_eq!(Some(4), from_json.pop());
+    assert_eq!(Some(4), from_bincode.pop());
+    assert!(from_json.iter().eq(&[3, 2, 1, 0]));
+}
+
+#[test]
+fn test_binary_heap() {
+    let heap: BinaryHeap<u32, Min> = BinaryHeap::from_vec(vec![5, 1, 4, 2, 3], Min);
+    let (from_json, from_bincode) = round_trip(&heap);
+    let sorted = heap.into_sorted_vec();
+    assert_eq!(sorted, from_json.into_sorted_vec());
+    assert_eq!(sorted, from_bincode.into_sorted_vec());
+    // 任意顺序的输入也会重新堆化
+    let heap: BinaryHeap<u32, Min> = serde_json::from_str("[9, 3, 7]").unwrap();
+    assert_eq!(Some(&3), heap.peek());
+}
+
+#[test]
+fn test_maps() {
+    assert_round_trip((0..20).map(|i| (i, i * i)).collect::<HashMap<u32, u32>>());
+    assert_round_trip((0..20).map(|i| (i.to_string(), i)).collect::<OrderedMap<String, u32>>());
+    let trie: Trie<u32> = [("tea", 1), ("ten", 2), ("to", 3), ("", 0)].into_iter().collect();
+    assert_eq!(r#"[["",0],["tea",1],["ten",2],["to",3]]"#, serde_json::to_string(&trie).unwrap());
+    assert_round_trip(trie);
+}
+
+#[test]
+fn test_graph() {
+    let mut graph = Graph::new_undirected();
+    let a = graph.add_node("a".to_string());
+    let b = graph.add_node("b".to_string());
+    let c = graph.add_node("c".to_string());
+    graph.add_edge(a, b, 1);
+    graph.add_edge(b, c, 2);
+    graph.add_edge(c, c, 3);
+    let json = serde_json::to_string(&graph).unwrap();
+    assert_eq!(r#"{"directed":false,"nodes":["a","b","c"],"edges":[[0,1,1],[1,2,2],[2,2,3]]}"#, json);
+    for restored in [
+        serde_json::from_str::<Graph<String, i32>>(&json).unwrap(),
+        bincode::deserialize(&bincode::serialize(&graph).unwrap()).unwrap(),
+    ] {
+        assert!(!restored.is_directed());
+        assert!(restored.nodes().eq(graph.nodes()));
+        assert_eq!(graph.edge_count(), restored.edge_count());
+        assert_eq!(graph.degree(b), restored.degree(b));
+        assert_eq!(graph.neighbors(c).collect::<Vec<_>>(), restored.neighbors(c).collect::<Vec<_>>());
+    }
+    let err = serde_json::from_str::<Graph<u8, u8>>(r#"{"directed":true,"nodes":[0],"edges":[[0,1,7]]}"#).unwrap_err();
+    assert!(err.to_string().contains("missing node"), "{}", err);
+}
+
+#[test]
+fn test_with_capacity() {
+    let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+    let queue = WithCapacity::<Queue<u32>>::new(5).deserialize(&mut de).unwrap();
+    assert_eq!(5, queue.capacity());
+    assert!(queue.iter().eq(&[1, 2, 3]));
+
+    let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+    let deque = WithCapacity::<Deque<u32>>::new(3).deserialize(&mut de).unwrap();
+    assert!(deque.is_full());
+
+    let mut de = serde_json::Deserializer::from_str("[1, 2]");
+    let mut stack = WithCapacity::<Stack<u32, Doubling>>::new(2).deserialize(&mut de).unwrap();
+    assert_eq!(Some(&2), stack.peek());
+    assert_eq!(Some(2), stack.max_depth());
+    stack.push(3);
+
+    // 容量只是上限，不会按不可信的容量预先分配
+    let mut de = serde_json::Deserializer::from_str("[1]");
+    let stack = WithCapacity::<Stack<u32>>::new(usize::MAX).deserialize(&mut de).unwrap();
+    assert_eq!(Some(usize::MAX), stack.max_depth());
+
+    let mut de = serde_json::Deserializer::from_str("[7]");
+    let array = WithCapacity::<Array<u32>>::new(3).deserialize(&mut de).unwrap();
+    assert_eq!(Some(3), array.capacity());
+    assert_eq!(1, *array.get_current());
+}
+
+#[test]
+fn test_with_capacity_rejects_oversized_input() {
+    // 自描述格式：读到超出的元素时报错
+    let mut de = serde_json::Deserializer::from_str("[1, 2, 3, 4]");
+    let err = WithCapacity::<Queue<u32>>::new(3).deserialize(&mut de).unwrap_err();
+    assert!(err.to_string().contains("at most 3 elements"), "{}", err);
+    let mut de = serde_json::Deserializer::from_str("[1, 2]");
+    assert!(WithCapacity::<Stack<u32>>::new(1).deserialize(&mut de).is_err());
+    let mut de = serde_json::Deserializer::from_str("[1, 2]");
+    assert!(WithCapacity::<Deque<u32>>::new(1).deserialize(&mut de).is_err());
+    let mut de = serde_json::Deserializer::from_str("[1, 2]");
+    assert!(WithCapacity::<Array<u32>>::new(1).deserialize(&mut de).is_err());
+
+    // 带长度前缀的格式：读取元素之前就根据长度拒绝
+    let bytes = bincode::serialize(&vec![1_u32; 10]).unwrap();
+    let mut de = bincode::Deserializer::from_slice(&bytes, bincode::options().with_fixint_encoding());
+    let err = WithCapacity::<Queue<u32>>::new(9).deserialize(&mut de).unwrap_err();
+    assert!(err.to_string().contains("invalid length 10"), "{}", err);
+    let mut de = bincode::Deserializer::from_slice(&bytes, bincode::options().with_fixint_encoding());
+    let queue = WithCapacity::<Queue<u32>>::new(10).deserialize(&mut de).unwrap();
+    assert!(queue.is_full());
+}